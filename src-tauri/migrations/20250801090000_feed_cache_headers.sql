@@ -0,0 +1,3 @@
+-- RSS源条件请求缓存头
+ALTER TABLE rss_feeds ADD COLUMN etag TEXT;
+ALTER TABLE rss_feeds ADD COLUMN last_modified TEXT;
@@ -0,0 +1,2 @@
+-- 文章级阅读模式偏好（JSON），为空时使用全局阅读主题
+ALTER TABLE rss_articles ADD COLUMN reader_prefs TEXT;
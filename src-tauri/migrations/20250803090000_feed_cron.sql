@@ -0,0 +1,2 @@
+-- RSS源自定义抓取计划（cron表达式），为空时使用固定刷新间隔
+ALTER TABLE rss_feeds ADD COLUMN cron TEXT;
@@ -0,0 +1,3 @@
+-- 文章阅读时间
+ALTER TABLE rss_articles ADD COLUMN read_at TEXT;
+CREATE INDEX IF NOT EXISTS idx_rss_articles_read_at ON rss_articles(read_at);
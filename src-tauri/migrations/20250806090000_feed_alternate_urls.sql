@@ -0,0 +1,4 @@
+-- RSS源备用地址（JSON数组），主地址失败时依次尝试
+ALTER TABLE rss_feeds ADD COLUMN alternate_urls TEXT;
+-- 最近一次成功抓取所用的地址
+ALTER TABLE rss_feeds ADD COLUMN last_fetched_url TEXT;
@@ -0,0 +1,29 @@
+-- 文章全文搜索索引（外部内容表，通过触发器与rss_articles保持同步）
+CREATE VIRTUAL TABLE IF NOT EXISTS rss_articles_fts USING fts5(
+    title,
+    description,
+    content,
+    author,
+    content = 'rss_articles',
+    content_rowid = 'rowid'
+);
+
+CREATE TRIGGER IF NOT EXISTS rss_articles_fts_ai AFTER INSERT ON rss_articles BEGIN
+    INSERT INTO rss_articles_fts (rowid, title, description, content, author)
+    VALUES (new.rowid, new.title, new.description, new.content, new.author);
+END;
+
+CREATE TRIGGER IF NOT EXISTS rss_articles_fts_ad AFTER DELETE ON rss_articles BEGIN
+    INSERT INTO rss_articles_fts (rss_articles_fts, rowid, title, description, content, author)
+    VALUES ('delete', old.rowid, old.title, old.description, old.content, old.author);
+END;
+
+CREATE TRIGGER IF NOT EXISTS rss_articles_fts_au AFTER UPDATE OF title, description, content, author ON rss_articles BEGIN
+    INSERT INTO rss_articles_fts (rss_articles_fts, rowid, title, description, content, author)
+    VALUES ('delete', old.rowid, old.title, old.description, old.content, old.author);
+    INSERT INTO rss_articles_fts (rowid, title, description, content, author)
+    VALUES (new.rowid, new.title, new.description, new.content, new.author);
+END;
+
+-- 为已有文章建立索引
+INSERT INTO rss_articles_fts (rss_articles_fts) VALUES ('rebuild');
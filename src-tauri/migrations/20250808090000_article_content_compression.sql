@@ -0,0 +1,4 @@
+-- 压缩存储的文章内容（gzip），此时content为空
+ALTER TABLE rss_articles ADD COLUMN content_compressed BLOB;
+-- 压缩算法标记，为空表示内容以明文存储在content中
+ALTER TABLE rss_articles ADD COLUMN content_compression TEXT;
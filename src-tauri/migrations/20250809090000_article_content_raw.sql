@@ -0,0 +1,2 @@
+-- 未经处理的原始文章内容，仅在开启“保留原始内容”调试选项时写入
+ALTER TABLE rss_articles ADD COLUMN content_raw TEXT;
@@ -0,0 +1,2 @@
+-- RSS源时区（IANA名称），用于解释不带时区信息的发布时间，为空时按UTC处理
+ALTER TABLE rss_feeds ADD COLUMN timezone TEXT;
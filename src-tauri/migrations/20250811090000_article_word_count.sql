@@ -0,0 +1,3 @@
+-- 文章字数（中日韩文字按字计数），用于区分长文和短讯
+ALTER TABLE rss_articles ADD COLUMN word_count INTEGER;
+CREATE INDEX IF NOT EXISTS idx_rss_articles_word_count ON rss_articles(word_count);
@@ -0,0 +1,4 @@
+-- RSS源文章标题需去除的前缀（正则表达式，从标题开头匹配）
+ALTER TABLE rss_feeds ADD COLUMN title_strip_prefix TEXT;
+-- 去除前缀之前的原始标题，未处理时为空
+ALTER TABLE rss_articles ADD COLUMN original_title TEXT;
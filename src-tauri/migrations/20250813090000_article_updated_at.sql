@@ -0,0 +1,3 @@
+-- 文章在源中的最后修改时间（区别于记录的创建时间）
+ALTER TABLE rss_articles ADD COLUMN updated_at TEXT;
+CREATE INDEX IF NOT EXISTS idx_rss_articles_updated_at ON rss_articles(updated_at);
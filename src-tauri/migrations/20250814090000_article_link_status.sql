@@ -0,0 +1,3 @@
+-- 文章链接检查结果（ok / not_found / gone / error）及检查时间
+ALTER TABLE rss_articles ADD COLUMN link_status TEXT;
+ALTER TABLE rss_articles ADD COLUMN link_checked_at TEXT;
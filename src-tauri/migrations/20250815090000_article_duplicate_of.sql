@@ -0,0 +1,3 @@
+-- 疑似重复文章指向的原文章，等待用户确认或忽略
+ALTER TABLE rss_articles ADD COLUMN duplicate_of TEXT;
+CREATE INDEX IF NOT EXISTS idx_rss_articles_duplicate_of ON rss_articles(duplicate_of);
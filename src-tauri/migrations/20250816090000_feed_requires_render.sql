@@ -0,0 +1,2 @@
+-- 需要执行脚本才能得到正文的源，提取全文时使用webview渲染
+ALTER TABLE rss_feeds ADD COLUMN requires_render BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- 单个RSS源保存文章内容的最大字节数，超出部分截断（为空表示不限制）
+ALTER TABLE rss_feeds ADD COLUMN max_content_bytes INTEGER;
@@ -0,0 +1,2 @@
+-- 最近一次抓取到的源格式（如 RSS 2.0、Atom），用于发现源改版
+ALTER TABLE rss_feeds ADD COLUMN feed_format TEXT;
@@ -0,0 +1,3 @@
+-- 文章最近一次出现在源内容中的时间，用于发现从源中移除的文章
+ALTER TABLE rss_articles ADD COLUMN last_seen_at TEXT;
+CREATE INDEX IF NOT EXISTS idx_rss_articles_feed_last_seen ON rss_articles(feed_id, last_seen_at);
@@ -0,0 +1,9 @@
+-- RSS源分类（名称不区分大小写）
+CREATE TABLE IF NOT EXISTS categories (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    created_at TEXT NOT NULL
+);
+
+ALTER TABLE rss_feeds ADD COLUMN category_id TEXT REFERENCES categories(id) ON DELETE SET NULL;
+CREATE INDEX IF NOT EXISTS idx_rss_feeds_category_id ON rss_feeds(category_id);
@@ -0,0 +1,22 @@
+-- 每个RSS源的未读文章数缓存，通过触发器随文章的增删和已读状态变化增量更新
+ALTER TABLE rss_feeds ADD COLUMN unread_count INTEGER NOT NULL DEFAULT 0;
+
+CREATE TRIGGER IF NOT EXISTS rss_articles_unread_ai AFTER INSERT ON rss_articles
+WHEN new.is_read = 0 BEGIN
+    UPDATE rss_feeds SET unread_count = unread_count + 1 WHERE id = new.feed_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS rss_articles_unread_ad AFTER DELETE ON rss_articles
+WHEN old.is_read = 0 BEGIN
+    UPDATE rss_feeds SET unread_count = unread_count - 1 WHERE id = old.feed_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS rss_articles_unread_au AFTER UPDATE OF is_read, feed_id ON rss_articles BEGIN
+    UPDATE rss_feeds SET unread_count = unread_count - (old.is_read = 0) WHERE id = old.feed_id;
+    UPDATE rss_feeds SET unread_count = unread_count + (new.is_read = 0) WHERE id = new.feed_id;
+END;
+
+-- 统计已有文章
+UPDATE rss_feeds SET unread_count = (
+    SELECT COUNT(*) FROM rss_articles WHERE rss_articles.feed_id = rss_feeds.id AND rss_articles.is_read = 0
+);
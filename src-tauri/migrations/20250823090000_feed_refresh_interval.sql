@@ -0,0 +1,2 @@
+-- 单个RSS源的自动刷新间隔（分钟），为空时使用全局刷新间隔
+ALTER TABLE rss_feeds ADD COLUMN refresh_interval_minutes INTEGER;
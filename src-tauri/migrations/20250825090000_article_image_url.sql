@@ -0,0 +1,2 @@
+-- 文章题图地址，来自源中的媒体信息、正文中的第一张图片或文章页面的og:image
+ALTER TABLE rss_articles ADD COLUMN image_url TEXT;
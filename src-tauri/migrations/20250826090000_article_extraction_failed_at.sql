@@ -0,0 +1,2 @@
+-- 最近一次全文提取失败的时间，冷却期内打开文章不再重复抓取
+ALTER TABLE rss_articles ADD COLUMN extraction_failed_at TEXT;
@@ -0,0 +1,4 @@
+-- RSS源的网站图标：图标地址、base64编码的图标数据和最近一次抓取时间
+ALTER TABLE rss_feeds ADD COLUMN favicon_url TEXT;
+ALTER TABLE rss_feeds ADD COLUMN favicon_data TEXT;
+ALTER TABLE rss_feeds ADD COLUMN favicon_checked_at TEXT;
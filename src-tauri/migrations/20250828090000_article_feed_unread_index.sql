@@ -0,0 +1,2 @@
+-- 按RSS源统计未读数时使用
+CREATE INDEX IF NOT EXISTS idx_rss_articles_feed_is_read ON rss_articles(feed_id, is_read);
@@ -0,0 +1,3 @@
+-- 规范化后的文章链接，用于跨RSS源合并重复文章，由应用写入和回填
+ALTER TABLE rss_articles ADD COLUMN link_key TEXT;
+CREATE INDEX IF NOT EXISTS idx_rss_articles_link_key ON rss_articles(link_key);
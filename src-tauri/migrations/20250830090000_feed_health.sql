@@ -0,0 +1,4 @@
+-- RSS源的健康状态：最近一次刷新失败的错误和时间，以及连续失败次数
+ALTER TABLE rss_feeds ADD COLUMN last_error TEXT;
+ALTER TABLE rss_feeds ADD COLUMN last_error_at TEXT;
+ALTER TABLE rss_feeds ADD COLUMN consecutive_failures INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,7 @@
+-- 按域名覆盖全文提取方式的站点规则，子域名同样适用
+CREATE TABLE IF NOT EXISTS site_rules (
+    domain TEXT PRIMARY KEY,
+    strategy TEXT NOT NULL,
+    selector TEXT,
+    created_at TEXT NOT NULL
+);
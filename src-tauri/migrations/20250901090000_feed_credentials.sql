@@ -0,0 +1,4 @@
+-- 需要HTTP Basic认证的RSS源的用户名和密码
+-- 密码以明文保存在本地数据库中，数据库文件和备份需要妥善保管
+ALTER TABLE rss_feeds ADD COLUMN username TEXT;
+ALTER TABLE rss_feeds ADD COLUMN password TEXT;
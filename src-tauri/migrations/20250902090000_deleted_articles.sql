@@ -0,0 +1,10 @@
+-- 被清理或确认为重复而删除的文章，刷新时跳过源中仍然存在的这些文章，避免重新作为未读文章出现
+CREATE TABLE IF NOT EXISTS deleted_articles (
+    feed_id TEXT NOT NULL,
+    guid TEXT,
+    link_key TEXT,
+    deleted_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_deleted_articles_feed_guid ON deleted_articles(feed_id, guid);
+CREATE INDEX IF NOT EXISTS idx_deleted_articles_feed_link_key ON deleted_articles(feed_id, link_key);
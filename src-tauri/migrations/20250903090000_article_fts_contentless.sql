@@ -0,0 +1,35 @@
+-- 全文索引改为不保存内容的表（contentless_delete），索引值不必与rss_articles中的列一致
+-- 压缩存储的文章content为空，其正文由应用解压后写入索引，触发器只维护明文存储的文章
+DROP TRIGGER IF EXISTS rss_articles_fts_ai;
+DROP TRIGGER IF EXISTS rss_articles_fts_ad;
+DROP TRIGGER IF EXISTS rss_articles_fts_au;
+DROP TABLE IF EXISTS rss_articles_fts;
+
+CREATE VIRTUAL TABLE IF NOT EXISTS rss_articles_fts USING fts5(
+    title,
+    description,
+    content,
+    author,
+    content = '',
+    contentless_delete = 1
+);
+
+CREATE TRIGGER IF NOT EXISTS rss_articles_fts_ai AFTER INSERT ON rss_articles BEGIN
+    INSERT INTO rss_articles_fts (rowid, title, description, content, author)
+    VALUES (new.rowid, new.title, new.description, new.content, new.author);
+END;
+
+CREATE TRIGGER IF NOT EXISTS rss_articles_fts_ad AFTER DELETE ON rss_articles BEGIN
+    DELETE FROM rss_articles_fts WHERE rowid = old.rowid;
+END;
+
+-- 压缩存储的文章由应用更新索引，压缩已有内容时保留原有索引
+CREATE TRIGGER IF NOT EXISTS rss_articles_fts_au AFTER UPDATE OF title, description, content, author ON rss_articles
+WHEN new.content_compression IS NULL BEGIN
+    INSERT OR REPLACE INTO rss_articles_fts (rowid, title, description, content, author)
+    VALUES (new.rowid, new.title, new.description, new.content, new.author);
+END;
+
+-- 为已有文章建立索引，已压缩的正文需通过重建索引补全
+INSERT INTO rss_articles_fts (rowid, title, description, content, author)
+SELECT rowid, title, description, content, author FROM rss_articles;
@@ -0,0 +1,4 @@
+-- 标题前缀规则默认按普通文本匹配，开启后按正则表达式匹配
+ALTER TABLE rss_feeds ADD COLUMN title_strip_is_regex BOOLEAN NOT NULL DEFAULT 0;
+-- 已有规则都是按正则表达式保存的
+UPDATE rss_feeds SET title_strip_is_regex = 1 WHERE title_strip_prefix IS NOT NULL;
@@ -0,0 +1,268 @@
+use crate::database;
+use crate::error::{AppError, AppResult};
+use crate::models::AppState;
+use crate::settings;
+use crate::utils;
+use chrono::{DateTime, Local, Utc};
+use log::{info, warn};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{Connection, SqliteConnection, SqlitePool};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// 自动备份的最小间隔（小时）
+const AUTO_BACKUP_INTERVAL_HOURS: i64 = 24;
+
+/// 默认保留的自动备份数
+pub const DEFAULT_AUTO_BACKUP_KEEP_COUNT: u32 = 7;
+
+/// 自动备份文件名前缀，清理时只处理带该前缀的文件
+const AUTO_BACKUP_PREFIX: &str = "auto-";
+
+/// 恢复的备份中必须存在的表
+const REQUIRED_TABLES: [&str; 4] = ["rss_feeds", "rss_articles", "app_settings", "_sqlx_migrations"];
+
+/// 使用VACUUM INTO将数据库完整复制到指定路径（目标文件不能已存在）
+pub async fn backup_to(db: &SqlitePool, path: &Path) -> AppResult<()> {
+    if path.exists() {
+        return Err(AppError::validation(format!("备份文件已存在: {}", path.display())));
+    }
+    let path_str = path
+        .to_str()
+        .ok_or_else(|| AppError::validation(format!("无效的备份路径: {}", path.display())))?;
+
+    sqlx::query("VACUUM INTO ?").bind(path_str).execute(db).await?;
+    Ok(())
+}
+
+/// 将数据库备份到用户选择的路径，应用运行中也能得到一致的副本，返回备份文件的字节数
+/// 目标目录不存在或不可写时返回Io错误
+pub async fn backup_database(db: &SqlitePool, dest: &Path) -> AppResult<u64> {
+    let dir = match dest.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    if !dir.is_dir() {
+        return Err(std::io::Error::new(
+            ErrorKind::NotFound,
+            format!("备份目录不存在: {}", dir.display()),
+        )
+        .into());
+    }
+    ensure_writable(dir)?;
+
+    backup_to(db, dest).await?;
+    let bytes = std::fs::metadata(dest)?.len();
+    info!("数据库已备份到 {}（{} 字节）", dest.display(), bytes);
+    Ok(bytes)
+}
+
+/// 检查备份文件是完好的SQLite数据库，包含必需的表，且迁移版本与当前数据库一致
+pub async fn validate_backup(db: &SqlitePool, src: &Path) -> AppResult<()> {
+    if !src.is_file() {
+        return Err(AppError::validation(format!("备份文件不存在: {}", src.display())));
+    }
+    let options = SqliteConnectOptions::new().filename(src).read_only(true);
+    let mut conn = SqliteConnection::connect_with(&options)
+        .await
+        .map_err(|e| AppError::validation(format!("无法打开备份文件 {}: {}", src.display(), e)))?;
+
+    // 不是SQLite数据库的文件在第一次查询时报错
+    let check: Result<String, _> = sqlx::query_scalar("PRAGMA quick_check").fetch_one(&mut conn).await;
+    match check {
+        Ok(result) if result == "ok" => {}
+        Ok(result) => return Err(AppError::validation(format!("备份文件已损坏: {}", result))),
+        Err(e) => return Err(AppError::validation(format!("备份文件不是有效的数据库: {}", e))),
+    }
+
+    for table in REQUIRED_TABLES {
+        let exists: Option<String> = sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?")
+            .bind(table)
+            .fetch_optional(&mut conn)
+            .await?;
+        if exists.is_none() {
+            return Err(AppError::validation(format!("备份文件缺少数据表: {}", table)));
+        }
+    }
+
+    let latest_migration = "SELECT MAX(version) FROM _sqlx_migrations WHERE success = 1";
+    let backup_version: Option<i64> = sqlx::query_scalar(latest_migration).fetch_one(&mut conn).await?;
+    let current_version: Option<i64> = sqlx::query_scalar(latest_migration).fetch_one(db).await?;
+    conn.close().await?;
+    if backup_version != current_version {
+        return Err(AppError::validation(format!(
+            "备份的数据库版本（{}）与当前版本（{}）不一致，无法恢复",
+            backup_version.unwrap_or_default(),
+            current_version.unwrap_or_default()
+        )));
+    }
+    Ok(())
+}
+
+/// 用备份文件替换数据库文件，并在应用状态中换上新的连接池
+///
+/// 旧连接池会被关闭，之前取得旧连接池的后台任务之后的查询都会失败，需由调用方重启（如定时刷新）
+/// 打开恢复的数据库失败时换回原数据库文件并重新打开，应用状态中不会留下已关闭的连接池
+pub async fn restore_database(state: &AppState, src: &Path, db_path: &Path) -> AppResult<()> {
+    let running = state.fetch_tokens.lock().map(|tokens| tokens.len()).unwrap_or_default();
+    if running > 0 {
+        return Err(AppError::validation(format!("有 {} 个RSS源正在抓取文章，请等待完成或取消后再恢复", running)));
+    }
+
+    let current = state.db();
+    validate_backup(&current, src).await?;
+
+    // 先复制到数据库旁的临时文件，复制失败时当前数据库不受影响
+    let staged = db_path.with_extension("restore");
+    std::fs::copy(src, &staged)?;
+
+    // 原数据库文件先移到一旁，恢复失败时换回
+    let original = db_path.with_extension("pre-restore");
+    current.close().await;
+    let restored = match replace_database_file(&staged, db_path, &original) {
+        Ok(()) => database::open_database(db_path).await,
+        Err(e) => Err(e.into()),
+    };
+
+    match restored {
+        Ok(db) => {
+            state.replace_db(db);
+            remove_database_files(&original);
+            info!("已从 {} 恢复数据库", src.display());
+            Ok(())
+        }
+        Err(e) => {
+            warn!("恢复数据库失败，换回原数据库: {}", e);
+            let _ = std::fs::remove_file(&staged);
+            if original.exists() {
+                remove_database_files(db_path);
+                move_database_files(&original, db_path)?;
+            }
+            state.replace_db(database::open_database(db_path).await?);
+            Err(e)
+        }
+    }
+}
+
+/// 将原数据库文件移到original，再将暂存的备份移到数据库位置
+fn replace_database_file(staged: &Path, db_path: &Path, original: &Path) -> std::io::Result<()> {
+    move_database_files(db_path, original)?;
+    if let Err(e) = std::fs::rename(staged, db_path) {
+        let _ = move_database_files(original, db_path);
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// 数据库文件及其WAL和共享内存文件的路径
+fn database_files(path: &Path) -> [PathBuf; 3] {
+    ["", "-wal", "-shm"].map(|suffix| PathBuf::from(format!("{}{}", path.display(), suffix)))
+}
+
+/// 移动数据库文件，同时移动存在的WAL和共享内存文件
+fn move_database_files(from: &Path, to: &Path) -> std::io::Result<()> {
+    for (from, to) in database_files(from).iter().zip(database_files(to).iter()) {
+        if from.exists() {
+            std::fs::rename(from, to)?;
+        }
+    }
+    Ok(())
+}
+
+/// 删除数据库文件及其WAL和共享内存文件
+fn remove_database_files(path: &Path) {
+    for path in database_files(path) {
+        if path.exists() {
+            if let Err(e) = std::fs::remove_file(&path) {
+                warn!("删除 {} 失败: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// 在目录中创建并删除一个临时文件，确认目录可写
+fn ensure_writable(dir: &Path) -> AppResult<()> {
+    let probe = dir.join(format!(".write-test-{}", Uuid::new_v4()));
+    std::fs::File::create(&probe).map_err(|e| {
+        std::io::Error::new(e.kind(), format!("备份目录不可写: {}（{}）", dir.display(), e))
+    })?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+/// 开启或关闭每日自动备份
+pub async fn set_auto_backup(db: &SqlitePool, enabled: bool, keep_count: u32) -> AppResult<()> {
+    if keep_count == 0 {
+        return Err(AppError::validation("自动备份至少需要保留1份"));
+    }
+    settings::set_bool(db, settings::AUTO_BACKUP_ENABLED, enabled).await?;
+    settings::set_u32(db, settings::AUTO_BACKUP_KEEP_COUNT, keep_count).await
+}
+
+/// 在备份目录中创建一份自动备份，并删除超出保留数量的旧备份
+pub async fn create_auto_backup(db: &SqlitePool, dir: &Path, keep_count: u32) -> AppResult<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+
+    let file_name = format!(
+        "{}{}.db",
+        AUTO_BACKUP_PREFIX,
+        Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let path = dir.join(file_name);
+    backup_to(db, &path).await?;
+
+    prune_auto_backups(dir, keep_count)?;
+    Ok(path)
+}
+
+/// 删除超出保留数量的旧自动备份（文件名包含时间，按名称排序即按时间排序）
+pub fn prune_auto_backups(dir: &Path, keep_count: u32) -> AppResult<Vec<PathBuf>> {
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(AUTO_BACKUP_PREFIX) && name.ends_with(".db"))
+        })
+        .collect();
+    backups.sort();
+
+    let excess = backups.len().saturating_sub(keep_count as usize);
+    let removed: Vec<PathBuf> = backups.into_iter().take(excess).collect();
+    for path in &removed {
+        std::fs::remove_file(path)?;
+    }
+    Ok(removed)
+}
+
+/// 开启自动备份且距上次备份已满一天时执行备份，由调度器定期调用
+pub async fn run_auto_backup_if_due(db: &SqlitePool) -> AppResult<()> {
+    if !settings::get_bool(db, settings::AUTO_BACKUP_ENABLED, false).await? {
+        return Ok(());
+    }
+
+    // 获取当前本地时间并转换为UTC时间
+    let now = Local::now().with_timezone(&Utc);
+    let last_backup_at = settings::get_setting(db, settings::AUTO_BACKUP_LAST_AT)
+        .await?
+        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+    if let Some(last_backup_at) = last_backup_at {
+        if now.signed_duration_since(last_backup_at).num_hours() < AUTO_BACKUP_INTERVAL_HOURS {
+            return Ok(());
+        }
+    }
+
+    let keep_count = settings::get_u32(
+        db,
+        settings::AUTO_BACKUP_KEEP_COUNT,
+        DEFAULT_AUTO_BACKUP_KEEP_COUNT,
+    )
+    .await?;
+    let path = create_auto_backup(db, &utils::get_backup_dir()?, keep_count).await?;
+    settings::set_setting(db, settings::AUTO_BACKUP_LAST_AT, &now.to_rfc3339()).await?;
+
+    info!("数据库自动备份完成: {}", path.display());
+    Ok(())
+}
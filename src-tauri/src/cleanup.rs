@@ -0,0 +1,94 @@
+use crate::error::AppResult;
+use crate::models::{CleanupReport, CleanupSample};
+use chrono::Utc;
+use log::info;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::HashSet;
+
+/// 清理报告中最多列出的示例文章数
+const SAMPLE_SIZE: usize = 20;
+
+/// 生成清理报告：目标文章数和前SAMPLE_SIZE篇示例
+pub fn report(targets: &[(String, String)], dry_run: bool) -> CleanupReport {
+    CleanupReport {
+        dry_run,
+        count: targets.len() as u32,
+        sample: targets
+            .iter()
+            .take(SAMPLE_SIZE)
+            .map(|(id, title)| CleanupSample {
+                id: id.clone(),
+                title: title.clone(),
+            })
+            .collect(),
+    }
+}
+
+/// 对选出的目标文章执行清理：dry_run时只返回数量和示例，不删除任何数据
+/// targets为 (文章id, 标题) 列表，由各清理操作自行筛选
+pub async fn apply(
+    db: &SqlitePool,
+    targets: Vec<(String, String)>,
+    dry_run: bool,
+) -> AppResult<CleanupReport> {
+    let report = report(&targets, dry_run);
+
+    if dry_run || targets.is_empty() {
+        return Ok(report);
+    }
+
+    let mut tx = db.begin().await?;
+    for (id, _) in &targets {
+        record_deleted(&mut tx, id).await?;
+        sqlx::query("DELETE FROM rss_articles WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+
+    info!("清理完成，删除 {} 篇文章", report.count);
+    Ok(report)
+}
+
+/// 删除文章前记录其guid和规范化链接，之后刷新时不再保存这篇文章
+pub(crate) async fn record_deleted(conn: &mut SqliteConnection, article_id: &str) -> AppResult<()> {
+    sqlx::query(
+        "INSERT INTO deleted_articles (feed_id, guid, link_key, deleted_at)
+         SELECT feed_id, guid, link_key, ? FROM rss_articles
+         WHERE id = ? AND (guid IS NOT NULL OR link_key IS NOT NULL)",
+    )
+    .bind(Utc::now().to_rfc3339())
+    .bind(article_id)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// RSS源中已删除文章的guid和规范化链接
+#[derive(Debug, Default)]
+pub(crate) struct DeletedArticles {
+    guids: HashSet<String>,
+    link_keys: HashSet<String>,
+}
+
+impl DeletedArticles {
+    pub(crate) async fn load(db: &SqlitePool, feed_id: &str) -> AppResult<Self> {
+        let rows: Vec<(Option<String>, Option<String>)> =
+            sqlx::query_as("SELECT guid, link_key FROM deleted_articles WHERE feed_id = ?")
+                .bind(feed_id)
+                .fetch_all(db)
+                .await?;
+        let mut deleted = DeletedArticles::default();
+        for (guid, link_key) in rows {
+            deleted.guids.extend(guid);
+            deleted.link_keys.extend(link_key);
+        }
+        Ok(deleted)
+    }
+
+    /// guid或规范化链接与已删除的文章相同
+    pub(crate) fn contains(&self, guid: &str, link_key: Option<&str>) -> bool {
+        self.guids.contains(guid) || link_key.is_some_and(|key| self.link_keys.contains(key))
+    }
+}
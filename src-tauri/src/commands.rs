@@ -1,8 +1,28 @@
+use crate::backup;
+use crate::compression;
+use crate::database;
+use crate::digest;
 use crate::error::AppResult;
-use crate::models::{AddFeedRequest, AppState, RssArticle, RssFeed, UpdateArticleRequest, RssFetchProgress, RssFetchStatus};
+use crate::export;
+use crate::links;
+use crate::models::{AddFeedRequest, AppState, ArticlePage, ArticleSort, BulkFeedEntry, BulkFeedResult, Category, CleanupReport, ContentCompressionReport, DatabaseIntegrityReport, Digest, ExportFormat, ExtractedContent, FeedCadence, FeedInfo, FeedPreview, FeedStorage, FlaggedDuplicate, HttpSettings, InboxGroup, LinkCheckSummary, OpmlImportResult, OpmlValidation, ProxySettings, ProxyTestResult, ReaderPrefs, ReadingHeatmap, ReadingMetricsProgress, ReadingStreak, RefreshDiff, RssArticle, RssArticleWithFeed, RssFeed, SearchDirection, SearchFilters, SearchIndexProgress, SiteRule, SiteRuleRequest, Tag, TopUnreadArticle, UpdateArticleRequest, RssFetchProgress, RssFetchStatus, VacuumReport};
+use crate::network;
+use crate::render;
 use crate::rss::RssService;
+use crate::sanitize;
+use crate::scheduler;
+use crate::search::SearchService;
+use crate::settings;
+use crate::site_rules;
+use crate::utils;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
 use tauri::{State, AppHandle, Emitter};
 use tokio::task;
+use tokio_util::sync::CancellationToken;
+
+/// 导入数量达到该值时，导入完成后自动重建全文索引
+const LARGE_IMPORT_THRESHOLD: usize = 20;
 
 
 
@@ -14,7 +34,7 @@ pub async fn add_rss_feed_sync(
     state: State<'_, AppState>,
     request: AddFeedRequest,
 ) -> AppResult<RssFeed> {
-    RssService::add_feed_sync(&state.db, request).await
+    RssService::add_feed_sync(&state.db(), request).await
 }
 
 /// 添加RSS源（异步版本，后台抓取文章）
@@ -27,14 +47,20 @@ pub async fn add_rss_feed_async(
     // 首先创建RSS源记录
 
     let request_clone = request.clone();
-    let feed = RssService::add_feed_sync(&state.db, request_clone).await?;
+    let feed = RssService::add_feed_sync(&state.db(), request_clone).await?;
     
     // 克隆必要的数据用于异步任务
-    let db = state.db.clone();
+    let db = state.db();
     let feed_id = feed.id.clone();
     let feed_title = feed.title.clone();
     let url = request.url.clone();
     let app_handle_clone = app_handle.clone();
+    let fetch_tokens = state.fetch_tokens.clone();
+    let cancel = CancellationToken::new();
+    fetch_tokens
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(feed_id.clone(), cancel.clone());
     
     // 启动异步任务抓取文章
     task::spawn(async move {
@@ -50,7 +76,25 @@ pub async fn add_rss_feed_async(
         let _ = app_handle_clone.emit("rss-fetch-progress", &progress);
         
         // 执行异步抓取
-        match RssService::fetch_articles_async(&db, &feed_id, &url, &app_handle_clone).await {
+        let result = RssService::fetch_articles_async(&db, &feed_id, &url, &app_handle_clone, &cancel).await;
+        // 抓取结束后移除取消令牌
+        fetch_tokens
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&feed_id);
+
+        match result {
+            Ok(_) if cancel.is_cancelled() => {
+                let progress = RssFetchProgress {
+                    feed_id: feed_id.clone(),
+                    feed_title: feed_title.clone(),
+                    total_articles: 0,
+                    fetched_articles: 0,
+                    current_article_title: None,
+                    status: RssFetchStatus::Failed("cancelled".to_string()),
+                };
+                let _ = app_handle_clone.emit("rss-fetch-progress", &progress);
+            }
             Ok(_) => {
                 let progress = RssFetchProgress {
                     feed_id: feed_id.clone(),
@@ -79,10 +123,47 @@ pub async fn add_rss_feed_async(
     Ok(feed)
 }
 
-/// 获取所有RSS源
+/// 取消正在后台抓取的RSS源，返回是否有正在进行的抓取
+#[tauri::command]
+pub async fn cancel_fetch(state: State<'_, AppState>, feed_id: String) -> AppResult<bool> {
+    let token = state
+        .fetch_tokens
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&feed_id)
+        .cloned();
+    match token {
+        Some(token) => {
+            token.cancel();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// 获取RSS源的技术信息，用于订阅前诊断
+#[tauri::command]
+pub async fn get_feed_info(url: String) -> AppResult<FeedInfo> {
+    RssService::get_feed_info(&url).await
+}
+
+/// 预览RSS源的标题、简介和前几篇文章，不订阅
+#[tauri::command]
+pub async fn preview_feed(
+    url: String,
+    username: Option<String>,
+    password: Option<String>,
+) -> AppResult<FeedPreview> {
+    RssService::preview_feed(&url, username, password).await
+}
+
+/// 获取RSS源，默认只返回启用的源
 #[tauri::command]
-pub async fn get_rss_feeds(state: State<'_, AppState>) -> AppResult<Vec<RssFeed>> {
-    RssService::get_feeds(&state.db).await
+pub async fn get_rss_feeds(
+    state: State<'_, AppState>,
+    include_inactive: Option<bool>,
+) -> AppResult<Vec<RssFeed>> {
+    RssService::get_feeds(&state.db(), include_inactive.unwrap_or(false)).await
 }
 
 /// 获取文章列表
@@ -92,8 +173,78 @@ pub async fn get_articles(
     feed_id: Option<String>,
     limit: Option<i32>,
     offset: Option<i32>,
+    sort: Option<ArticleSort>,
+    is_read: Option<bool>,
+    is_starred: Option<bool>,
+) -> AppResult<Vec<RssArticle>> {
+    RssService::get_articles(&state.db(), feed_id, limit, offset, sort, is_read, is_starred).await
+}
+
+/// 按游标分页获取文章列表，之后的每一页传入上一页最后一篇文章的published_at和id
+#[tauri::command]
+pub async fn get_articles_after(
+    state: State<'_, AppState>,
+    cursor_published_at: Option<DateTime<Utc>>,
+    cursor_id: Option<String>,
+    limit: Option<i32>,
+    feed_id: Option<String>,
+    is_read: Option<bool>,
+    is_starred: Option<bool>,
+) -> AppResult<Vec<RssArticle>> {
+    RssService::get_articles_after(&state.db(), cursor_published_at, cursor_id, limit, feed_id, is_read, is_starred)
+        .await
+}
+
+/// 分页获取文章列表，同时返回文章总数
+#[tauri::command]
+pub async fn get_articles_paginated(
+    state: State<'_, AppState>,
+    feed_id: Option<String>,
+    limit: Option<i32>,
+    offset: Option<i32>,
+    sort: Option<ArticleSort>,
+    is_read: Option<bool>,
+    is_starred: Option<bool>,
+) -> AppResult<ArticlePage> {
+    RssService::get_articles_paginated(&state.db(), feed_id, limit, offset, sort, is_read, is_starred)
+        .await
+}
+
+/// 获取每个RSS源最新的一篇未读文章
+#[tauri::command]
+pub async fn get_top_unread_per_feed(
+    state: State<'_, AppState>,
+) -> AppResult<Vec<TopUnreadArticle>> {
+    RssService::get_top_unread_per_feed(&state.db()).await
+}
+
+/// 专注模式：获取未读长文
+#[tauri::command]
+pub async fn get_longform_unread(
+    state: State<'_, AppState>,
+    min_words: Option<u32>,
+    limit: Option<i32>,
+    feed_id: Option<String>,
 ) -> AppResult<Vec<RssArticle>> {
-    RssService::get_articles(&state.db, feed_id, limit, offset).await
+    RssService::get_longform_unread(&state.db(), min_words, limit, feed_id).await
+}
+
+/// 获取今天发布的文章（按指定时区划分日期）
+#[tauri::command]
+pub async fn get_todays_articles(
+    state: State<'_, AppState>,
+    timezone: Option<String>,
+) -> AppResult<Vec<RssArticleWithFeed>> {
+    RssService::get_todays_articles(&state.db(), timezone).await
+}
+
+/// 获取按RSS源分组的合并收件箱
+#[tauri::command]
+pub async fn get_inbox(
+    state: State<'_, AppState>,
+    limit_per_feed: Option<u32>,
+) -> AppResult<Vec<InboxGroup>> {
+    RssService::get_inbox(&state.db(), limit_per_feed).await
 }
 
 /// 获取单篇文章详细内容
@@ -102,7 +253,65 @@ pub async fn get_article_content(
     state: State<'_, AppState>,
     article_id: String,
 ) -> AppResult<RssArticle> {
-    RssService::get_article_content(&state.db, article_id).await
+    RssService::get_article_content(&state.db(), article_id).await
+}
+
+/// 忽略提取失败的冷却时间，立即从原文重新提取全文
+#[tauri::command]
+pub async fn force_extract_content(
+    state: State<'_, AppState>,
+    article_id: String,
+) -> AppResult<RssArticle> {
+    RssService::force_extract_content(&state.db(), article_id).await
+}
+
+/// 获取是否跨RSS源合并重复文章
+#[tauri::command]
+pub async fn get_dedup_across_feeds(state: State<'_, AppState>) -> AppResult<bool> {
+    settings::get_bool(&state.db(), settings::DEDUP_ACROSS_FEEDS, false).await
+}
+
+/// 开启或关闭跨RSS源合并重复文章（不按RSS源筛选时只显示最早的一篇）
+#[tauri::command]
+pub async fn set_dedup_across_feeds(state: State<'_, AppState>, enabled: bool) -> AppResult<()> {
+    RssService::set_dedup_across_feeds(&state.db(), enabled).await
+}
+
+/// 获取是否在打开文章时自动标记已读
+#[tauri::command]
+pub async fn get_mark_read_on_open(state: State<'_, AppState>) -> AppResult<bool> {
+    settings::get_bool(&state.db(), settings::MARK_READ_ON_OPEN, false).await
+}
+
+/// 开启或关闭打开文章时自动标记已读
+#[tauri::command]
+pub async fn set_mark_read_on_open(state: State<'_, AppState>, enabled: bool) -> AppResult<()> {
+    settings::set_bool(&state.db(), settings::MARK_READ_ON_OPEN, enabled).await
+}
+
+/// 开启或关闭保留原始文章内容（调试用）
+#[tauri::command]
+pub async fn set_keep_raw_content(state: State<'_, AppState>, enabled: bool) -> AppResult<()> {
+    settings::set_bool(&state.db(), settings::KEEP_RAW_CONTENT, enabled).await
+}
+
+/// 获取文章未经处理的原始内容，用于排查内容处理问题
+#[tauri::command]
+pub async fn get_article_raw_content(
+    state: State<'_, AppState>,
+    article_id: String,
+) -> AppResult<Option<String>> {
+    RssService::get_article_raw_content(&state.db(), article_id).await
+}
+
+/// 设置文章阅读模式偏好
+#[tauri::command]
+pub async fn set_article_reader_prefs(
+    state: State<'_, AppState>,
+    article_id: String,
+    prefs: Option<ReaderPrefs>,
+) -> AppResult<String> {
+    RssService::set_article_reader_prefs(&state.db(), article_id, prefs).await
 }
 
 /// 更新文章状态
@@ -111,25 +320,793 @@ pub async fn update_article(
     state: State<'_, AppState>,
     request: UpdateArticleRequest,
 ) -> AppResult<String> {
-    RssService::update_article(&state.db, request).await
+    RssService::update_article(&state.db(), request).await
+}
+
+/// 批量更新多篇文章的已读和收藏状态，返回被更新的文章数量
+#[tauri::command]
+pub async fn update_articles_bulk(
+    state: State<'_, AppState>,
+    ids: Vec<String>,
+    is_read: Option<bool>,
+    is_starred: Option<bool>,
+) -> AppResult<u64> {
+    RssService::update_articles_bulk(&state.db(), ids, is_read, is_starred).await
+}
+
+/// 将指定RSS源或全部RSS源的未读文章标记为已读
+#[tauri::command]
+pub async fn mark_all_read(
+    state: State<'_, AppState>,
+    feed_id: Option<String>,
+) -> AppResult<u64> {
+    RssService::mark_all_read(&state.db(), feed_id).await
+}
+
+/// 获取单个RSS源的未读文章数
+#[tauri::command]
+pub async fn get_feed_unread_count(state: State<'_, AppState>, feed_id: String) -> AppResult<i64> {
+    RssService::get_feed_unread_count(&state.db(), &feed_id).await
+}
+
+/// 获取全部未读文章数
+#[tauri::command]
+pub async fn get_total_unread_count(state: State<'_, AppState>) -> AppResult<i64> {
+    RssService::get_total_unread_count(&state.db()).await
+}
+
+/// 重新统计各RSS源缓存的未读数
+#[tauri::command]
+pub async fn recompute_unread_counts(state: State<'_, AppState>) -> AppResult<u64> {
+    RssService::recompute_unread_counts(&state.db()).await
+}
+
+/// 为文章添加标签，标签不存在时创建
+#[tauri::command]
+pub async fn add_article_tag(
+    state: State<'_, AppState>,
+    article_id: String,
+    tag: String,
+) -> AppResult<String> {
+    RssService::add_article_tag(&state.db(), article_id, tag).await
+}
+
+/// 移除文章的标签
+#[tauri::command]
+pub async fn remove_article_tag(
+    state: State<'_, AppState>,
+    article_id: String,
+    tag: String,
+) -> AppResult<String> {
+    RssService::remove_article_tag(&state.db(), article_id, tag).await
+}
+
+/// 获取所有标签
+#[tauri::command]
+pub async fn list_tags(state: State<'_, AppState>) -> AppResult<Vec<Tag>> {
+    RssService::list_tags(&state.db()).await
+}
+
+/// 获取带有指定标签的文章
+#[tauri::command]
+pub async fn get_articles_by_tag(
+    state: State<'_, AppState>,
+    tag: String,
+    limit: Option<i32>,
+    offset: Option<i32>,
+) -> AppResult<Vec<RssArticle>> {
+    RssService::get_articles_by_tag(&state.db(), tag, limit, offset).await
+}
+
+/// 将一篇文章的阅读状态复制到另一篇文章
+#[tauri::command]
+pub async fn transfer_article_state(
+    state: State<'_, AppState>,
+    from_id: String,
+    to_id: String,
+) -> AppResult<String> {
+    RssService::transfer_article_state(&state.db(), from_id, to_id).await
+}
+
+/// 将文章链接更新为网页声明的canonical地址
+#[tauri::command]
+pub async fn canonicalize_article_link(
+    state: State<'_, AppState>,
+    article_id: String,
+) -> AppResult<String> {
+    RssService::canonicalize_article_link(&state.db(), article_id).await
+}
+
+/// 检查已保存文章的链接是否仍可访问，进度通过事件推送
+#[tauri::command]
+pub async fn check_article_links(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    feed_id: Option<String>,
+    concurrency: Option<usize>,
+) -> AppResult<LinkCheckSummary> {
+    links::check_article_links(&state.db(), feed_id, concurrency, |progress| {
+        let _ = app_handle.emit("link-check-progress", progress);
+    })
+    .await
+}
+
+/// 获取被标记为疑似重复的文章
+#[tauri::command]
+pub async fn get_flagged_duplicates(state: State<'_, AppState>) -> AppResult<Vec<FlaggedDuplicate>> {
+    RssService::get_flagged_duplicates(&state.db()).await
+}
+
+/// 确认疑似重复文章并删除
+#[tauri::command]
+pub async fn confirm_duplicate(state: State<'_, AppState>, id: String) -> AppResult<String> {
+    RssService::confirm_duplicate(&state.db(), id).await
+}
+
+/// 忽略疑似重复标记，保留文章
+#[tauri::command]
+pub async fn dismiss_duplicate(state: State<'_, AppState>, id: String) -> AppResult<String> {
+    RssService::dismiss_duplicate(&state.db(), id).await
+}
+
+/// 将文章移动到另一个RSS源
+#[tauri::command]
+pub async fn move_article(
+    state: State<'_, AppState>,
+    article_id: String,
+    target_feed_id: String,
+) -> AppResult<String> {
+    RssService::move_article(&state.db(), article_id, target_feed_id).await
 }
 
 /// 刷新RSS源
 #[tauri::command]
-pub async fn refresh_rss_feed(state: State<'_, AppState>, feed_id: String) -> AppResult<String> {
-    RssService::refresh_feed(&state.db, feed_id).await
+pub async fn refresh_rss_feed(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    feed_id: String,
+) -> AppResult<String> {
+    RssService::refresh_feed(&state.db(), &app_handle, feed_id).await
+}
+
+/// 刷新RSS源并返回本次新增、更新和可能被移除的文章
+#[tauri::command]
+pub async fn refresh_feed_diff(state: State<'_, AppState>, feed_id: String) -> AppResult<RefreshDiff> {
+    RssService::refresh_feed_diff(&state.db(), feed_id).await
+}
+
+/// 强制刷新RSS源（忽略ETag/Last-Modified缓存）
+#[tauri::command]
+pub async fn force_refresh_feed(state: State<'_, AppState>, feed_id: String) -> AppResult<String> {
+    RssService::force_refresh_feed(&state.db(), feed_id).await
+}
+
+/// 设置RSS源的cron抓取计划
+#[tauri::command]
+pub async fn set_feed_cron(
+    state: State<'_, AppState>,
+    feed_id: String,
+    expr: Option<String>,
+) -> AppResult<String> {
+    RssService::set_feed_cron(&state.db(), feed_id, expr).await
+}
+
+/// 设置RSS源的时区，用于解释不带时区信息的发布时间
+#[tauri::command]
+pub async fn set_feed_timezone(
+    state: State<'_, AppState>,
+    feed_id: String,
+    tz: Option<String>,
+) -> AppResult<String> {
+    RssService::set_feed_timezone(&state.db(), feed_id, tz).await
+}
+
+/// 预览HTML按白名单清理后的结果
+#[tauri::command]
+pub fn preview_sanitized(html: String) -> String {
+    sanitize::sanitize_html(&html)
+}
+
+/// 设置RSS源保存文章内容的最大字节数
+#[tauri::command]
+pub async fn set_feed_max_content(
+    state: State<'_, AppState>,
+    feed_id: String,
+    bytes: Option<u32>,
+) -> AppResult<String> {
+    RssService::set_feed_max_content(&state.db(), feed_id, bytes).await
+}
+
+/// 获取全局的文章内容大小上限（字节），0表示不限制
+#[tauri::command]
+pub async fn get_max_content_bytes(state: State<'_, AppState>) -> AppResult<u32> {
+    RssService::get_max_content_bytes(&state.db()).await
+}
+
+/// 设置全局的文章内容大小上限，RSS源单独设置的上限优先
+#[tauri::command]
+pub async fn set_max_content_bytes(state: State<'_, AppState>, bytes: u32) -> AppResult<()> {
+    RssService::set_max_content_bytes(&state.db(), bytes).await
+}
+
+/// 设置RSS源的自动刷新间隔（分钟）
+#[tauri::command]
+pub async fn set_feed_refresh_interval(
+    state: State<'_, AppState>,
+    feed_id: String,
+    minutes: Option<u32>,
+) -> AppResult<String> {
+    RssService::set_feed_refresh_interval(&state.db(), feed_id, minutes).await
+}
+
+/// 设置RSS源文章标题需去除的前缀，is_regex为true时按正则表达式匹配
+#[tauri::command]
+pub async fn set_feed_title_strip(
+    state: State<'_, AppState>,
+    feed_id: String,
+    pattern: Option<String>,
+    is_regex: Option<bool>,
+) -> AppResult<String> {
+    RssService::set_feed_title_strip(&state.db(), feed_id, pattern, is_regex.unwrap_or(false)).await
+}
+
+/// 设置RSS源是否使用webview渲染提取全文
+#[tauri::command]
+pub async fn set_feed_requires_render(
+    state: State<'_, AppState>,
+    feed_id: String,
+    requires_render: bool,
+) -> AppResult<String> {
+    RssService::set_feed_requires_render(&state.db(), feed_id, requires_render).await
+}
+
+/// 并发刷新所有活跃的RSS源，返回每个源的新增文章数或错误信息
+#[tauri::command]
+pub async fn refresh_all_rss_feeds(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    concurrency: Option<usize>,
+) -> AppResult<Vec<(String, Result<u32, String>)>> {
+    RssService::refresh_all_feeds(&state.db(), &app_handle, None, concurrency).await
+}
+
+/// 导出所有RSS源为OPML
+#[tauri::command]
+pub async fn export_opml(state: State<'_, AppState>) -> AppResult<String> {
+    RssService::export_opml(&state.db()).await
+}
+
+/// 预览OPML导入结果，不写入数据库
+#[tauri::command]
+pub async fn validate_opml(state: State<'_, AppState>, content: String) -> AppResult<OpmlValidation> {
+    RssService::validate_opml(&state.db(), &content).await
+}
+
+/// 导入OPML，默认在后台立即抓取新导入的RSS源
+#[tauri::command]
+pub async fn import_opml(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    content: String,
+    fetch_immediately: Option<bool>,
+) -> AppResult<OpmlImportResult> {
+    let result = RssService::import_opml(&state.db(), &content).await?;
+
+    if fetch_immediately.unwrap_or(true) && !result.imported.is_empty() {
+        let feed_ids: Vec<String> = result.imported.iter().map(|f| f.id.clone()).collect();
+        spawn_import_refresh(app_handle, state.db(), feed_ids).await?;
+    }
+
+    Ok(result)
+}
+
+/// 批量订阅RSS源，可为每个URL指定分类，默认在后台立即抓取新订阅的源
+#[tauri::command]
+pub async fn add_feeds_bulk(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    entries: Vec<BulkFeedEntry>,
+    fetch_immediately: Option<bool>,
+) -> AppResult<Vec<BulkFeedResult>> {
+    let results = RssService::add_feeds_bulk(&state.db(), entries).await?;
+
+    let feed_ids: Vec<String> = results
+        .iter()
+        .filter_map(|r| r.feed.as_ref().map(|f| f.id.clone()))
+        .collect();
+    if fetch_immediately.unwrap_or(true) && !feed_ids.is_empty() {
+        spawn_import_refresh(app_handle, state.db(), feed_ids).await?;
+    }
+
+    Ok(results)
+}
+
+/// 获取所有分类
+#[tauri::command]
+pub async fn get_categories(state: State<'_, AppState>) -> AppResult<Vec<Category>> {
+    RssService::get_categories(&state.db()).await
+}
+
+/// 启动异步任务刷新新导入的RSS源，进度通过事件推送
+async fn spawn_import_refresh(
+    app_handle: AppHandle,
+    db: SqlitePool,
+    feed_ids: Vec<String>,
+) -> AppResult<()> {
+    let large_import = feed_ids.len() >= LARGE_IMPORT_THRESHOLD;
+    let concurrency = RssService::get_import_concurrency(&db).await? as usize;
+
+    task::spawn(async move {
+        let refreshed =
+            RssService::refresh_all_feeds(&db, &app_handle, Some(feed_ids), Some(concurrency)).await;
+        if let Err(e) = refreshed {
+            log::error!("导入后刷新失败: {}", e);
+        }
+
+        // 大批量导入后重建全文索引，确保搜索覆盖所有文章
+        if large_import {
+            let result = SearchService::rebuild_index(&db, |progress| {
+                let _ = app_handle.emit("search-index-progress", progress);
+            })
+            .await;
+            if let Err(e) = result {
+                log::error!("导入后重建全文索引失败: {}", e);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// 获取OPML导入后立即刷新的并发数
+#[tauri::command]
+pub async fn get_import_concurrency(state: State<'_, AppState>) -> AppResult<u32> {
+    RssService::get_import_concurrency(&state.db()).await
+}
+
+/// 设置OPML导入后立即刷新的并发数
+#[tauri::command]
+pub async fn set_import_concurrency(state: State<'_, AppState>, concurrency: u32) -> AppResult<()> {
+    RssService::set_import_concurrency(&state.db(), concurrency).await
+}
+
+/// 获取摘要判断字数阈值
+#[tauri::command]
+pub async fn get_teaser_threshold(state: State<'_, AppState>) -> AppResult<u32> {
+    RssService::get_teaser_threshold(&state.db()).await
+}
+
+/// 设置摘要判断字数阈值，低于该字数的源内容会被替换为提取的全文
+#[tauri::command]
+pub async fn set_teaser_threshold(state: State<'_, AppState>, words: u32) -> AppResult<()> {
+    RssService::set_teaser_threshold(&state.db(), words).await
+}
+
+/// 设置RSS源的备用地址
+#[tauri::command]
+pub async fn set_feed_alternates(
+    state: State<'_, AppState>,
+    feed_id: String,
+    urls: Vec<String>,
+) -> AppResult<RssFeed> {
+    RssService::set_feed_alternates(&state.db(), feed_id, urls).await
+}
+
+/// 将收藏的文章导出为Atom文档
+#[tauri::command]
+pub async fn export_starred_atom(state: State<'_, AppState>) -> AppResult<String> {
+    export::export_starred_atom(&state.db()).await
+}
+
+/// 将RSS源的所有文章导出为Markdown
+#[tauri::command]
+pub async fn export_feed_markdown(state: State<'_, AppState>, feed_id: String) -> AppResult<String> {
+    export::export_feed_markdown(&state.db(), &feed_id).await
+}
+
+/// 将带有指定标签的所有文章导出为Markdown、HTML或JSON文件，返回导出的文章数
+#[tauri::command]
+pub async fn export_tag(
+    state: State<'_, AppState>,
+    tag: String,
+    format: ExportFormat,
+    dest_path: String,
+) -> AppResult<u32> {
+    export::export_tag(&state.db(), &tag, format, std::path::Path::new(&dest_path)).await
+}
+
+/// 清空RSS源中未收藏的文章，dry_run为true时只预览
+#[tauri::command]
+pub async fn clear_feed_articles(
+    state: State<'_, AppState>,
+    feed_id: String,
+    dry_run: Option<bool>,
+) -> AppResult<CleanupReport> {
+    RssService::clear_feed_articles(&state.db(), feed_id, dry_run.unwrap_or(false)).await
+}
+
+/// 设置保存的文章总数上限，0表示不限制
+#[tauri::command]
+pub async fn set_max_total_articles(state: State<'_, AppState>, max: u32) -> AppResult<()> {
+    RssService::set_max_total_articles(&state.db(), max).await
+}
+
+/// 按文章总数上限删除最旧的已读文章，dry_run时只预览
+#[tauri::command]
+pub async fn enforce_article_quota(
+    state: State<'_, AppState>,
+    dry_run: Option<bool>,
+) -> AppResult<CleanupReport> {
+    RssService::enforce_article_quota(&state.db(), dry_run.unwrap_or(false)).await
+}
+
+/// 删除超过指定天数的旧文章（不删除收藏），dry_run时只预览
+#[tauri::command]
+pub async fn cleanup_old_articles(
+    state: State<'_, AppState>,
+    older_than_days: u32,
+    only_read: bool,
+    dry_run: Option<bool>,
+) -> AppResult<CleanupReport> {
+    RssService::cleanup_old_articles(&state.db(), older_than_days, only_read, dry_run.unwrap_or(false)).await
+}
+
+/// 每个RSS源只保留最新的若干篇文章（不删除收藏），dry_run时只预览
+#[tauri::command]
+pub async fn prune_articles(
+    state: State<'_, AppState>,
+    keep_per_feed: u32,
+    dry_run: Option<bool>,
+) -> AppResult<CleanupReport> {
+    RssService::prune_articles(&state.db(), keep_per_feed, dry_run.unwrap_or(false)).await
+}
+
+/// 合并所有RSS源中链接重复的文章，dry_run时只预览
+#[tauri::command]
+pub async fn deduplicate_all_articles(
+    state: State<'_, AppState>,
+    dry_run: Option<bool>,
+) -> AppResult<CleanupReport> {
+    RssService::deduplicate_all_articles(&state.db(), dry_run.unwrap_or(false)).await
 }
 
 /// 删除RSS源
 #[tauri::command]
 pub async fn delete_rss_feed(state: State<'_, AppState>, feed_id: String) -> AppResult<String> {
-    RssService::delete_feed(&state.db, feed_id).await
+    RssService::delete_feed(&state.db(), feed_id).await
+}
+
+/// 停用RSS源，保留其文章
+#[tauri::command]
+pub async fn disable_feed(state: State<'_, AppState>, feed_id: String) -> AppResult<String> {
+    RssService::disable_feed(&state.db(), feed_id).await
+}
+
+/// 启用RSS源
+#[tauri::command]
+pub async fn enable_feed(state: State<'_, AppState>, feed_id: String) -> AppResult<String> {
+    RssService::enable_feed(&state.db(), feed_id).await
+}
+
+/// 重新抓取RSS源的网站图标
+#[tauri::command]
+pub async fn refresh_favicon(
+    state: State<'_, AppState>,
+    feed_id: String,
+) -> AppResult<RssFeed> {
+    RssService::refresh_favicon(&state.db(), &feed_id).await
+}
+
+/// 获取RSS源缓存的网站图标（base64编码）
+#[tauri::command]
+pub async fn get_feed_favicon(
+    state: State<'_, AppState>,
+    feed_id: String,
+) -> AppResult<Option<String>> {
+    RssService::get_feed_favicon(&state.db(), &feed_id).await
+}
+
+/// 获取连续刷新失败的RSS源，默认连续失败3次及以上
+#[tauri::command]
+pub async fn get_unhealthy_feeds(
+    state: State<'_, AppState>,
+    min_failures: Option<u32>,
+) -> AppResult<Vec<RssFeed>> {
+    RssService::get_unhealthy_feeds(&state.db(), min_failures).await
+}
+
+/// 重新启用RSS源并清除连续失败记录
+#[tauri::command]
+pub async fn reactivate_feed(state: State<'_, AppState>, feed_id: String) -> AppResult<RssFeed> {
+    RssService::reactivate_feed(&state.db(), feed_id).await
+}
+
+/// 设置RSS源的HTTP Basic认证信息，用户名为空时清除
+#[tauri::command]
+pub async fn set_feed_credentials(
+    state: State<'_, AppState>,
+    feed_id: String,
+    username: Option<String>,
+    password: Option<String>,
+) -> AppResult<RssFeed> {
+    RssService::set_feed_credentials(&state.db(), &feed_id, username, password).await
+}
+
+/// 获取自动停用RSS源的连续失败次数
+#[tauri::command]
+pub async fn get_auto_disable_failures(state: State<'_, AppState>) -> AppResult<u32> {
+    RssService::get_auto_disable_failures(&state.db()).await
+}
+
+/// 设置自动停用RSS源的连续失败次数，设为0时不自动停用
+#[tauri::command]
+pub async fn set_auto_disable_failures(state: State<'_, AppState>, failures: u32) -> AppResult<()> {
+    RssService::set_auto_disable_failures(&state.db(), failures).await
 }
 
 /// 获取统计信息
 #[tauri::command]
 pub async fn get_statistics(state: State<'_, AppState>) -> AppResult<serde_json::Value> {
-    RssService::get_statistics(&state.db).await
+    RssService::get_statistics(&state.db()).await
+}
+
+/// 获取每个RSS源占用的存储空间
+#[tauri::command]
+pub async fn get_storage_by_feed(state: State<'_, AppState>) -> AppResult<Vec<FeedStorage>> {
+    RssService::get_storage_by_feed(&state.db()).await
+}
+
+/// 获取RSS源的发文频率
+#[tauri::command]
+pub async fn get_feed_cadence(state: State<'_, AppState>, feed_id: String) -> AppResult<FeedCadence> {
+    RssService::get_feed_cadence(&state.db(), feed_id).await
+}
+
+/// 获取连续阅读天数
+#[tauri::command]
+pub async fn get_reading_streak(state: State<'_, AppState>) -> AppResult<ReadingStreak> {
+    RssService::get_reading_streak(&state.db()).await
+}
+
+/// 按星期和小时统计阅读习惯
+#[tauri::command]
+pub async fn get_reading_by_weekday(state: State<'_, AppState>) -> AppResult<ReadingHeatmap> {
+    RssService::get_reading_by_weekday(&state.db()).await
+}
+
+/// 重建全文搜索索引
+#[tauri::command]
+pub async fn rebuild_search_index(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+) -> AppResult<SearchIndexProgress> {
+    SearchService::rebuild_index(&state.db(), |progress| {
+        let _ = app_handle.emit("search-index-progress", progress);
+    })
+    .await
+}
+
+/// 根据已存储的内容重新计算文章的字数和阅读时间
+#[tauri::command]
+pub async fn recompute_reading_metrics(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    feed_id: Option<String>,
+) -> AppResult<ReadingMetricsProgress> {
+    RssService::recompute_reading_metrics(&state.db(), feed_id, |progress| {
+        let _ = app_handle.emit("reading-metrics-progress", progress);
+    })
+    .await
+}
+
+/// 全文搜索文章
+#[tauri::command]
+pub async fn search_articles(
+    state: State<'_, AppState>,
+    query: String,
+    filters: Option<SearchFilters>,
+    limit: Option<i32>,
+    offset: Option<i32>,
+) -> AppResult<Vec<RssArticle>> {
+    let filters = filters.unwrap_or_default();
+    SearchService::search_articles(&state.db(), &query, &filters, limit, offset).await
+}
+
+/// 获取搜索结果中相邻的文章
+#[tauri::command]
+pub async fn get_adjacent_in_search(
+    state: State<'_, AppState>,
+    current_id: String,
+    query: String,
+    filters: Option<SearchFilters>,
+    direction: SearchDirection,
+) -> AppResult<Option<RssArticle>> {
+    let filters = filters.unwrap_or_default();
+    SearchService::get_adjacent(&state.db(), &current_id, &query, &filters, direction).await
+}
+
+/// 开启或关闭新文章内容的压缩存储
+#[tauri::command]
+pub async fn set_content_compression(state: State<'_, AppState>, enabled: bool) -> AppResult<()> {
+    compression::set_enabled(&state.db(), enabled).await
+}
+
+/// 将已有的明文文章内容迁移为压缩存储
+#[tauri::command]
+pub async fn compress_existing_content(
+    state: State<'_, AppState>,
+) -> AppResult<ContentCompressionReport> {
+    compression::compress_existing_content(&state.db()).await
+}
+
+/// 开启或关闭仅IPv4抓取
+#[tauri::command]
+pub async fn set_ipv4_only(state: State<'_, AppState>, enabled: bool) -> AppResult<()> {
+    network::set_ipv4_only(&state.db(), enabled).await
+}
+
+/// 获取请求超时和User-Agent设置
+#[tauri::command]
+pub async fn get_http_settings() -> AppResult<HttpSettings> {
+    Ok(network::http_settings())
+}
+
+/// 设置请求超时（秒）和User-Agent，User-Agent为空时恢复默认值
+#[tauri::command]
+pub async fn set_http_settings(
+    state: State<'_, AppState>,
+    timeout_secs: u32,
+    user_agent: Option<String>,
+) -> AppResult<()> {
+    network::set_http_settings(&state.db(), timeout_secs, user_agent).await
+}
+
+/// 获取外部渲染服务地址
+#[tauri::command]
+pub async fn get_render_service_url() -> AppResult<Option<String>> {
+    Ok(render::service_url())
+}
+
+/// 设置外部渲染服务地址，为空时关闭
+#[tauri::command]
+pub async fn set_render_service_url(state: State<'_, AppState>, url: Option<String>) -> AppResult<()> {
+    render::set_service_url(&state.db(), url).await
+}
+
+/// 获取文章全文及提取方式（静态抓取或渲染服务），返回前清理脚本和事件属性
+#[tauri::command]
+pub async fn extract_article_content(url: String) -> AppResult<Option<ExtractedContent>> {
+    Ok(RssService::extract_article_content_detailed(&url, false)
+        .await
+        .map(|extracted| ExtractedContent {
+            content: sanitize::sanitize_html(&extracted.content),
+            ..extracted
+        }))
+}
+
+/// 获取所有站点提取规则
+#[tauri::command]
+pub async fn get_site_rules(state: State<'_, AppState>) -> AppResult<Vec<SiteRule>> {
+    site_rules::get_rules(&state.db()).await
+}
+
+/// 新增或修改站点提取规则
+#[tauri::command]
+pub async fn set_site_rule(state: State<'_, AppState>, request: SiteRuleRequest) -> AppResult<SiteRule> {
+    site_rules::set_rule(&state.db(), request).await
+}
+
+/// 删除站点提取规则，返回规则是否存在
+#[tauri::command]
+pub async fn delete_site_rule(state: State<'_, AppState>, domain: String) -> AppResult<bool> {
+    site_rules::delete_rule(&state.db(), &domain).await
+}
+
+/// 设置代理，地址都为空时清除
+#[tauri::command]
+pub async fn set_proxy(state: State<'_, AppState>, proxy: ProxySettings) -> AppResult<()> {
+    network::set_proxy(&state.db(), proxy).await
+}
+
+/// 用给定的代理设置请求测试地址，不保存设置
+#[tauri::command]
+pub async fn test_proxy(proxy: ProxySettings, url: Option<String>) -> AppResult<ProxyTestResult> {
+    network::test_proxy(proxy, url.as_deref()).await
+}
+
+/// 检查数据库完整性
+#[tauri::command]
+pub async fn check_database_integrity(state: State<'_, AppState>) -> AppResult<DatabaseIntegrityReport> {
+    database::check_integrity(&state.db()).await
+}
+
+/// 执行VACUUM回收数据库空间，返回回收前后的文件大小
+#[tauri::command]
+pub async fn vacuum_database(state: State<'_, AppState>) -> AppResult<VacuumReport> {
+    database::vacuum(&state.db(), &utils::get_database_path()?).await
+}
+
+/// 获取数据库文件大小（字节）
+#[tauri::command]
+pub async fn get_database_size() -> AppResult<u64> {
+    database::database_size(&utils::get_database_path()?)
+}
+
+/// 将数据库备份到指定路径，返回备份文件的字节数
+#[tauri::command]
+pub async fn backup_database(state: State<'_, AppState>, dest_path: String) -> AppResult<u64> {
+    backup::backup_database(&state.db(), std::path::Path::new(&dest_path)).await
+}
+
+/// 从备份文件恢复数据库，用恢复的数据库重启后台调度器，并重新加载缓存的网络、渲染服务和站点规则设置
+#[tauri::command]
+pub async fn restore_database(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    src_path: String,
+) -> AppResult<()> {
+    backup::restore_database(&state, std::path::Path::new(&src_path), &utils::get_database_path()?).await?;
+
+    let db = state.db();
+    state.set_scheduler(scheduler::start(db.clone(), app_handle));
+    network::load_settings(&db).await?;
+    render::load_service_url(&db).await?;
+    site_rules::load_rules(&db).await
+}
+
+/// 设置每日自动备份及保留的备份数
+#[tauri::command]
+pub async fn set_auto_backup(
+    state: State<'_, AppState>,
+    enabled: bool,
+    keep_count: u32,
+) -> AppResult<()> {
+    backup::set_auto_backup(&state.db(), enabled, keep_count).await
+}
+
+/// 获取指定时间之后新增文章的摘要
+#[tauri::command]
+pub async fn get_digest_since(
+    state: State<'_, AppState>,
+    timestamp: DateTime<Utc>,
+) -> AppResult<Digest> {
+    digest::get_digest_since(&state.db(), timestamp).await
+}
+
+/// 开启或关闭定期摘要
+#[tauri::command]
+pub async fn set_digest_enabled(state: State<'_, AppState>, enabled: bool) -> AppResult<()> {
+    digest::set_digest_enabled(&state.db(), enabled).await
+}
+
+/// 暂停后台自动刷新
+#[tauri::command]
+pub async fn pause_scheduler(state: State<'_, AppState>) -> AppResult<()> {
+    scheduler::set_paused(&state.db(), true).await
+}
+
+/// 恢复后台自动刷新
+#[tauri::command]
+pub async fn resume_scheduler(state: State<'_, AppState>) -> AppResult<()> {
+    scheduler::set_paused(&state.db(), false).await
+}
+
+/// 获取后台自动刷新是否已暂停
+#[tauri::command]
+pub async fn is_scheduler_paused(state: State<'_, AppState>) -> AppResult<bool> {
+    scheduler::is_paused(&state.db()).await
+}
+
+/// 获取后台自动刷新的间隔（分钟）
+#[tauri::command]
+pub async fn get_refresh_interval(state: State<'_, AppState>) -> AppResult<u32> {
+    scheduler::get_refresh_interval(&state.db()).await
+}
+
+/// 设置后台自动刷新的间隔（分钟）
+#[tauri::command]
+pub async fn set_refresh_interval(state: State<'_, AppState>, minutes: u32) -> AppResult<()> {
+    scheduler::set_refresh_interval(&state.db(), minutes).await
 }
 
 /// 保留原有的greet函数用于基本测试
@@ -0,0 +1,135 @@
+use crate::error::{AppError, AppResult};
+use crate::models::ContentCompressionReport;
+use crate::search::SearchService;
+use crate::settings;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use log::info;
+use sqlx::{Row, SqlitePool};
+use std::io::{Read, Write};
+
+/// content_compression列中gzip算法的标记
+pub const GZIP: &str = "gzip";
+
+/// 小于该长度的内容压缩收益有限，保持明文存储
+const MIN_COMPRESS_BYTES: usize = 512;
+
+/// 压缩已有内容时每批处理的文章数
+const COMPRESS_BATCH_SIZE: i64 = 200;
+
+/// 待写入数据库的文章内容，压缩存储时plain保留明文用于全文索引
+#[derive(Debug, Default)]
+pub struct StoredContent {
+    pub content: Option<String>,
+    pub compressed: Option<Vec<u8>>,
+    pub compression: Option<&'static str>,
+    pub plain: Option<String>,
+}
+
+/// gzip压缩文本
+pub fn compress(text: &str) -> AppResult<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(text.as_bytes())?;
+    Ok(encoder.finish()?)
+}
+
+/// 解压gzip压缩的文本
+pub fn decompress(bytes: &[u8]) -> AppResult<String> {
+    let mut text = String::new();
+    GzDecoder::new(bytes).read_to_string(&mut text)?;
+    Ok(text)
+}
+
+/// 按算法标记还原存储的内容
+pub fn decode(compression: Option<&str>, compressed: Option<&[u8]>) -> AppResult<Option<String>> {
+    match (compression, compressed) {
+        (Some(GZIP), Some(bytes)) => decompress(bytes).map(Some),
+        (Some(other), Some(_)) => Err(AppError::internal(format!("未知的内容压缩算法: {}", other))),
+        _ => Ok(None),
+    }
+}
+
+/// 根据设置决定内容以明文还是压缩形式存储
+pub fn encode(content: Option<String>, enabled: bool) -> AppResult<StoredContent> {
+    match content {
+        Some(text) if enabled && text.len() >= MIN_COMPRESS_BYTES => Ok(StoredContent {
+            content: None,
+            compressed: Some(compress(&text)?),
+            compression: Some(GZIP),
+            plain: Some(text),
+        }),
+        content => Ok(StoredContent {
+            content,
+            ..Default::default()
+        }),
+    }
+}
+
+/// 新写入的文章内容是否压缩存储
+pub async fn is_enabled(db: &SqlitePool) -> AppResult<bool> {
+    settings::get_bool(db, settings::COMPRESS_CONTENT, false).await
+}
+
+/// 开启或关闭新内容的压缩存储，已存储的内容不受影响
+pub async fn set_enabled(db: &SqlitePool, enabled: bool) -> AppResult<()> {
+    settings::set_bool(db, settings::COMPRESS_CONTENT, enabled).await
+}
+
+/// 将以明文存储的文章内容迁移为压缩存储，返回节省的空间
+/// 压缩前先将明文写入全文索引，压缩后的正文仍可搜索
+pub async fn compress_existing_content(db: &SqlitePool) -> AppResult<ContentCompressionReport> {
+    let mut report = ContentCompressionReport::default();
+    let mut last_rowid: i64 = 0;
+
+    loop {
+        let rows = sqlx::query(
+            "SELECT rowid, id, content FROM rss_articles
+             WHERE rowid > ? AND content_compression IS NULL AND length(content) >= ?
+             ORDER BY rowid LIMIT ?",
+        )
+        .bind(last_rowid)
+        .bind(MIN_COMPRESS_BYTES as i64)
+        .bind(COMPRESS_BATCH_SIZE)
+        .fetch_all(db)
+        .await?;
+
+        if rows.is_empty() {
+            break;
+        }
+
+        let mut tx = db.begin().await?;
+        for row in &rows {
+            last_rowid = row.get("rowid");
+            let content: String = row.get("content");
+            let compressed = compress(&content)?;
+
+            // 压缩后反而更大的内容保持明文
+            if compressed.len() >= content.len() {
+                continue;
+            }
+
+            SearchService::index_compressed_content(&mut tx, row.get("id"), &content).await?;
+            sqlx::query(
+                "UPDATE rss_articles SET content = NULL, content_compressed = ?, content_compression = ? WHERE rowid = ?",
+            )
+            .bind(&compressed)
+            .bind(GZIP)
+            .bind(last_rowid)
+            .execute(&mut *tx)
+            .await?;
+
+            report.compressed_articles += 1;
+            report.bytes_before += content.len() as u64;
+            report.bytes_after += compressed.len() as u64;
+        }
+        tx.commit().await?;
+    }
+
+    report.bytes_saved = report.bytes_before - report.bytes_after;
+    info!(
+        "文章内容压缩完成: {} 篇，节省 {} 字节",
+        report.compressed_articles, report.bytes_saved
+    );
+    Ok(report)
+}
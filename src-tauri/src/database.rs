@@ -1,6 +1,11 @@
+use crate::models::{DatabaseIntegrityReport, VacuumReport};
 use crate::{error::AppResult, utils};
-use log::info;
-use sqlx::{migrate::MigrateDatabase, SqlitePool};
+use log::{info, warn};
+use sqlx::{migrate::MigrateDatabase, Row, SqlitePool};
+use std::path::Path;
+
+/// integrity_check最多返回的问题数
+const MAX_INTEGRITY_ERRORS: i64 = 100;
 
 /// 初始化数据库
 pub async fn init_database() -> AppResult<SqlitePool> {
@@ -9,14 +14,18 @@ pub async fn init_database() -> AppResult<SqlitePool> {
 
     // 获取数据库文件路径
     let db_path = utils::get_database_path()?;
+    open_database(&db_path).await
+}
 
+/// 打开指定路径的数据库（不存在时创建）并运行迁移
+pub async fn open_database(db_path: &Path) -> AppResult<SqlitePool> {
     // 如果数据库文件不存在则创建
     if !db_path.exists() {
-        sqlx::Sqlite::create_database(db_path.as_path().to_str().unwrap()).await?;
+        sqlx::Sqlite::create_database(db_path.to_str().unwrap()).await?;
     }
 
     // 获取数据库连接URL
-    let database_url = utils::get_database_url()?;
+    let database_url = format!("sqlite:{}", db_path.display());
     info!("database_url: {:?}", database_url);
 
     // 创建连接池
@@ -27,3 +36,93 @@ pub async fn init_database() -> AppResult<SqlitePool> {
 
     Ok(pool)
 }
+
+/// 检查数据库完整性（integrity_check和foreign_key_check），并返回页数等基本信息
+pub async fn check_integrity(db: &SqlitePool) -> AppResult<DatabaseIntegrityReport> {
+    let integrity_errors: Vec<String> = sqlx::query_scalar::<_, String>(&format!(
+        "PRAGMA integrity_check({})",
+        MAX_INTEGRITY_ERRORS
+    ))
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .filter(|line| line != "ok")
+    .collect();
+
+    let foreign_key_errors: Vec<String> = sqlx::query("PRAGMA foreign_key_check")
+        .fetch_all(db)
+        .await?
+        .iter()
+        .map(|row| {
+            format!(
+                "表 {} 第 {} 行引用的 {} 记录不存在",
+                row.get::<String, _>(0),
+                row.get::<Option<i64>, _>(1).unwrap_or_default(),
+                row.get::<String, _>(2)
+            )
+        })
+        .collect();
+
+    let page_count: i64 = sqlx::query_scalar("PRAGMA page_count").fetch_one(db).await?;
+    let page_size: i64 = sqlx::query_scalar("PRAGMA page_size").fetch_one(db).await?;
+    let freelist_count: i64 = sqlx::query_scalar("PRAGMA freelist_count").fetch_one(db).await?;
+
+    let ok = integrity_errors.is_empty() && foreign_key_errors.is_empty();
+    let suggestion = if !integrity_errors.is_empty() {
+        Some("数据库文件已损坏，建议从备份恢复，或先备份后执行VACUUM重建数据库".to_string())
+    } else if !foreign_key_errors.is_empty() {
+        Some("存在无效的关联记录，建议先备份数据库，再删除相关文章或RSS源".to_string())
+    } else if freelist_count * 4 > page_count {
+        Some("数据库中空闲页较多，可以执行VACUUM回收空间".to_string())
+    } else {
+        None
+    };
+
+    if !ok {
+        warn!(
+            "数据库完整性检查发现问题: {} 个完整性错误，{} 个外键错误",
+            integrity_errors.len(),
+            foreign_key_errors.len()
+        );
+    }
+
+    Ok(DatabaseIntegrityReport {
+        ok,
+        integrity_errors,
+        foreign_key_errors,
+        page_count: page_count as u64,
+        page_size: page_size as u64,
+        freelist_count: freelist_count as u64,
+        suggestion,
+    })
+}
+
+/// 数据库文件占用的字节数，包括WAL文件
+pub fn database_size(path: &Path) -> AppResult<u64> {
+    let mut size = std::fs::metadata(path)?.len();
+    let mut wal = path.as_os_str().to_owned();
+    wal.push("-wal");
+    if let Ok(metadata) = std::fs::metadata(&wal) {
+        size += metadata.len();
+    }
+    Ok(size)
+}
+
+/// 执行VACUUM重建数据库文件并回收空闲页，再执行PRAGMA optimize更新查询统计
+/// VACUUM不能在事务中执行，因此直接在连接池上执行，path为数据库文件路径，用于统计回收的空间
+pub async fn vacuum(db: &SqlitePool, path: &Path) -> AppResult<VacuumReport> {
+    let size_before = database_size(path)?;
+
+    sqlx::query("VACUUM").execute(db).await?;
+    sqlx::query("PRAGMA optimize").execute(db).await?;
+
+    let size_after = database_size(path)?;
+    let reclaimed_bytes = size_before.saturating_sub(size_after);
+    info!("VACUUM完成，回收 {} 字节", reclaimed_bytes);
+
+    Ok(VacuumReport {
+        size_before,
+        size_after,
+        reclaimed_bytes,
+    })
+}
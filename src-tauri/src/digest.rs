@@ -0,0 +1,100 @@
+use crate::error::AppResult;
+use crate::models::{Digest, DigestGroup, RssFeed};
+use crate::rss::{RssService, ARTICLE_COLUMNS};
+use crate::settings;
+use chrono::{DateTime, Duration, Local, Utc};
+use log::info;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+/// 定期摘要的间隔（小时）
+const DIGEST_INTERVAL_HOURS: i64 = 24;
+
+/// 获取指定时间之后新增的文章，按RSS源分组
+/// 只包含活跃的源，不包含被标记为疑似重复的文章
+pub async fn get_digest_since(db: &SqlitePool, since: DateTime<Utc>) -> AppResult<Digest> {
+    // 获取当前本地时间并转换为UTC时间
+    let until = Local::now().with_timezone(&Utc);
+
+    let rows = sqlx::query(&format!(
+        "SELECT a.* FROM (SELECT {} FROM rss_articles) a
+         JOIN rss_feeds f ON f.id = a.feed_id
+         WHERE f.is_active = 1 AND a.duplicate_of IS NULL AND a.created_at > ?
+         ORDER BY a.feed_id, a.published_at DESC, a.created_at DESC",
+        ARTICLE_COLUMNS
+    ))
+    .bind(since.to_rfc3339())
+    .fetch_all(db)
+    .await?;
+
+    let mut feeds: HashMap<String, RssFeed> = RssService::get_feeds(db, true)
+        .await?
+        .into_iter()
+        .map(|feed| (feed.id.clone(), feed))
+        .collect();
+
+    let mut groups: Vec<DigestGroup> = Vec::new();
+    for article in rows.iter().map(RssService::article_from_row) {
+        match groups.last_mut() {
+            Some(group) if group.feed.id == article.feed_id => group.articles.push(article),
+            _ => {
+                if let Some(feed) = feeds.remove(&article.feed_id) {
+                    groups.push(DigestGroup {
+                        feed,
+                        count: 0,
+                        articles: vec![article],
+                    });
+                }
+            }
+        }
+    }
+
+    for group in &mut groups {
+        group.count = group.articles.len() as u32;
+    }
+    // 新文章多的源排在前面
+    groups.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.feed.title.cmp(&b.feed.title)));
+
+    Ok(Digest {
+        since,
+        until,
+        total: groups.iter().map(|g| g.count).sum(),
+        groups,
+    })
+}
+
+/// 开启或关闭定期摘要
+pub async fn set_digest_enabled(db: &SqlitePool, enabled: bool) -> AppResult<()> {
+    settings::set_bool(db, settings::DIGEST_ENABLED, enabled).await
+}
+
+/// 摘要到期时生成自上次摘要以来的新文章摘要并记录生成时间
+/// 首次生成时包含最近一个周期的文章；没有新文章时也推进时间，避免重复统计
+pub async fn generate_digest_if_due(db: &SqlitePool) -> AppResult<Option<Digest>> {
+    if !settings::get_bool(db, settings::DIGEST_ENABLED, false).await? {
+        return Ok(None);
+    }
+
+    // 获取当前本地时间并转换为UTC时间
+    let now = Local::now().with_timezone(&Utc);
+    let last_digest_at = settings::get_setting(db, settings::DIGEST_LAST_AT)
+        .await?
+        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+    if let Some(last_digest_at) = last_digest_at {
+        if now.signed_duration_since(last_digest_at).num_hours() < DIGEST_INTERVAL_HOURS {
+            return Ok(None);
+        }
+    }
+
+    let since = last_digest_at.unwrap_or(now - Duration::hours(DIGEST_INTERVAL_HOURS));
+    let digest = get_digest_since(db, since).await?;
+    settings::set_setting(db, settings::DIGEST_LAST_AT, &digest.until.to_rfc3339()).await?;
+
+    info!(
+        "生成摘要: {} 个RSS源共 {} 篇新文章",
+        digest.groups.len(),
+        digest.total
+    );
+    Ok(Some(digest))
+}
@@ -10,7 +10,10 @@ pub enum AppError {
     Migration(#[from] sqlx::migrate::MigrateError),
 
     #[error("HTTP请求错误: {0}")]
-    Http(#[from] reqwest::Error),
+    Http(#[source] reqwest::Error),
+
+    #[error("请求超时: {url}")]
+    Timeout { url: String },
 
     #[error("RSS解析错误: {0}")]
     RssParse(#[from] feed_rs::parser::ParseFeedError),
@@ -36,12 +39,21 @@ pub enum AppError {
     #[error("文章未找到: {id}")]
     ArticleNotFound { id: String },
 
-    #[error("无效的RSS URL: {url}")]
-    InvalidRssUrl { url: String },
+    #[error("无效的RSS URL: {url}{}", .hint.as_deref().map(|hint| format!("，{}", hint)).unwrap_or_default())]
+    InvalidRssUrl { url: String, hint: Option<String> },
 
     #[error("RSS源已存在: {url}")]
     FeedAlreadyExists { url: String },
 
+    #[error("无法访问RSS源 {url}: {reason}")]
+    FeedUnreachable { url: String, reason: String },
+
+    #[error("RSS源返回HTTP状态 {status}: {url}")]
+    HttpStatus { url: String, status: u16 },
+
+    #[error("不是有效的RSS源 {url}: {reason}")]
+    NotAFeed { url: String, reason: String },
+
     #[error("配置错误: {message}")]
     Config { message: String },
 
@@ -55,18 +67,79 @@ pub enum AppError {
 /// 应用统一结果类型
 pub type AppResult<T> = Result<T, AppError>;
 
-/// 为Tauri命令实现序列化
+/// 为Tauri命令实现序列化，输出 { code, message } 便于前端区分错误类型
 impl serde::Serialize for AppError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::ser::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        use serde::ser::SerializeStruct;
+
+        // 超时和HTTP状态错误额外输出地址和状态码，便于前端给出针对性的提示
+        let (url, status) = match self {
+            Self::Timeout { url } => (Some(url), None),
+            Self::HttpStatus { url, status } => (Some(url), Some(*status)),
+            _ => (None, None),
+        };
+        let len = 2 + usize::from(url.is_some()) + usize::from(status.is_some());
+        let mut state = serializer.serialize_struct("AppError", len)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        if let Some(url) = url {
+            state.serialize_field("url", url)?;
+        }
+        if let Some(status) = status {
+            state.serialize_field("status", &status)?;
+        }
+        state.end()
+    }
+}
+
+/// 超时和带状态码的错误（如error_for_status）转换为专门的错误类型，其余保留为Http
+impl From<reqwest::Error> for AppError {
+    fn from(e: reqwest::Error) -> Self {
+        let url = e.url().map(|url| url.to_string()).unwrap_or_default();
+        if e.is_timeout() {
+            return Self::Timeout { url };
+        }
+        match e.status() {
+            Some(status) => Self::HttpStatus {
+                url,
+                status: status.as_u16(),
+            },
+            None => Self::Http(e),
+        }
     }
 }
 
 /// 便捷的错误构造函数
 impl AppError {
+    /// 机器可读的错误码
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Database(_) => "database",
+            Self::Migration(_) => "migration",
+            Self::Http(_) => "http",
+            Self::Timeout { .. } => "timeout",
+            Self::RssParse(_) => "rss_parse",
+            Self::Io(_) => "io",
+            Self::Serde(_) => "serde",
+            Self::Uuid(_) => "uuid",
+            Self::UrlParse(_) => "url_parse",
+            Self::Tauri(_) => "tauri",
+            Self::FeedNotFound { .. } => "feed_not_found",
+            Self::ArticleNotFound { .. } => "article_not_found",
+            Self::InvalidRssUrl { .. } => "invalid_url",
+            Self::FeedAlreadyExists { .. } => "feed_already_exists",
+            Self::FeedUnreachable { .. } => "feed_unreachable",
+            Self::HttpStatus { .. } => "http_status",
+            Self::NotAFeed { .. } => "not_a_feed",
+            Self::Config { .. } => "config",
+            Self::Validation { .. } => "validation",
+            Self::Internal { .. } => "internal",
+        }
+    }
+
     pub fn config(message: impl Into<String>) -> Self {
         Self::Config {
             message: message.into(),
@@ -94,10 +167,58 @@ impl AppError {
     }
 
     pub fn invalid_rss_url(url: impl Into<String>) -> Self {
-        Self::InvalidRssUrl { url: url.into() }
+        Self::InvalidRssUrl {
+            url: url.into(),
+            hint: None,
+        }
+    }
+
+    /// 带处理建议的无效URL错误
+    pub fn invalid_rss_url_with_hint(url: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self::InvalidRssUrl {
+            url: url.into(),
+            hint: Some(hint.into()),
+        }
     }
 
     pub fn feed_already_exists(url: impl Into<String>) -> Self {
         Self::FeedAlreadyExists { url: url.into() }
     }
+
+    pub fn feed_unreachable(url: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::FeedUnreachable {
+            url: url.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// 请求失败时按原因区分：超时、HTTP状态错误或其他无法访问的情况
+    pub fn request_failed(url: impl Into<String>, e: &reqwest::Error) -> Self {
+        let url = url.into();
+        if e.is_timeout() {
+            return Self::Timeout { url };
+        }
+        match e.status() {
+            Some(status) => Self::http_status(url, status.as_u16()),
+            None => Self::feed_unreachable(url, e.to_string()),
+        }
+    }
+
+    pub fn timeout(url: impl Into<String>) -> Self {
+        Self::Timeout { url: url.into() }
+    }
+
+    pub fn http_status(url: impl Into<String>, status: u16) -> Self {
+        Self::HttpStatus {
+            url: url.into(),
+            status,
+        }
+    }
+
+    pub fn not_a_feed(url: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::NotAFeed {
+            url: url.into(),
+            reason: reason.into(),
+        }
+    }
 }
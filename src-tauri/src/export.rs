@@ -0,0 +1,255 @@
+use crate::error::AppResult;
+use crate::models::{ExportFormat, RssArticle, RssArticleWithFeed};
+use crate::rss::{RssService, ARTICLE_COLUMNS};
+use chrono::{Local, Utc};
+use sqlx::{Row, SqlitePool};
+use std::fmt::Write;
+use std::path::Path;
+
+/// 导出的收藏源的固定标识
+const STARRED_FEED_ID: &str = "urn:you-know:starred";
+
+/// 转义XML文本和属性值中的特殊字符
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 将收藏的文章导出为Atom 1.0文档
+pub async fn export_starred_atom(db: &SqlitePool) -> AppResult<String> {
+    let rows = sqlx::query(&format!(
+        "SELECT {} FROM rss_articles
+         WHERE is_starred = 1 ORDER BY published_at DESC, created_at DESC",
+        ARTICLE_COLUMNS
+    ))
+    .fetch_all(db)
+    .await?;
+
+    let articles: Vec<RssArticle> = rows.iter().map(RssService::article_from_row).collect();
+
+    // 获取当前本地时间并转换为UTC时间
+    let now = Local::now().with_timezone(&Utc);
+    Ok(build_atom(&articles, now.to_rfc3339()))
+}
+
+/// 将一个RSS源的所有文章导出为Markdown文档，按发布时间从早到晚排列
+pub async fn export_feed_markdown(db: &SqlitePool, feed_id: &str) -> AppResult<String> {
+    let feed = RssService::get_feed(db, feed_id).await?;
+    let rows = sqlx::query(&format!(
+        "SELECT {} FROM rss_articles
+         WHERE feed_id = ? ORDER BY COALESCE(published_at, created_at), rowid",
+        ARTICLE_COLUMNS
+    ))
+    .bind(feed_id)
+    .fetch_all(db)
+    .await?;
+    let articles: Vec<RssArticle> = rows.iter().map(RssService::article_from_row).collect();
+
+    // 获取当前本地时间并转换为UTC时间
+    let now = Local::now().with_timezone(&Utc);
+    Ok(build_markdown(&feed.title, &articles, now.to_rfc3339()))
+}
+
+/// 将带有指定标签（名称不区分大小写）的所有文章导出到dest_path，按发布时间从早到晚排列
+/// 每篇文章都注明所属RSS源，返回导出的文章数
+pub async fn export_tag(db: &SqlitePool, tag: &str, format: ExportFormat, dest_path: &Path) -> AppResult<u32> {
+    let tag = tag.trim();
+    let rows = sqlx::query(&format!(
+        "SELECT {}, (SELECT title FROM rss_feeds WHERE id = rss_articles.feed_id) AS feed_title
+         FROM rss_articles
+         WHERE id IN (
+             SELECT at.article_id FROM article_tags at
+             JOIN tags t ON t.id = at.tag_id
+             WHERE t.name = ?
+         )
+         ORDER BY COALESCE(published_at, created_at), rowid",
+        ARTICLE_COLUMNS
+    ))
+    .bind(tag)
+    .fetch_all(db)
+    .await?;
+    let articles: Vec<RssArticleWithFeed> = rows
+        .iter()
+        .map(|row| RssArticleWithFeed {
+            article: RssService::article_from_row(row),
+            feed_title: row.get::<Option<String>, _>("feed_title").unwrap_or_default(),
+        })
+        .collect();
+
+    // 获取当前本地时间并转换为UTC时间
+    let now = Local::now().with_timezone(&Utc);
+    let document = match format {
+        ExportFormat::Markdown => build_tag_markdown(tag, &articles, now.to_rfc3339()),
+        ExportFormat::Html => build_html(tag, &articles, now.to_rfc3339()),
+        ExportFormat::Json => serde_json::to_string_pretty(&articles)?,
+    };
+    std::fs::write(dest_path, document)?;
+
+    Ok(articles.len() as u32)
+}
+
+/// 生成Markdown文档：开头是源标题和导出时间，每篇文章一节
+/// 文章标题为一级标题，其后是发布时间、作者和原文链接，正文由HTML转换而来，没有正文时使用摘要
+pub fn build_markdown(feed_title: &str, articles: &[RssArticle], exported_at: String) -> String {
+    let mut markdown = String::new();
+    let _ = writeln!(markdown, "# {}\n", feed_title.trim());
+    let _ = writeln!(markdown, "导出时间: {}", exported_at);
+
+    for article in articles {
+        push_markdown_article(&mut markdown, article, None);
+    }
+
+    markdown
+}
+
+/// 生成按标签导出的Markdown文档，与build_markdown格式相同，每篇文章额外注明所属RSS源
+pub fn build_tag_markdown(tag: &str, articles: &[RssArticleWithFeed], exported_at: String) -> String {
+    let mut markdown = String::new();
+    let _ = writeln!(markdown, "# 标签: {}\n", tag);
+    let _ = writeln!(markdown, "导出时间: {}", exported_at);
+
+    for item in articles {
+        push_markdown_article(&mut markdown, &item.article, Some(&item.feed_title));
+    }
+
+    markdown
+}
+
+/// 追加一篇文章的Markdown小节，feed_title不为空时写在元数据最前面
+fn push_markdown_article(markdown: &mut String, article: &RssArticle, feed_title: Option<&str>) {
+    markdown.push_str("\n---\n\n");
+    let _ = writeln!(markdown, "# {}\n", article.title.trim());
+
+    let mut metadata = Vec::new();
+    if let Some(feed_title) = feed_title.map(str::trim).filter(|t| !t.is_empty()) {
+        metadata.push(feed_title.to_string());
+    }
+    if let Some(published_at) = article.published_at {
+        metadata.push(published_at.format("%Y-%m-%d %H:%M").to_string());
+    }
+    if let Some(author) = article.author.as_deref().filter(|a| !a.trim().is_empty()) {
+        metadata.push(author.trim().to_string());
+    }
+    if let Some(link) = &article.link {
+        metadata.push(format!("[原文]({})", link));
+    }
+    if !metadata.is_empty() {
+        let _ = writeln!(markdown, "*{}*\n", metadata.join(" · "));
+    }
+
+    let body = article_body(article)
+        .map(|html| html2md::parse_html(html).trim().to_string())
+        .unwrap_or_default();
+    if !body.is_empty() {
+        let _ = writeln!(markdown, "{}", body);
+    }
+}
+
+/// 文章正文，没有正文时使用摘要
+fn article_body(article: &RssArticle) -> Option<&str> {
+    article
+        .content
+        .as_deref()
+        .filter(|content| !content.trim().is_empty())
+        .or(article.description.as_deref())
+}
+
+/// 生成HTML文档：每篇文章一个article元素，包含所属RSS源、发布时间、作者、原文链接和正文
+pub fn build_html(title: &str, articles: &[RssArticleWithFeed], exported_at: String) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(html, "<title>{}</title>", escape_xml(title));
+    html.push_str("</head>\n<body>\n");
+    let _ = writeln!(html, "<h1>{}</h1>", escape_xml(title));
+    let _ = writeln!(html, "<p>导出时间: {}</p>", escape_xml(&exported_at));
+
+    for item in articles {
+        let article = &item.article;
+        html.push_str("<article>\n");
+        let _ = writeln!(html, "<h2>{}</h2>", escape_xml(article.title.trim()));
+
+        let mut metadata = Vec::new();
+        if !item.feed_title.trim().is_empty() {
+            metadata.push(escape_xml(item.feed_title.trim()));
+        }
+        if let Some(published_at) = article.published_at {
+            metadata.push(format!(
+                "<time datetime=\"{}\">{}</time>",
+                published_at.to_rfc3339(),
+                published_at.format("%Y-%m-%d %H:%M")
+            ));
+        }
+        if let Some(author) = article.author.as_deref().filter(|a| !a.trim().is_empty()) {
+            metadata.push(escape_xml(author.trim()));
+        }
+        if let Some(link) = &article.link {
+            metadata.push(format!("<a href=\"{}\">原文</a>", escape_xml(link)));
+        }
+        if !metadata.is_empty() {
+            let _ = writeln!(html, "<p>{}</p>", metadata.join(" · "));
+        }
+
+        if let Some(body) = article_body(article) {
+            let _ = writeln!(html, "{}", body.trim());
+        }
+        html.push_str("</article>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// 生成Atom文档，updated为文档的更新时间
+pub fn build_atom(articles: &[RssArticle], updated: String) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    let _ = writeln!(xml, "  <id>{}</id>", STARRED_FEED_ID);
+    xml.push_str("  <title>Starred articles</title>\n");
+    let _ = writeln!(xml, "  <updated>{}</updated>", escape_xml(&updated));
+    // Atom要求每个条目或整个源至少有一个作者
+    xml.push_str("  <author><name>you-know</name></author>\n");
+
+    for article in articles {
+        let entry_updated = article.published_at.unwrap_or(article.created_at).to_rfc3339();
+        let id = article
+            .guid
+            .clone()
+            .filter(|guid| guid.contains(':'))
+            .unwrap_or_else(|| format!("urn:uuid:{}", article.id));
+
+        xml.push_str("  <entry>\n");
+        let _ = writeln!(xml, "    <id>{}</id>", escape_xml(&id));
+        let _ = writeln!(xml, "    <title>{}</title>", escape_xml(&article.title));
+        if let Some(link) = &article.link {
+            let _ = writeln!(xml, "    <link rel=\"alternate\" href=\"{}\"/>", escape_xml(link));
+        }
+        let _ = writeln!(xml, "    <updated>{}</updated>", entry_updated);
+        if let Some(published_at) = article.published_at {
+            let _ = writeln!(xml, "    <published>{}</published>", published_at.to_rfc3339());
+        }
+        if let Some(author) = &article.author {
+            let _ = writeln!(xml, "    <author><name>{}</name></author>", escape_xml(author));
+        }
+        if let Some(summary) = &article.description {
+            let _ = writeln!(xml, "    <summary type=\"html\">{}</summary>", escape_xml(summary));
+        }
+        if let Some(content) = &article.content {
+            let _ = writeln!(xml, "    <content type=\"html\">{}</content>", escape_xml(content));
+        }
+        xml.push_str("  </entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
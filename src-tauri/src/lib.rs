@@ -1,9 +1,25 @@
 // 声明模块
+pub mod backup;
+pub mod cleanup;
 pub mod commands;
+pub mod compression;
 pub mod database;
+pub mod digest;
 pub mod error;
+pub mod export;
+pub mod links;
 pub mod models;
+pub mod network;
+pub mod opml;
+pub mod parser;
+pub mod render;
 pub mod rss;
+pub mod sanitize;
+pub mod scheduler;
+pub mod search;
+pub mod settings;
+pub mod site_rules;
+pub mod storage;
 pub mod utils;
 
 #[cfg(test)]
@@ -0,0 +1,157 @@
+use crate::error::AppResult;
+use crate::models::{DeadLink, LinkCheckProgress, LinkCheckSummary};
+use crate::network;
+use chrono::{Local, Utc};
+use log::info;
+use sqlx::{Row, SqlitePool};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{mpsc, Semaphore};
+use url::Url;
+
+/// 链接有效
+pub const LINK_OK: &str = "ok";
+/// 服务器返回404
+pub const LINK_NOT_FOUND: &str = "not_found";
+/// 服务器返回410，内容已永久删除
+pub const LINK_GONE: &str = "gone";
+/// 网络错误、无效地址或其他HTTP错误
+pub const LINK_ERROR: &str = "error";
+
+/// 同一主机两次请求之间的间隔（毫秒）
+const PER_HOST_DELAY_MS: u64 = 200;
+
+/// 默认同时检查的主机数
+const DEFAULT_LINK_CHECK_CONCURRENCY: usize = 4;
+
+/// 根据HTTP状态码判断链接状态，None表示请求失败
+pub fn classify_status(status: Option<u16>) -> &'static str {
+    match status {
+        Some(200..=399) => LINK_OK,
+        Some(404) => LINK_NOT_FOUND,
+        Some(410) => LINK_GONE,
+        _ => LINK_ERROR,
+    }
+}
+
+/// 检查单个链接：先发HEAD请求，服务器不支持HEAD时改用GET
+async fn check_link(client: &reqwest::Client, link: &str) -> &'static str {
+    let status = match client.head(link).send().await {
+        Ok(response)
+            if response.status() == reqwest::StatusCode::METHOD_NOT_ALLOWED
+                || response.status() == reqwest::StatusCode::NOT_IMPLEMENTED =>
+        {
+            client.get(link).send().await.ok().map(|r| r.status().as_u16())
+        }
+        Ok(response) => Some(response.status().as_u16()),
+        Err(_) => None,
+    };
+    classify_status(status)
+}
+
+/// 检查已保存文章的链接是否仍可访问，并记录每篇文章的link_status
+/// 按主机分组，同一主机串行请求并限速，不同主机并发检查
+pub async fn check_article_links<F>(
+    db: &SqlitePool,
+    feed_id: Option<String>,
+    concurrency: Option<usize>,
+    on_progress: F,
+) -> AppResult<LinkCheckSummary>
+where
+    F: Fn(&LinkCheckProgress),
+{
+    let rows = sqlx::query(
+        "SELECT id, title, link FROM rss_articles
+         WHERE link IS NOT NULL AND link != '' AND (? IS NULL OR feed_id = ?)",
+    )
+    .bind(&feed_id)
+    .bind(&feed_id)
+    .fetch_all(db)
+    .await?;
+
+    let mut titles: HashMap<String, (String, String)> = HashMap::new();
+    let mut by_host: HashMap<String, Vec<(String, String)>> = HashMap::new();
+    let mut invalid: Vec<String> = Vec::new();
+    for row in &rows {
+        let id: String = row.get("id");
+        let link: String = row.get("link");
+        titles.insert(id.clone(), (row.get("title"), link.clone()));
+        match Url::parse(&link).ok().and_then(|url| url.host_str().map(|h| h.to_string())) {
+            Some(host) => by_host.entry(host).or_default().push((id, link)),
+            None => invalid.push(id),
+        }
+    }
+
+    let mut progress = LinkCheckProgress {
+        checked: 0,
+        total: rows.len() as u32,
+        dead: 0,
+    };
+    on_progress(&progress);
+
+    let client = network::client_builder()
+        .timeout(std::time::Duration::from_secs(15))
+        .build()?;
+    let concurrency = concurrency.unwrap_or(DEFAULT_LINK_CHECK_CONCURRENCY).max(1);
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let (sender, mut receiver) = mpsc::unbounded_channel::<(String, &'static str)>();
+
+    // 无效地址无需请求
+    for id in invalid {
+        let _ = sender.send((id, LINK_ERROR));
+    }
+
+    for (_, links) in by_host {
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+        let sender = sender.clone();
+        tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            for (index, (id, link)) in links.into_iter().enumerate() {
+                if index > 0 {
+                    tokio::time::sleep(std::time::Duration::from_millis(PER_HOST_DELAY_MS)).await;
+                }
+                let status = check_link(&client, &link).await;
+                if sender.send((id, status)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    drop(sender);
+
+    let mut summary = LinkCheckSummary::default();
+    while let Some((id, status)) = receiver.recv().await {
+        // 获取当前本地时间并转换为UTC时间
+        let now = Local::now().with_timezone(&Utc);
+        sqlx::query("UPDATE rss_articles SET link_status = ?, link_checked_at = ? WHERE id = ?")
+            .bind(status)
+            .bind(now.to_rfc3339())
+            .bind(&id)
+            .execute(db)
+            .await?;
+
+        summary.checked += 1;
+        if status == LINK_OK {
+            summary.ok += 1;
+        } else if let Some((title, link)) = titles.remove(&id) {
+            summary.dead.push(DeadLink {
+                article_id: id,
+                title,
+                link,
+                status: status.to_string(),
+            });
+        }
+
+        progress.checked = summary.checked;
+        progress.dead = summary.dead.len() as u32;
+        on_progress(&progress);
+    }
+
+    info!(
+        "链接检查完成: 共 {} 个，失效 {} 个",
+        summary.checked,
+        summary.dead.len()
+    );
+    Ok(summary)
+}
@@ -6,7 +6,8 @@ use log::{error, info};
 use tauri::Manager;
 use tauri_plugin_log::{Target, TargetKind};
 use you_know_lib::models::AppState;
-use you_know_lib::{commands, database, utils};
+use you_know_lib::rss::RssService;
+use you_know_lib::{commands, database, network, render, scheduler, site_rules, utils};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -36,22 +37,158 @@ pub fn run() {
                 }
             });
 
-            // 设置应用状态
-            app.manage(AppState { db });
+            // 加载网络设置，之后创建的HTTP客户端都会使用
+            if let Err(e) = tauri::async_runtime::block_on(network::load_settings(&db)) {
+                error!("Failed to load network settings: {}", e);
+            }
+            if let Err(e) = tauri::async_runtime::block_on(render::load_service_url(&db)) {
+                error!("Failed to load render service settings: {}", e);
+            }
+            if let Err(e) = tauri::async_runtime::block_on(site_rules::load_rules(&db)) {
+                error!("Failed to load site extraction rules: {}", e);
+            }
+
+            // 为旧版本或恢复的备份中的文章补全规范化链接，并合并同一源中链接重复的文章
+            let backfill_db = db.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = RssService::backfill_link_keys(&backfill_db).await {
+                    error!("Failed to backfill article link keys: {}", e);
+                    return;
+                }
+                if let Err(e) = RssService::dedupe_articles_by_link(&backfill_db).await {
+                    error!("Failed to merge duplicate articles: {}", e);
+                }
+            });
+
+            // 全文提取可能需要在后台任务中创建渲染窗口
+            render::init(app.handle().clone());
+
+            // 设置应用状态并启动后台定时刷新，恢复数据库后调度器会用新的连接池重启
+            let state = AppState::new(db.clone());
+            state.set_scheduler(scheduler::start(db, app.handle().clone()));
+            app.manage(state);
             info!("Database initialized successfully");
 
             Ok(())
         })
+        .register_uri_scheme_protocol(render::RESULT_SCHEME, render::handle_result)
         .invoke_handler(tauri::generate_handler![
             commands::add_rss_feed_sync,
             commands::add_rss_feed_async,
+            commands::cancel_fetch,
+            commands::get_feed_info,
+            commands::preview_feed,
             commands::get_rss_feeds,
             commands::get_articles,
+            commands::get_articles_paginated,
+            commands::get_articles_after,
+            commands::get_top_unread_per_feed,
+            commands::get_inbox,
+            commands::get_longform_unread,
+            commands::get_todays_articles,
             commands::get_article_content,
+            commands::force_extract_content,
+            commands::get_article_raw_content,
+            commands::preview_sanitized,
+            commands::set_keep_raw_content,
+            commands::get_mark_read_on_open,
+            commands::set_mark_read_on_open,
+            commands::get_dedup_across_feeds,
+            commands::set_dedup_across_feeds,
             commands::update_article,
+            commands::update_articles_bulk,
+            commands::mark_all_read,
+            commands::get_feed_unread_count,
+            commands::get_total_unread_count,
+            commands::recompute_unread_counts,
+            commands::set_article_reader_prefs,
+            commands::transfer_article_state,
+            commands::add_article_tag,
+            commands::remove_article_tag,
+            commands::list_tags,
+            commands::get_articles_by_tag,
+            commands::move_article,
+            commands::canonicalize_article_link,
+            commands::check_article_links,
+            commands::get_flagged_duplicates,
+            commands::confirm_duplicate,
+            commands::dismiss_duplicate,
             commands::refresh_rss_feed,
+            commands::refresh_feed_diff,
+            commands::force_refresh_feed,
+            commands::set_feed_cron,
+            commands::set_feed_timezone,
+            commands::set_feed_title_strip,
+            commands::set_feed_requires_render,
+            commands::set_feed_max_content,
+            commands::get_max_content_bytes,
+            commands::set_max_content_bytes,
+            commands::set_feed_refresh_interval,
+            commands::set_feed_alternates,
+            commands::refresh_all_rss_feeds,
+            commands::export_opml,
+            commands::validate_opml,
+            commands::import_opml,
+            commands::add_feeds_bulk,
+            commands::get_categories,
+            commands::get_import_concurrency,
+            commands::set_import_concurrency,
+            commands::get_teaser_threshold,
+            commands::set_teaser_threshold,
+            commands::export_starred_atom,
+            commands::export_feed_markdown,
+            commands::export_tag,
+            commands::clear_feed_articles,
+            commands::set_max_total_articles,
+            commands::enforce_article_quota,
+            commands::cleanup_old_articles,
+            commands::prune_articles,
+            commands::deduplicate_all_articles,
             commands::delete_rss_feed,
+            commands::disable_feed,
+            commands::enable_feed,
+            commands::refresh_favicon,
+            commands::get_feed_favicon,
+            commands::get_unhealthy_feeds,
+            commands::reactivate_feed,
+            commands::set_feed_credentials,
+            commands::get_auto_disable_failures,
+            commands::set_auto_disable_failures,
             commands::get_statistics,
+            commands::get_storage_by_feed,
+            commands::get_feed_cadence,
+            commands::get_reading_streak,
+            commands::get_reading_by_weekday,
+            commands::rebuild_search_index,
+            commands::recompute_reading_metrics,
+            commands::search_articles,
+            commands::get_adjacent_in_search,
+            commands::set_content_compression,
+            commands::compress_existing_content,
+            commands::set_auto_backup,
+            commands::backup_database,
+            commands::restore_database,
+            commands::check_database_integrity,
+            commands::vacuum_database,
+            commands::get_database_size,
+            commands::set_ipv4_only,
+            commands::get_http_settings,
+            commands::set_http_settings,
+            commands::get_render_service_url,
+            commands::set_render_service_url,
+            commands::extract_article_content,
+            commands::get_site_rules,
+            commands::set_site_rule,
+            commands::delete_site_rule,
+            commands::set_proxy,
+            commands::test_proxy,
+            commands::get_digest_since,
+            commands::set_digest_enabled,
+            commands::pause_scheduler,
+            commands::resume_scheduler,
+            commands::is_scheduler_paused,
+            commands::get_refresh_interval,
+            commands::set_refresh_interval,
             commands::greet
         ])
         .on_window_event(|_window, f| {
@@ -1,5 +1,8 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use tokio_util::sync::CancellationToken;
 
 
 
@@ -13,6 +16,26 @@ pub struct RssFeed {
     pub website_url: Option<String>,
     pub last_updated: Option<DateTime<Utc>>,
     pub is_active: bool,
+    pub cron: Option<String>,
+    pub alternate_urls: Vec<String>,
+    pub last_fetched_url: Option<String>,
+    pub timezone: Option<String>,
+    pub title_strip_prefix: Option<String>,
+    pub title_strip_is_regex: bool,
+    pub requires_render: bool,
+    pub max_content_bytes: Option<u32>,
+    pub feed_format: Option<String>,
+    pub category_id: Option<String>,
+    pub unread_count: u32,
+    pub refresh_interval_minutes: Option<u32>,
+    // 网站图标地址，图标数据通过get_feed_favicon单独获取
+    pub favicon_url: Option<String>,
+    // 最近一次刷新失败的错误信息和时间，以及连续失败次数（刷新成功时清零）
+    pub last_error: Option<String>,
+    pub last_error_at: Option<DateTime<Utc>>,
+    pub consecutive_failures: u32,
+    // HTTP Basic认证的用户名，密码不返回给前端
+    pub username: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -27,17 +50,181 @@ pub struct RssArticle {
     pub content: Option<String>,
     pub author: Option<String>,
     pub published_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
     pub guid: Option<String>,
     pub is_read: bool,
     pub is_starred: bool,
     pub read_time: Option<String>,
+    pub reader_prefs: Option<ReaderPrefs>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub word_count: Option<u32>,
+    pub duplicate_of: Option<String>,
+    pub image_url: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
-// 请求数据模型
+/// 文章级阅读模式偏好
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReaderPrefs {
+    pub reader_theme: Option<String>,
+    pub font_size: Option<u32>,
+}
+
+// 每个RSS源最新的一篇未读文章
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopUnreadArticle {
+    pub feed_id: String,
+    pub feed_title: String,
+    pub article: Option<RssArticle>,
+}
+
+// 附带所属RSS源标题的文章，用于跨源的文章列表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RssArticleWithFeed {
+    pub article: RssArticle,
+    pub feed_title: String,
+}
+
+// 订阅前的RSS源技术信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedInfo {
+    pub url: String,
+    pub content_type: Option<String>,
+    pub charset: Option<String>,
+    pub format: String,
+    pub title: Option<String>,
+    pub entry_count: u32,
+    // 带有完整正文的条目数，全部带正文时无需自动提取
+    pub entries_with_content: u32,
+    pub newest_entry_date: Option<DateTime<Utc>>,
+}
+
+// 全文提取使用的方式：静态抓取或外部渲染服务
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExtractionSource {
+    Static,
+    Rendered,
+}
+
+// 全文提取结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractedContent {
+    pub content: String,
+    pub source: ExtractionSource,
+}
+
+// 全文提取方式：readability、CSS选择器或拼接所有段落
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExtractionStrategy {
+    Readability,
+    Selectors,
+    Paragraphs,
+}
+
+impl ExtractionStrategy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExtractionStrategy::Readability => "readability",
+            ExtractionStrategy::Selectors => "selectors",
+            ExtractionStrategy::Paragraphs => "paragraphs",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "readability" => Some(ExtractionStrategy::Readability),
+            "selectors" => Some(ExtractionStrategy::Selectors),
+            "paragraphs" => Some(ExtractionStrategy::Paragraphs),
+            _ => None,
+        }
+    }
+}
+
+// 按域名覆盖全文提取方式的站点规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiteRule {
+    pub domain: String,
+    pub strategy: ExtractionStrategy,
+    // 选择器方式使用的CSS选择器
+    pub selector: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// 新增或修改站点规则的请求
 #[derive(Debug, Clone, Deserialize)]
+pub struct SiteRuleRequest {
+    pub domain: String,
+    pub strategy: ExtractionStrategy,
+    pub selector: Option<String>,
+}
+
+// 订阅前预览的RSS源内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedPreview {
+    pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub website_url: Option<String>,
+    // 前几篇文章的标题
+    pub article_titles: Vec<String>,
+}
+
+// 合并收件箱中的一个RSS源及其最新未读文章
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboxGroup {
+    pub feed: RssFeed,
+    pub articles: Vec<RssArticle>,
+}
+
+// RSS源发文频率
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedCadence {
+    pub feed_id: String,
+    pub total_articles: u32,
+    pub first_published: Option<DateTime<Utc>>,
+    pub last_published: Option<DateTime<Utc>>,
+    pub posts_per_week: f64,
+    pub average_gap_hours: Option<f64>,
+    pub median_gap_hours: Option<f64>,
+    // 按星期统计的发文数，周一在前
+    pub posts_by_weekday: [u32; 7],
+}
+
+// RSS源文章占用的存储空间
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedStorage {
+    pub feed_id: String,
+    pub feed_title: String,
+    pub article_count: u32,
+    // 正文、原始内容、压缩内容和摘要的近似字节数
+    pub bytes: u64,
+}
+
+// 连续阅读天数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadingStreak {
+    pub current_days: u32,
+    pub longest_days: u32,
+    pub last_read_date: Option<NaiveDate>,
+}
+
+// 按星期和小时统计的阅读次数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadingHeatmap {
+    // counts[星期][小时]，周一在前，按本地时间
+    pub counts: [[u32; 24]; 7],
+    pub total: u32,
+}
+
+// 请求数据模型
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct AddFeedRequest {
     pub url: String,
+    // 需要HTTP Basic认证的源的用户名和密码
+    pub username: Option<String>,
+    pub password: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -50,7 +237,40 @@ pub struct UpdateArticleRequest {
 // 应用状态
 #[derive(Debug)]
 pub struct AppState {
-    pub db: sqlx::SqlitePool,
+    // 从备份恢复时会替换为新的连接池，通过db()获取
+    db: RwLock<sqlx::SqlitePool>,
+    // 正在后台抓取文章的RSS源及其取消令牌
+    pub fetch_tokens: Arc<Mutex<HashMap<String, CancellationToken>>>,
+    // 后台调度器任务，替换数据库后需要用新的连接池重启
+    scheduler: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+}
+
+impl AppState {
+    pub fn new(db: sqlx::SqlitePool) -> Self {
+        AppState {
+            db: RwLock::new(db),
+            fetch_tokens: Default::default(),
+            scheduler: Mutex::new(None),
+        }
+    }
+
+    // 保存新启动的调度器任务，并停止之前的调度器
+    pub fn set_scheduler(&self, handle: tauri::async_runtime::JoinHandle<()>) {
+        let previous = self.scheduler.lock().unwrap_or_else(|e| e.into_inner()).replace(handle);
+        if let Some(previous) = previous {
+            previous.abort();
+        }
+    }
+
+    // 当前的数据库连接池
+    pub fn db(&self) -> sqlx::SqlitePool {
+        self.db.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    // 替换数据库连接池，之前取得的连接池不再更新
+    pub fn replace_db(&self, db: sqlx::SqlitePool) {
+        *self.db.write().unwrap_or_else(|e| e.into_inner()) = db;
+    }
 }
 
 // RSS抓取进度事件
@@ -72,6 +292,277 @@ pub enum RssFetchStatus {
     Failed(String),
 }
 
+// RSS源因连续刷新失败被自动停用的事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedAutoDisabled {
+    pub feed_id: String,
+    pub feed_title: String,
+    pub consecutive_failures: u32,
+    pub last_error: String,
+}
+
+// 批量刷新进度事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RssBatchRefreshProgress {
+    pub total_feeds: u32,
+    pub completed_feeds: u32,
+    pub failed_feeds: u32,
+    pub current_feed_title: Option<String>,
+}
+
+// 搜索过滤条件
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SearchFilters {
+    pub feed_id: Option<String>,
+    pub is_read: Option<bool>,
+    pub is_starred: Option<bool>,
+}
+
+// 文章列表排序方式
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArticleSort {
+    #[default]
+    Published,
+    Updated,
+}
+
+// 分页的文章列表及筛选后的文章总数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticlePage {
+    pub articles: Vec<RssArticle>,
+    pub total: i64,
+    pub offset: i32,
+    pub limit: i32,
+}
+
+// 按标签导出文章的文件格式
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+// 在搜索结果中移动的方向
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchDirection {
+    Next,
+    Previous,
+}
+
+// 全文索引重建进度事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchIndexProgress {
+    pub indexed: u32,
+    pub total: u32,
+}
+
+// 重新计算阅读指标的进度事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadingMetricsProgress {
+    pub processed: u32,
+    pub total: u32,
+}
+
+// HTTP请求设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpSettings {
+    pub timeout_secs: u32,
+    pub user_agent: String,
+}
+
+// 代理设置，用户名为空时不认证
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProxySettings {
+    pub http_proxy: Option<String>,
+    pub https_proxy: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+// 代理测试结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyTestResult {
+    pub success: bool,
+    pub status: Option<u16>,
+    pub error: Option<String>,
+}
+
+// 清理操作中的一篇目标文章
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupSample {
+    pub id: String,
+    pub title: String,
+}
+
+// 清理操作结果，dry_run时count为将要删除的数量
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupReport {
+    pub dry_run: bool,
+    pub count: u32,
+    pub sample: Vec<CleanupSample>,
+}
+
+// VACUUM前后的数据库文件大小（字节）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VacuumReport {
+    pub size_before: u64,
+    pub size_after: u64,
+    pub reclaimed_bytes: u64,
+}
+
+// 数据库完整性检查结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseIntegrityReport {
+    pub ok: bool,
+    pub integrity_errors: Vec<String>,
+    pub foreign_key_errors: Vec<String>,
+    pub page_count: u64,
+    pub page_size: u64,
+    pub freelist_count: u64,
+    pub suggestion: Option<String>,
+}
+
+// 文章内容压缩结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContentCompressionReport {
+    pub compressed_articles: u32,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub bytes_saved: u64,
+}
+
+// 一次刷新的变化：新增、更新以及可能已从源中移除的文章
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RefreshDiff {
+    pub feed_id: String,
+    pub not_modified: bool,
+    pub added: Vec<RssArticle>,
+    pub updated: Vec<RssArticle>,
+    pub possibly_removed: Vec<RssArticle>,
+}
+
+// 摘要中的一个RSS源及其新文章
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestGroup {
+    pub feed: RssFeed,
+    pub count: u32,
+    pub articles: Vec<RssArticle>,
+}
+
+// 一段时间内的新文章摘要
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Digest {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub total: u32,
+    pub groups: Vec<DigestGroup>,
+}
+
+// 疑似重复的文章及其原文章
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlaggedDuplicate {
+    pub article: RssArticle,
+    pub original: Option<RssArticle>,
+}
+
+// 链接检查进度事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkCheckProgress {
+    pub checked: u32,
+    pub total: u32,
+    pub dead: u32,
+}
+
+// 失效的文章链接
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLink {
+    pub article_id: String,
+    pub title: String,
+    pub link: String,
+    pub status: String,
+}
+
+// 链接检查结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LinkCheckSummary {
+    pub checked: u32,
+    pub ok: u32,
+    pub dead: Vec<DeadLink>,
+}
+
+// RSS源分类
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Category {
+    pub id: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// 文章标签及使用该标签的文章数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tag {
+    pub id: String,
+    pub name: String,
+    pub article_count: u32,
+    pub created_at: DateTime<Utc>,
+}
+
+// 批量订阅的一个条目
+#[derive(Debug, Clone, Deserialize)]
+pub struct BulkFeedEntry {
+    pub url: String,
+    pub category: Option<String>,
+}
+
+// 批量订阅中单个URL的处理结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkFeedStatus {
+    Added,
+    Duplicate,
+    Invalid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkFeedResult {
+    pub url: String,
+    pub status: BulkFeedStatus,
+    pub feed: Option<RssFeed>,
+    // 新订阅所在的分类名称
+    pub category: Option<String>,
+}
+
+// OPML导入结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpmlImportResult {
+    pub imported: Vec<RssFeed>,
+    // 已订阅而跳过的URL
+    pub skipped: Vec<String>,
+    // 格式无效的URL
+    pub invalid: Vec<String>,
+}
+
+// OPML导入预览中的一个RSS源
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpmlFeedPreview {
+    pub title: Option<String>,
+    pub url: String,
+    pub already_subscribed: bool,
+}
+
+// OPML导入预览
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpmlValidation {
+    pub total: u32,
+    pub already_subscribed: u32,
+    pub feeds: Vec<OpmlFeedPreview>,
+    pub malformed: Vec<String>,
+}
+
 // RSS文章抓取事件
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RssArticleFetched {
@@ -0,0 +1,221 @@
+use crate::error::{AppError, AppResult};
+use crate::models::{HttpSettings, ProxySettings, ProxyTestResult};
+use crate::settings;
+use log::{info, warn};
+use sqlx::SqlitePool;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::RwLock;
+
+/// 默认的请求超时时间（秒）
+pub const DEFAULT_HTTP_TIMEOUT_SECS: u32 = 30;
+
+/// 允许设置的请求超时范围（秒）
+pub const MIN_HTTP_TIMEOUT_SECS: u32 = 5;
+pub const MAX_HTTP_TIMEOUT_SECS: u32 = 300;
+
+/// 默认的User-Agent，部分网站会拒绝非浏览器的请求
+pub const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36";
+
+/// 是否只使用IPv4连接，启动时从设置中加载
+static IPV4_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// 请求超时时间（秒），启动时从设置中加载
+static HTTP_TIMEOUT_SECS: AtomicU32 = AtomicU32::new(DEFAULT_HTTP_TIMEOUT_SECS);
+
+/// 测试代理时请求的地址
+pub const PROXY_TEST_URL: &str = "https://www.example.com/";
+
+/// 自定义的User-Agent，为空时使用默认值
+static USER_AGENT: RwLock<Option<String>> = RwLock::new(None);
+
+/// 应用设置中的代理，未设置时为None
+static PROXY: RwLock<Option<ProxySettings>> = RwLock::new(None);
+
+/// 创建应用网络设置的HTTP客户端构建器，所有抓取（RSS源、全文提取、链接检查）都应使用
+///
+/// 代理的优先级：应用设置中配置了代理时只使用该代理，忽略HTTP_PROXY/HTTPS_PROXY/ALL_PROXY
+/// 和NO_PROXY环境变量；没有配置代理时由reqwest按环境变量自动选择系统代理
+pub fn client_builder() -> reqwest::ClientBuilder {
+    let proxies = match PROXY.read().ok().and_then(|proxy| proxy.clone()) {
+        Some(proxy) => build_proxies(&proxy).unwrap_or_else(|e| {
+            warn!("代理设置无效，已忽略: {}", e);
+            Vec::new()
+        }),
+        None => Vec::new(),
+    };
+    builder_with_proxies(proxies)
+}
+
+fn builder_with_proxies(proxies: Vec<reqwest::Proxy>) -> reqwest::ClientBuilder {
+    let settings = http_settings();
+    let mut builder = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(settings.timeout_secs as u64))
+        .user_agent(settings.user_agent);
+    for proxy in proxies {
+        builder = builder.proxy(proxy);
+    }
+    if is_ipv4_only() {
+        // 绑定到IPv4地址后只会连接IPv4地址，避免IPv6不通的网络中请求挂起
+        builder.local_address(IpAddr::V4(Ipv4Addr::UNSPECIFIED))
+    } else {
+        builder
+    }
+}
+
+/// 当前是否只使用IPv4
+pub fn is_ipv4_only() -> bool {
+    IPV4_ONLY.load(Ordering::Relaxed)
+}
+
+/// 当前的请求超时和User-Agent
+pub fn http_settings() -> HttpSettings {
+    let user_agent = USER_AGENT
+        .read()
+        .ok()
+        .and_then(|user_agent| user_agent.clone())
+        .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string());
+    HttpSettings {
+        timeout_secs: HTTP_TIMEOUT_SECS.load(Ordering::Relaxed),
+        user_agent,
+    }
+}
+
+/// 从设置中加载网络选项，在应用启动时调用
+pub async fn load_settings(db: &SqlitePool) -> AppResult<()> {
+    let ipv4_only = settings::get_bool(db, settings::IPV4_ONLY, false).await?;
+    IPV4_ONLY.store(ipv4_only, Ordering::Relaxed);
+    let timeout_secs = settings::get_u32(db, settings::HTTP_TIMEOUT_SECS, DEFAULT_HTTP_TIMEOUT_SECS).await?;
+    HTTP_TIMEOUT_SECS.store(timeout_secs, Ordering::Relaxed);
+    let user_agent = settings::get_setting(db, settings::HTTP_USER_AGENT)
+        .await?
+        .filter(|user_agent| !user_agent.is_empty());
+    store_user_agent(user_agent);
+    store_proxy(load_proxy(db).await?);
+    Ok(())
+}
+
+/// 设置请求超时和User-Agent，User-Agent为空时恢复默认值，对之后创建的请求立即生效
+pub async fn set_http_settings(db: &SqlitePool, timeout_secs: u32, user_agent: Option<String>) -> AppResult<()> {
+    if !(MIN_HTTP_TIMEOUT_SECS..=MAX_HTTP_TIMEOUT_SECS).contains(&timeout_secs) {
+        return Err(AppError::validation(format!(
+            "请求超时时间需在 {} 到 {} 秒之间",
+            MIN_HTTP_TIMEOUT_SECS, MAX_HTTP_TIMEOUT_SECS
+        )));
+    }
+    let user_agent = user_agent
+        .map(|user_agent| user_agent.trim().to_string())
+        .filter(|user_agent| !user_agent.is_empty());
+    if let Some(user_agent) = &user_agent {
+        if reqwest::header::HeaderValue::from_str(user_agent).is_err() {
+            return Err(AppError::validation("User-Agent包含无效字符"));
+        }
+    }
+
+    settings::set_u32(db, settings::HTTP_TIMEOUT_SECS, timeout_secs).await?;
+    settings::set_setting(db, settings::HTTP_USER_AGENT, user_agent.as_deref().unwrap_or("")).await?;
+    HTTP_TIMEOUT_SECS.store(timeout_secs, Ordering::Relaxed);
+    store_user_agent(user_agent);
+    info!("请求超时已设置为 {} 秒", timeout_secs);
+    Ok(())
+}
+
+fn store_user_agent(user_agent: Option<String>) {
+    if let Ok(mut current) = USER_AGENT.write() {
+        *current = user_agent;
+    }
+}
+
+/// 开启或关闭仅IPv4模式，对之后创建的请求立即生效
+pub async fn set_ipv4_only(db: &SqlitePool, enabled: bool) -> AppResult<()> {
+    settings::set_bool(db, settings::IPV4_ONLY, enabled).await?;
+    IPV4_ONLY.store(enabled, Ordering::Relaxed);
+    info!("仅IPv4模式已{}", if enabled { "开启" } else { "关闭" });
+    Ok(())
+}
+
+/// 将代理设置转换为reqwest代理，用户名不为空时附带基本认证
+fn build_proxies(proxy: &ProxySettings) -> AppResult<Vec<reqwest::Proxy>> {
+    let auth = |p: reqwest::Proxy| match proxy.username.as_deref() {
+        Some(username) => p.basic_auth(username, proxy.password.as_deref().unwrap_or("")),
+        None => p,
+    };
+    let mut proxies = Vec::new();
+    if let Some(url) = &proxy.http_proxy {
+        proxies.push(auth(reqwest::Proxy::http(url)?));
+    }
+    if let Some(url) = &proxy.https_proxy {
+        proxies.push(auth(reqwest::Proxy::https(url)?));
+    }
+    Ok(proxies)
+}
+
+/// 去除空白项，没有配置任何代理地址时返回None
+fn normalize_proxy(proxy: ProxySettings) -> Option<ProxySettings> {
+    let clean = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+    let proxy = ProxySettings {
+        http_proxy: clean(proxy.http_proxy),
+        https_proxy: clean(proxy.https_proxy),
+        username: clean(proxy.username),
+        password: proxy.password.filter(|password| !password.is_empty()),
+    };
+    (proxy.http_proxy.is_some() || proxy.https_proxy.is_some()).then_some(proxy)
+}
+
+async fn load_proxy(db: &SqlitePool) -> AppResult<Option<ProxySettings>> {
+    Ok(normalize_proxy(ProxySettings {
+        http_proxy: settings::get_setting(db, settings::HTTP_PROXY).await?,
+        https_proxy: settings::get_setting(db, settings::HTTPS_PROXY).await?,
+        username: settings::get_setting(db, settings::PROXY_USERNAME).await?,
+        password: settings::get_setting(db, settings::PROXY_PASSWORD).await?,
+    }))
+}
+
+fn store_proxy(proxy: Option<ProxySettings>) {
+    if let Ok(mut current) = PROXY.write() {
+        *current = proxy;
+    }
+}
+
+/// 设置代理，http_proxy和https_proxy都为空时清除代理，改为使用环境变量中的代理
+/// 密码以明文保存在设置表中
+pub async fn set_proxy(db: &SqlitePool, proxy: ProxySettings) -> AppResult<()> {
+    let proxy = normalize_proxy(proxy);
+    if let Some(proxy) = &proxy {
+        build_proxies(proxy).map_err(|e| AppError::validation(format!("代理地址无效: {}", e)))?;
+    }
+
+    // 清除代理时写入空值
+    let stored = proxy.clone().unwrap_or_default();
+    settings::set_setting(db, settings::HTTP_PROXY, stored.http_proxy.as_deref().unwrap_or("")).await?;
+    settings::set_setting(db, settings::HTTPS_PROXY, stored.https_proxy.as_deref().unwrap_or("")).await?;
+    settings::set_setting(db, settings::PROXY_USERNAME, stored.username.as_deref().unwrap_or("")).await?;
+    settings::set_setting(db, settings::PROXY_PASSWORD, stored.password.as_deref().unwrap_or("")).await?;
+
+    info!("代理已{}", if proxy.is_some() { "设置" } else { "清除" });
+    store_proxy(proxy);
+    Ok(())
+}
+
+/// 使用给定的代理设置请求测试地址（默认为PROXY_TEST_URL），不保存设置
+/// 代理为空时测试的是环境变量中的代理或直连
+pub async fn test_proxy(proxy: ProxySettings, url: Option<&str>) -> AppResult<ProxyTestResult> {
+    let url = url.unwrap_or(PROXY_TEST_URL);
+    let proxies = match normalize_proxy(proxy) {
+        Some(proxy) => build_proxies(&proxy).map_err(|e| AppError::validation(format!("代理地址无效: {}", e)))?,
+        None => Vec::new(),
+    };
+
+    Ok(match builder_with_proxies(proxies).build()?.get(url).send().await {
+        Ok(response) => ProxyTestResult {
+            success: response.status().is_success(),
+            status: Some(response.status().as_u16()),
+            error: None,
+        },
+        Err(e) => ProxyTestResult {
+            success: false,
+            status: None,
+            error: Some(e.to_string()),
+        },
+    })
+}
@@ -0,0 +1,110 @@
+use crate::error::{AppError, AppResult};
+use crate::export::escape_xml;
+use crate::models::RssFeed;
+use chrono::{DateTime, Utc};
+use std::fmt::Write;
+use url::Url;
+
+/// 标记停用RSS源的自定义属性，导入时据此恢复停用状态
+const INACTIVE_ATTRIBUTE: &str = "isActive";
+
+/// OPML中的一个订阅条目
+#[derive(Debug, Clone)]
+pub struct OpmlOutline {
+    pub title: Option<String>,
+    pub xml_url: String,
+    pub html_url: Option<String>,
+    pub is_active: bool,
+}
+
+/// OPML解析结果
+#[derive(Debug, Clone, Default)]
+pub struct ParsedOpml {
+    /// 有效的订阅条目（包括分组内的条目）
+    pub outlines: Vec<OpmlOutline>,
+    /// 无法识别的条目描述：缺少xmlUrl的叶子节点或无效的URL
+    pub malformed: Vec<String>,
+}
+
+/// 解析OPML内容
+pub fn parse_opml(content: &str) -> AppResult<ParsedOpml> {
+    let document = roxmltree::Document::parse(content)
+        .map_err(|e| AppError::validation(format!("OPML解析失败: {}", e)))?;
+
+    let root = document.root_element();
+    if !root.has_tag_name("opml") {
+        return Err(AppError::validation("不是有效的OPML文件: 缺少<opml>根节点"));
+    }
+
+    let mut parsed = ParsedOpml::default();
+
+    for node in root.descendants().filter(|node| node.has_tag_name("outline")) {
+        let title = node
+            .attribute("title")
+            .or_else(|| node.attribute("text"))
+            .map(|s| s.to_string());
+        let xml_url = node.attribute("xmlUrl").map(str::trim).unwrap_or_default();
+
+        if xml_url.is_empty() {
+            // 有子节点的是分组，没有子节点又缺少xmlUrl的才是无效条目
+            let is_group = node.children().any(|child| child.has_tag_name("outline"));
+            if !is_group {
+                parsed
+                    .malformed
+                    .push(title.unwrap_or_else(|| "<未命名条目>".to_string()));
+            }
+            continue;
+        }
+
+        let valid_url = Url::parse(xml_url)
+            .map(|url| url.scheme() == "http" || url.scheme() == "https")
+            .unwrap_or(false);
+        if !valid_url {
+            parsed.malformed.push(xml_url.to_string());
+            continue;
+        }
+
+        parsed.outlines.push(OpmlOutline {
+            title,
+            xml_url: xml_url.to_string(),
+            html_url: node.attribute("htmlUrl").map(|s| s.to_string()),
+            is_active: node.attribute(INACTIVE_ATTRIBUTE) != Some("false"),
+        });
+    }
+
+    Ok(parsed)
+}
+
+/// 将RSS源生成OPML 2.0文档，停用的源带有isActive="false"属性
+pub fn build_opml(feeds: &[RssFeed], now: DateTime<Utc>) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<opml version=\"2.0\">\n");
+    xml.push_str("  <head>\n");
+    xml.push_str("    <title>you-know subscriptions</title>\n");
+    let _ = writeln!(xml, "    <dateCreated>{}</dateCreated>", now.to_rfc2822());
+    xml.push_str("  </head>\n");
+    xml.push_str("  <body>\n");
+
+    for feed in feeds {
+        let title = escape_xml(&feed.title);
+        let _ = write!(
+            xml,
+            "    <outline type=\"rss\" text=\"{}\" title=\"{}\" xmlUrl=\"{}\"",
+            title,
+            title,
+            escape_xml(&feed.url)
+        );
+        if let Some(website_url) = &feed.website_url {
+            let _ = write!(xml, " htmlUrl=\"{}\"", escape_xml(website_url));
+        }
+        if !feed.is_active {
+            let _ = write!(xml, " {}=\"false\"", INACTIVE_ATTRIBUTE);
+        }
+        xml.push_str("/>\n");
+    }
+
+    xml.push_str("  </body>\n");
+    xml.push_str("</opml>\n");
+    xml
+}
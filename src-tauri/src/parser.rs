@@ -0,0 +1,339 @@
+use crate::error::AppResult;
+use crate::utils;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use feed_rs::model::{FeedType, MediaObject};
+use feed_rs::parser;
+use serde::Deserialize;
+
+/// 解析后的RSS源（与具体格式无关）
+#[derive(Debug, Clone, Default)]
+pub struct ParsedFeed {
+    /// 源格式，如 RSS 2.0、Atom、JSON Feed
+    pub format: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub website_url: Option<String>,
+    /// 源声明的更新时间，没有时使用发布时间
+    pub updated: Option<DateTime<Utc>>,
+    pub entries: Vec<ParsedEntry>,
+}
+
+/// 解析后的文章条目（与具体格式无关）
+#[derive(Debug, Clone, Default)]
+pub struct ParsedEntry {
+    pub id: String,
+    pub title: Option<String>,
+    pub link: Option<String>,
+    pub summary: Option<String>,
+    pub content: Option<String>,
+    pub author: Option<String>,
+    pub published: Option<DateTime<Utc>>,
+    pub updated: Option<DateTime<Utc>>,
+    /// 发布时间和更新时间在源中是否没有时区信息，没有时已按UTC解析
+    pub published_naive: bool,
+    pub updated_naive: bool,
+    /// 源中附带的题图或缩略图地址
+    pub image_url: Option<String>,
+}
+
+impl ParsedEntry {
+    /// 发布时间，源中没有时区信息时按RSS源设置的时区解释
+    pub fn published_in(&self, timezone: Option<Tz>) -> Option<DateTime<Utc>> {
+        localize(self.published, self.published_naive, timezone)
+    }
+
+    /// 更新时间，源中没有时区信息时按RSS源设置的时区解释
+    pub fn updated_in(&self, timezone: Option<Tz>) -> Option<DateTime<Utc>> {
+        localize(self.updated, self.updated_naive, timezone)
+    }
+}
+
+/// 只对不带时区信息的时间应用时区，带偏移的时间保持不变
+fn localize(dt: Option<DateTime<Utc>>, naive: bool, timezone: Option<Tz>) -> Option<DateTime<Utc>> {
+    match (dt, timezone) {
+        (Some(dt), Some(tz)) if naive => Some(utils::localize_naive(dt, tz)),
+        _ => dt,
+    }
+}
+
+/// RSS源解析器
+pub trait FeedParser: Send + Sync {
+    /// 解析器名称，用于日志
+    fn name(&self) -> &'static str;
+
+    /// 根据Content-Type和内容判断是否能解析
+    fn can_parse(&self, content_type: Option<&str>, body: &[u8]) -> bool;
+
+    /// 解析内容
+    fn parse(&self, body: &[u8]) -> AppResult<ParsedFeed>;
+}
+
+/// 基于feed_rs的默认解析器，支持RSS/Atom
+pub struct FeedRsParser;
+
+impl FeedParser for FeedRsParser {
+    fn name(&self) -> &'static str {
+        "feed-rs"
+    }
+
+    fn can_parse(&self, _content_type: Option<&str>, _body: &[u8]) -> bool {
+        true
+    }
+
+    fn parse(&self, body: &[u8]) -> AppResult<ParsedFeed> {
+        let feed = parser::parse(body)?;
+        // 默认解析器丢弃不带时区的时间（只有日期的按UTC零点处理），
+        // 再只按不带时区的格式解析一遍，找出这些时间
+        let naive_dates: Vec<_> = parser::Builder::new()
+            .timestamp_parser(utils::parse_naive_datetime)
+            .build()
+            .parse(body)
+            .map(|naive| naive.entries.into_iter().map(|entry| (entry.published, entry.updated)).collect())
+            .unwrap_or_default();
+
+        let entries = feed
+            .entries
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let (naive_published, naive_updated) = naive_dates.get(index).copied().unwrap_or_default();
+                ParsedEntry {
+                    id: entry.id,
+                    title: entry.title.map(|t| t.content),
+                    link: entry.links.first().map(|l| l.href.clone()),
+                    summary: entry.summary.map(|s| s.content),
+                    content: entry.content.and_then(|c| c.body),
+                    author: entry.authors.first().map(|a| a.name.clone()),
+                    published: naive_published.or(entry.published),
+                    updated: naive_updated.or(entry.updated),
+                    published_naive: naive_published.is_some(),
+                    updated_naive: naive_updated.is_some(),
+                    image_url: entry_image_url(&entry.media),
+                }
+            })
+            .collect();
+
+        let format = match feed.feed_type {
+            FeedType::Atom => "Atom",
+            FeedType::JSON => "JSON Feed",
+            FeedType::RSS0 => "RSS 0.9x",
+            FeedType::RSS1 => "RSS 1.0",
+            FeedType::RSS2 => "RSS 2.0",
+        };
+
+        Ok(ParsedFeed {
+            format: format.to_string(),
+            title: feed.title.map(|t| t.content),
+            description: feed.description.map(|d| d.content),
+            website_url: feed.links.first().map(|l| l.href.clone()),
+            updated: feed.updated.or(feed.published),
+            entries,
+        })
+    }
+}
+
+/// 从条目的媒体信息中取题图：优先media:thumbnail，其次图片类型的media:content或enclosure
+fn entry_image_url(media: &[MediaObject]) -> Option<String> {
+    media
+        .iter()
+        .flat_map(|object| &object.thumbnails)
+        .map(|thumbnail| thumbnail.image.uri.clone())
+        .next()
+        .or_else(|| {
+            media
+                .iter()
+                .flat_map(|object| &object.content)
+                .find(|content| {
+                    content
+                        .content_type
+                        .as_ref()
+                        .is_some_and(|mime| mime.type_().as_str() == "image")
+                })
+                .and_then(|content| content.url.as_ref())
+                .map(|url| url.to_string())
+        })
+}
+
+/// JSON Feed (https://jsonfeed.org) 解析器
+pub struct JsonFeedParser;
+
+#[derive(Debug, Deserialize)]
+struct JsonFeed {
+    title: Option<String>,
+    home_page_url: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    items: Vec<JsonFeedItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonFeedItem {
+    id: serde_json::Value,
+    url: Option<String>,
+    title: Option<String>,
+    summary: Option<String>,
+    content_html: Option<String>,
+    content_text: Option<String>,
+    date_published: Option<String>,
+    date_modified: Option<String>,
+    /// JSON Feed 1.0的单个作者
+    author: Option<JsonFeedAuthor>,
+    /// JSON Feed 1.1改为作者列表
+    #[serde(default)]
+    authors: Vec<JsonFeedAuthor>,
+    image: Option<String>,
+    banner_image: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonFeedAuthor {
+    name: Option<String>,
+}
+
+impl FeedParser for JsonFeedParser {
+    fn name(&self) -> &'static str {
+        "json-feed"
+    }
+
+    fn can_parse(&self, content_type: Option<&str>, body: &[u8]) -> bool {
+        if let Some(content_type) = content_type {
+            if content_type.contains("json") {
+                return true;
+            }
+        }
+        // 嗅探内容：JSON对象以 '{' 开头
+        body.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{')
+    }
+
+    fn parse(&self, body: &[u8]) -> AppResult<ParsedFeed> {
+        let feed: JsonFeed = serde_json::from_slice(body)?;
+
+        let entries = feed
+            .items
+            .into_iter()
+            .map(|item| {
+                // id 可能是字符串或数字
+                let id = match item.id {
+                    serde_json::Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                let (published, published_naive) = parse_json_date(item.date_published.as_deref());
+                let (updated, updated_naive) = parse_json_date(item.date_modified.as_deref());
+                ParsedEntry {
+                    id,
+                    title: item.title,
+                    link: item.url,
+                    summary: item.summary,
+                    content: item
+                        .content_html
+                        .or_else(|| item.content_text.as_deref().map(text_to_html)),
+                    author: item
+                        .authors
+                        .into_iter()
+                        .chain(item.author)
+                        .find_map(|a| a.name),
+                    published,
+                    updated,
+                    published_naive,
+                    updated_naive,
+                    image_url: item.image.or(item.banner_image),
+                }
+            })
+            .collect();
+
+        Ok(ParsedFeed {
+            format: "JSON Feed".to_string(),
+            title: feed.title,
+            description: feed.description,
+            website_url: feed.home_page_url,
+            // JSON Feed没有源级别的时间
+            updated: None,
+            entries,
+        })
+    }
+}
+
+/// 将纯文本正文转为HTML：转义特殊字符，空行分隔的段落包在<p>中
+fn text_to_html(text: &str) -> String {
+    text.split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| {
+            let escaped = paragraph
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('\n', "<br>");
+            format!("<p>{}</p>", escaped)
+        })
+        .collect()
+}
+
+/// 解析JSON Feed的日期，返回时间以及是否不带时区信息，不带时区信息的按UTC处理
+fn parse_json_date(s: Option<&str>) -> (Option<DateTime<Utc>>, bool) {
+    let Some(s) = s else {
+        return (None, false);
+    };
+    match DateTime::parse_from_rfc3339(s) {
+        Ok(dt) => (Some(dt.with_timezone(&Utc)), false),
+        Err(_) => {
+            let naive = utils::parse_naive_datetime(s);
+            (naive, naive.is_some())
+        }
+    }
+}
+
+/// 判断响应是否明显是HTML网页而不是RSS源
+/// 只看Content-Type不够可靠，部分源以text/html返回XML，因此还要检查内容开头
+pub fn looks_like_html(content_type: Option<&str>, body: &[u8]) -> bool {
+    let head = String::from_utf8_lossy(&body[..body.len().min(512)]);
+    let head = head.trim_start_matches('\u{feff}').trim_start().to_ascii_lowercase();
+    if head.starts_with("<!doctype html") || head.starts_with("<html") {
+        return true;
+    }
+
+    let is_feed_like = ["<?xml", "<rss", "<feed", "<rdf", "{"]
+        .iter()
+        .any(|prefix| head.starts_with(prefix));
+    content_type.is_some_and(|ct| ct.to_ascii_lowercase().contains("text/html")) && !is_feed_like
+}
+
+/// 获取声明的字符集：优先Content-Type中的charset，其次XML声明中的encoding
+pub fn declared_charset(content_type: Option<&str>, body: &[u8]) -> Option<String> {
+    let from_header = content_type.and_then(|ct| {
+        ct.split(';')
+            .filter_map(|param| param.trim().split_once('='))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("charset"))
+            .map(|(_, value)| value.trim().trim_matches('"').to_string())
+    });
+    if from_header.is_some() {
+        return from_header;
+    }
+
+    // XML声明只会出现在开头
+    let head = String::from_utf8_lossy(&body[..body.len().min(200)]);
+    let declaration = head.trim_start().strip_prefix("<?xml")?;
+    let declaration = &declaration[..declaration.find("?>")?];
+    let value = declaration.split_once("encoding")?.1.trim_start().strip_prefix('=')?.trim_start();
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = &value[1..];
+    Some(value[..value.find(quote)?].to_string())
+}
+
+/// 根据Content-Type和内容选择解析器
+pub fn select_parser(content_type: Option<&str>, body: &[u8]) -> Box<dyn FeedParser> {
+    let parsers: Vec<Box<dyn FeedParser>> = vec![Box::new(JsonFeedParser), Box::new(FeedRsParser)];
+
+    parsers
+        .into_iter()
+        .find(|p| p.can_parse(content_type, body))
+        .unwrap_or_else(|| Box::new(FeedRsParser))
+}
+
+/// 选择合适的解析器并解析内容
+pub fn parse_feed(content_type: Option<&str>, body: &[u8]) -> AppResult<ParsedFeed> {
+    let parser = select_parser(content_type, body);
+    log::info!("使用解析器: {}", parser.name());
+    parser.parse(body)
+}
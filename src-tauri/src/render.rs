@@ -0,0 +1,204 @@
+use crate::error::{AppError, AppResult};
+use crate::network;
+use crate::settings;
+use log::{info, warn};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::time::Duration;
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{AppHandle, Runtime, UriSchemeContext, WebviewUrl, WebviewWindowBuilder};
+use tokio::sync::oneshot;
+use url::Url;
+use uuid::Uuid;
+
+/// 渲染完成后页面将DOM通过POST请求发送到该自定义协议，请求体不受URL长度限制
+/// 只开放这一个协议而不开放IPC，避免任意网站调用应用命令
+pub const RESULT_SCHEME: &str = "render-result";
+
+/// 页面中访问自定义协议的地址，Windows和Android上自定义协议映射为http://<协议>.localhost
+#[cfg(any(windows, target_os = "android"))]
+const RESULT_ENDPOINT: &str = "http://render-result.localhost/";
+#[cfg(not(any(windows, target_os = "android")))]
+const RESULT_ENDPOINT: &str = "render-result://localhost/";
+
+/// 页面load事件后等待脚本渲染的时间（毫秒）
+const SETTLE_MS: u64 = 1500;
+
+/// 等待渲染结果的最长时间
+const RENDER_TIMEOUT_SECS: u64 = 45;
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// 等待渲染结果的窗口，按窗口标签保存结果的发送端
+static PENDING: OnceLock<Mutex<HashMap<String, oneshot::Sender<String>>>> = OnceLock::new();
+
+/// 外部渲染服务地址，启动时从设置中加载，未设置时不使用
+static SERVICE_URL: RwLock<Option<String>> = RwLock::new(None);
+
+/// 渲染服务地址中表示文章地址的占位符
+const SERVICE_URL_PLACEHOLDER: &str = "{url}";
+
+/// 保存AppHandle以便后台任务创建渲染窗口，在应用启动时调用
+pub fn init(app: AppHandle) {
+    let _ = APP_HANDLE.set(app);
+}
+
+/// 是否可以使用webview渲染（测试和命令行环境中不可用）
+pub fn is_available() -> bool {
+    APP_HANDLE.get().is_some()
+}
+
+fn render_script() -> String {
+    format!(
+        r#"window.addEventListener('load', function () {{
+    setTimeout(function () {{
+        fetch('{}', {{ method: 'POST', body: document.documentElement.outerHTML }});
+    }}, {});
+}});"#,
+        RESULT_ENDPOINT, SETTLE_MS
+    )
+}
+
+fn pending() -> &'static Mutex<HashMap<String, oneshot::Sender<String>>> {
+    PENDING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 处理渲染窗口发送到RESULT_SCHEME的DOM，在应用启动时注册为自定义协议
+/// 只接受正在等待结果的渲染窗口的请求，其他窗口的请求返回403
+pub fn handle_result<R: Runtime>(ctx: UriSchemeContext<'_, R>, request: Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let sender = pending()
+        .lock()
+        .ok()
+        .and_then(|mut pending| pending.remove(ctx.webview_label()));
+    let status = match sender {
+        Some(sender) => {
+            let html = String::from_utf8_lossy(request.body()).into_owned();
+            let _ = sender.send(html);
+            StatusCode::NO_CONTENT
+        }
+        None => {
+            warn!("忽略来自窗口 {} 的渲染结果", ctx.webview_label());
+            StatusCode::FORBIDDEN
+        }
+    };
+
+    // 请求来自外部网页，需要允许跨域
+    Response::builder()
+        .status(status)
+        .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+        .body(Vec::new())
+        .unwrap_or_default()
+}
+
+/// 在隐藏的webview窗口中加载网页，返回脚本执行后的DOM
+pub async fn render_page(url: &str) -> AppResult<String> {
+    let app = APP_HANDLE
+        .get()
+        .ok_or_else(|| AppError::internal("webview渲染不可用"))?;
+    let page_url = Url::parse(url)?;
+    let label = format!("render-{}", Uuid::new_v4());
+
+    let (sender, receiver) = oneshot::channel::<String>();
+    if let Ok(mut pending) = pending().lock() {
+        pending.insert(label.clone(), sender);
+    }
+    let window = WebviewWindowBuilder::new(app, &label, WebviewUrl::External(page_url))
+        .visible(false)
+        .initialization_script(render_script())
+        .build();
+    let window = match window {
+        Ok(window) => window,
+        Err(e) => {
+            if let Ok(mut pending) = pending().lock() {
+                pending.remove(&label);
+            }
+            return Err(e.into());
+        }
+    };
+
+    let result = tokio::time::timeout(Duration::from_secs(RENDER_TIMEOUT_SECS), receiver).await;
+    if let Ok(mut pending) = pending().lock() {
+        pending.remove(&label);
+    }
+    let _ = window.close();
+
+    match result {
+        Ok(Ok(html)) => {
+            info!("webview渲染完成: {}，长度: {}", url, html.len());
+            Ok(html)
+        }
+        Ok(Err(_)) => Err(AppError::internal("渲染窗口在返回结果前已关闭")),
+        Err(_) => Err(AppError::internal(format!("渲染页面超时: {}", url))),
+    }
+}
+
+/// 从设置中加载渲染服务地址，在应用启动时调用
+pub async fn load_service_url(db: &SqlitePool) -> AppResult<()> {
+    let url = settings::get_setting(db, settings::RENDER_SERVICE_URL)
+        .await?
+        .filter(|url| !url.is_empty());
+    store_service_url(url);
+    Ok(())
+}
+
+/// 当前配置的渲染服务地址
+pub fn service_url() -> Option<String> {
+    SERVICE_URL.read().ok().and_then(|url| url.clone())
+}
+
+/// 设置渲染服务地址，为空时关闭渲染服务回退
+///
+/// 地址中包含{url}时替换为编码后的文章地址（如`https://render.local/render?url={url}`），
+/// 否则直接在末尾拼接文章地址（如`http://localhost:3000/`）
+pub async fn set_service_url(db: &SqlitePool, url: Option<String>) -> AppResult<()> {
+    let url = url.map(|url| url.trim().to_string()).filter(|url| !url.is_empty());
+    if let Some(url) = &url {
+        let sample = service_request_url(url, "https://example.com/article");
+        let parsed = Url::parse(&sample).map_err(|_| AppError::validation(format!("无效的渲染服务地址: {}", url)))?;
+        if parsed.scheme() != "http" && parsed.scheme() != "https" {
+            return Err(AppError::validation(format!("渲染服务地址必须是http或https: {}", url)));
+        }
+    }
+
+    settings::set_setting(db, settings::RENDER_SERVICE_URL, url.as_deref().unwrap_or("")).await?;
+    info!("渲染服务已{}", if url.is_some() { "设置" } else { "关闭" });
+    store_service_url(url);
+    Ok(())
+}
+
+fn store_service_url(url: Option<String>) {
+    if let Ok(mut current) = SERVICE_URL.write() {
+        *current = url;
+    }
+}
+
+/// 拼接请求渲染服务的地址
+pub(crate) fn service_request_url(service_url: &str, page_url: &str) -> String {
+    if service_url.contains(SERVICE_URL_PLACEHOLDER) {
+        let encoded: String = url::form_urlencoded::byte_serialize(page_url.as_bytes()).collect();
+        service_url.replace(SERVICE_URL_PLACEHOLDER, &encoded)
+    } else {
+        format!("{}{}", service_url, page_url)
+    }
+}
+
+/// 通过外部渲染服务获取脚本执行后的网页HTML，未配置渲染服务时返回错误
+pub async fn render_via_service(url: &str) -> AppResult<String> {
+    let service = service_url().ok_or_else(|| AppError::config("未配置渲染服务"))?;
+    let request_url = service_request_url(&service, url);
+
+    // 渲染需要等待页面脚本执行，使用与webview渲染相同的超时时间
+    let response = network::client_builder()
+        .timeout(Duration::from_secs(RENDER_TIMEOUT_SECS))
+        .build()?
+        .get(&request_url)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(AppError::http_status(&request_url, response.status().as_u16()));
+    }
+    let html = response.text().await?;
+    info!("渲染服务返回页面: {}，长度: {}", url, html.len());
+    Ok(html)
+}
@@ -1,12 +1,30 @@
+use crate::cleanup;
+use crate::compression;
 use crate::error::{AppError, AppResult};
-use crate::models::{AddFeedRequest, RssArticle, RssFeed, UpdateArticleRequest, RssFetchProgress, RssFetchStatus, RssArticleFetched};
-use chrono::{DateTime, Utc, Local};
-use feed_rs::parser;
-use log::info;
+use crate::models::{AddFeedRequest, ArticlePage, ArticleSort, BulkFeedEntry, BulkFeedResult, BulkFeedStatus, Category, CleanupReport, ExtractedContent, ExtractionSource, ExtractionStrategy, FeedInfo, FeedStorage, FlaggedDuplicate, InboxGroup, ReaderPrefs, ReadingMetricsProgress, RefreshDiff, RssArticle, RssArticleWithFeed, RssFeed, UpdateArticleRequest, RssFetchProgress, RssFetchStatus, RssArticleFetched, Tag, TopUnreadArticle, FeedAutoDisabled, FeedCadence, FeedPreview, OpmlFeedPreview, OpmlImportResult, OpmlValidation, ReadingHeatmap, ReadingStreak, RssBatchRefreshProgress};
+use crate::network;
+use crate::opml;
+use crate::parser::{self, ParsedEntry, ParsedFeed};
+use crate::render;
+use crate::sanitize;
+use crate::scheduler;
+use crate::search::SearchService;
+use crate::settings;
+use crate::site_rules;
+use crate::storage::Storage;
+use crate::utils;
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, Timelike, Utc};
+use log::{debug, error, info, warn};
 use readability::extractor;
 use reqwest;
 use scraper::{Html, Selector};
-use sqlx::{Row, SqlitePool};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, Sqlite, SqliteConnection, SqlitePool, Transaction};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
 use tauri::{AppHandle, Emitter};
 use url::Url;
 use uuid::Uuid;
@@ -14,21 +32,444 @@ use uuid::Uuid;
 /// RSS服务结构体
 pub struct RssService;
 
+/// 查询RSS源时使用的字段列表
+pub(crate) const FEED_COLUMNS: &str = "id, title, url, description, website_url, last_updated, is_active, cron, alternate_urls, last_fetched_url, timezone, title_strip_prefix, title_strip_is_regex, requires_render, max_content_bytes, feed_format, category_id, unread_count, refresh_interval_minutes, favicon_url, last_error, last_error_at, consecutive_failures, username, created_at, updated_at";
+
+/// 网络请求遇到暂时性错误时的最大重试次数
+const FETCH_MAX_RETRIES: u32 = 3;
+
+/// 重试的基础等待时间（毫秒），每次重试翻倍
+const FETCH_RETRY_BASE_DELAY_MS: u64 = 500;
+
+/// 全文提取失败后，打开文章时不再自动重试的冷却时间（小时）
+const EXTRACTION_RETRY_COOLDOWN_HOURS: i64 = 24;
+
+/// 没有抓到网站图标时，再次尝试前的等待时间（小时）
+const FAVICON_RETRY_COOLDOWN_HOURS: i64 = 24;
+
+/// 缓存的网站图标大小上限（字节）
+const MAX_FAVICON_BYTES: usize = 256 * 1024;
+
+/// 批量更新文章时每条语句包含的文章数，避免超过SQLite的参数个数上限
+const BULK_UPDATE_CHUNK_SIZE: usize = 500;
+
+/// 抓取RSS源时最多跟随的重定向次数
+const MAX_REDIRECTS: usize = 10;
+
+/// 静态提取的正文少于该字符数时视为提取失败，配置了渲染服务时改用渲染后的页面
+const MIN_STATIC_EXTRACT_CHARS: usize = 200;
+
+/// 没有站点规则时尝试的常见文章内容选择器
+const CONTENT_SELECTORS: [&str; 11] = [
+    "article",
+    ".post-content",
+    ".entry-content",
+    ".content",
+    "main",
+    ".article-body",
+    "#content",
+    ".post-body",
+    ".article-content",
+    ".post",
+    "[role='main']",
+];
+
+/// 默认在连续刷新失败多少次后自动停用RSS源
+pub const DEFAULT_AUTO_DISABLE_FAILURES: u32 = 10;
+
+/// 默认视为异常的连续刷新失败次数
+const DEFAULT_UNHEALTHY_MIN_FAILURES: u32 = 3;
+
+/// 预览RSS源时返回的文章标题数
+const PREVIEW_ARTICLE_COUNT: usize = 10;
+
+/// 重新计算阅读指标时每批处理的文章数
+const METRICS_BATCH_SIZE: i64 = 200;
+
+/// 默认的摘要判断字数阈值
+pub const DEFAULT_TEASER_WORD_THRESHOLD: u32 = 50;
+
+/// 专注模式下长文的默认最少字数
+const DEFAULT_LONGFORM_MIN_WORDS: u32 = 1500;
+
+/// 合并收件箱中每个RSS源默认返回的未读文章数
+const DEFAULT_INBOX_LIMIT_PER_FEED: u32 = 10;
+
+/// 批量刷新时的最大并发数
+pub const DEFAULT_REFRESH_CONCURRENCY: usize = 8;
+
+/// 允许设置的最大批量刷新并发数
+pub const MAX_REFRESH_CONCURRENCY: usize = 32;
+
+/// 单个源内容大小上限的最小值（字节）
+const MIN_FEED_MAX_CONTENT_BYTES: u32 = 1024;
+
+/// 标题相同的文章发布时间相差不超过该小时数时才视为疑似重复
+const DUPLICATE_TITLE_WINDOW_HOURS: i64 = 24;
+
+/// 默认的文章内容大小上限（字节），避免内嵌大量图片数据的源撑大数据库
+pub const DEFAULT_MAX_CONTENT_BYTES: u32 = 500 * 1024;
+
+/// 内容超过大小上限被截断时追加的标记
+pub(crate) const CONTENT_TRUNCATED_MARKER: &str = "<p>……（内容过长，已截断）</p>";
+
+/// 查询文章时使用的字段列表
+pub(crate) const ARTICLE_COLUMNS: &str = "id, feed_id, title, link, description, content, author, published_at, updated_at, guid, is_read, is_starred, read_time, reader_prefs, read_at, word_count, duplicate_of, image_url, created_at, content_compressed, content_compression";
+
+/// 刷新时每处理多少篇文章推送一次进度
+const PROGRESS_EMIT_INTERVAL: usize = 5;
+
+/// 刷新单个RSS源时推送rss-fetch-progress事件
+pub(crate) struct FeedProgress<'a> {
+    app_handle: &'a AppHandle,
+    feed_id: String,
+    feed_title: String,
+}
+
+impl<'a> FeedProgress<'a> {
+    fn new(app_handle: &'a AppHandle, feed_id: &str, feed_title: &str) -> Self {
+        Self {
+            app_handle,
+            feed_id: feed_id.to_string(),
+            feed_title: feed_title.to_string(),
+        }
+    }
+
+    fn emit(&self, status: RssFetchStatus, total_articles: u32, fetched_articles: u32, current_article_title: Option<String>) {
+        let progress = RssFetchProgress {
+            feed_id: self.feed_id.clone(),
+            feed_title: self.feed_title.clone(),
+            total_articles,
+            fetched_articles,
+            current_article_title,
+            status,
+        };
+        let _ = self.app_handle.emit("rss-fetch-progress", &progress);
+    }
+
+    /// 推送RSS源因连续失败被自动停用的事件
+    fn emit_auto_disabled(&self, consecutive_failures: u32, last_error: String) {
+        let event = FeedAutoDisabled {
+            feed_id: self.feed_id.clone(),
+            feed_title: self.feed_title.clone(),
+            consecutive_failures,
+            last_error,
+        };
+        let _ = self.app_handle.emit("feed-auto-disabled", &event);
+    }
+
+    /// 推送刷新结束的状态
+    fn finish<T>(&self, result: &AppResult<T>) {
+        let status = match result {
+            Ok(_) => RssFetchStatus::Completed,
+            Err(e) => RssFetchStatus::Failed(e.to_string()),
+        };
+        self.emit(status, 0, 0, None);
+    }
+}
+
+/// 按频率限制刷新的结果
+enum RefreshAttempt {
+    /// 距上次刷新太近，未抓取
+    TooFrequent(String),
+    /// 已抓取并保存
+    Done(RefreshDiff),
+}
+
+/// 条件请求的抓取结果，moved_to为经过永久重定向后的新地址
+enum FeedFetch {
+    /// 服务器返回304，内容未变化
+    NotModified { moved_to: Option<String> },
+    /// 获取到新内容
+    Modified {
+        feed: Box<ParsedFeed>,
+        content_type: Option<String>,
+        charset: Option<String>,
+        etag: Option<String>,
+        last_modified: Option<String>,
+        moved_to: Option<String>,
+    },
+}
+
+impl FeedFetch {
+    fn moved_to(&self) -> Option<&str> {
+        match self {
+            FeedFetch::NotModified { moved_to } | FeedFetch::Modified { moved_to, .. } => moved_to.as_deref(),
+        }
+    }
+}
+
+/// 抓取RSS源使用的HTTP Basic认证信息
+struct BasicAuth {
+    username: String,
+    password: Option<String>,
+}
+
+impl BasicAuth {
+    /// 用户名为空时不使用认证
+    fn new(username: Option<String>, password: Option<String>) -> Option<Self> {
+        let username = username.map(|u| u.trim().to_string()).filter(|u| !u.is_empty())?;
+        Some(BasicAuth {
+            username,
+            password: password.filter(|p| !p.is_empty()),
+        })
+    }
+}
+
+/// save_articles准备好的写入操作
+enum ArticleWrite<'a> {
+    /// 源中已有的文章，正文有变化时更新
+    Update {
+        article_id: String,
+        entry: &'a ParsedEntry,
+        content: Option<String>,
+    },
+    /// 新文章
+    Insert(Box<NewArticle>),
+}
+
+/// 待插入的新文章
+struct NewArticle {
+    id: String,
+    title: String,
+    original_title: Option<String>,
+    link: Option<String>,
+    description: Option<String>,
+    stored: compression::StoredContent,
+    raw_content: Option<String>,
+    author: Option<String>,
+    published_at: Option<String>,
+    updated_at: Option<String>,
+    guid: String,
+    read_time: Option<String>,
+    word_count: Option<u32>,
+    image_url: Option<String>,
+}
+
+/// 文章列表的筛选条件，列表查询和总数查询共用同一个WHERE子句，保证两者的筛选一致
+pub(crate) struct ArticleFilter<'a> {
+    feed_id: Option<&'a str>,
+    is_read: Option<bool>,
+    is_starred: Option<bool>,
+    /// 合并不同源中规范化链接相同的文章，只保留最早保存的一篇
+    collapse_duplicates: bool,
+}
+
+impl<'a> ArticleFilter<'a> {
+    /// 合并重复文章时，存在更早保存的同链接文章的文章被排除；没有规范化链接的文章总是保留
+    const COLLAPSE_DUPLICATES: &'static str = "(link_key IS NULL OR NOT EXISTS (
+             SELECT 1 FROM rss_articles earlier
+             WHERE earlier.link_key = rss_articles.link_key
+               AND (earlier.created_at < rss_articles.created_at
+                    OR (earlier.created_at = rss_articles.created_at AND earlier.rowid < rss_articles.rowid))
+         ))";
+
+    /// 创建筛选条件，没有按RSS源筛选且开启了跨源去重设置时合并重复文章
+    pub(crate) async fn new(
+        db: &SqlitePool,
+        feed_id: Option<&'a str>,
+        is_read: Option<bool>,
+        is_starred: Option<bool>,
+    ) -> AppResult<ArticleFilter<'a>> {
+        let collapse_duplicates =
+            feed_id.is_none() && settings::get_bool(db, settings::DEDUP_ACROSS_FEEDS, false).await?;
+        Ok(ArticleFilter {
+            feed_id,
+            is_read,
+            is_starred,
+            collapse_duplicates,
+        })
+    }
+
+    /// 生成WHERE子句，只包含已设置的条件，以便查询使用索引；多个条件同时设置时需全部满足
+    pub(crate) fn where_clause(&self) -> String {
+        let mut predicates = Vec::new();
+        if self.feed_id.is_some() {
+            predicates.push("feed_id = ?");
+        }
+        if self.is_read.is_some() {
+            predicates.push("is_read = ?");
+        }
+        if self.is_starred.is_some() {
+            predicates.push("is_starred = ?");
+        }
+        if self.collapse_duplicates {
+            predicates.push(Self::COLLAPSE_DUPLICATES);
+        }
+        if predicates.is_empty() {
+            return "1".to_string();
+        }
+        predicates.join(" AND ")
+    }
+
+    /// 按where_clause中占位符的顺序绑定参数
+    pub(crate) fn bind(
+        &self,
+        mut query: sqlx::query::Query<'a, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'a>>,
+    ) -> sqlx::query::Query<'a, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'a>> {
+        if let Some(feed_id) = self.feed_id {
+            query = query.bind(feed_id);
+        }
+        if let Some(is_read) = self.is_read {
+            query = query.bind(is_read);
+        }
+        if let Some(is_starred) = self.is_starred {
+            query = query.bind(is_starred);
+        }
+        query
+    }
+}
+
 impl RssService {
+    /// 发送GET请求，连接失败、超时和5xx响应时按指数退避（500ms、1s、2s，附加随机抖动）重试
+    /// 4xx响应和无效URL等不可重试的错误直接返回
+    pub(crate) async fn fetch_with_retry(
+        request: reqwest::RequestBuilder,
+    ) -> reqwest::Result<reqwest::Response> {
+        let mut attempt = 0;
+        loop {
+            // 无法克隆的请求（流式请求体）只发送一次
+            let Some(current) = request.try_clone() else {
+                return request.send().await;
+            };
+
+            let retryable = match current.send().await {
+                Ok(response) if response.status().is_server_error() && attempt < FETCH_MAX_RETRIES => {
+                    format!("HTTP {}", response.status().as_u16())
+                }
+                Err(e) if (e.is_connect() || e.is_timeout()) && attempt < FETCH_MAX_RETRIES => {
+                    e.to_string()
+                }
+                result => return result,
+            };
+
+            let delay = FETCH_RETRY_BASE_DELAY_MS << attempt;
+            let jitter = (Uuid::new_v4().as_u128() % (delay as u128 / 4 + 1)) as u64;
+            attempt += 1;
+            warn!(
+                "请求失败（{}），{}ms后进行第{}次重试",
+                retryable,
+                delay + jitter,
+                attempt
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(delay + jitter)).await;
+        }
+    }
+
+    /// 获取RSS内容并选择合适的解析器解析
+    async fn fetch_feed(url: &str, auth: Option<&BasicAuth>) -> AppResult<ParsedFeed> {
+        match Self::fetch_feed_conditional(url, auth, None, None).await? {
+            FeedFetch::Modified { feed, .. } => Ok(*feed),
+            FeedFetch::NotModified { .. } => Err(AppError::internal("服务器对非条件请求返回了304")),
+        }
+    }
+
+    /// 带ETag/Last-Modified的条件请求获取RSS内容
+    async fn fetch_feed_conditional(
+        url: &str,
+        auth: Option<&BasicAuth>,
+        etag: Option<&str>,
+        last_modified: Option<&str>,
+    ) -> AppResult<FeedFetch> {
+        let temporary_redirect = Arc::new(AtomicBool::new(false));
+        let client = network::client_builder()
+            .redirect(Self::redirect_policy(temporary_redirect.clone()))
+            .build()?;
+        let mut request = client.get(url);
+        if let Some(auth) = auth {
+            request = request.basic_auth(&auth.username, auth.password.as_deref());
+        }
+        if let Some(etag) = etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+        let response = Self::fetch_with_retry(request)
+            .await
+            .map_err(|e| AppError::request_failed(url, &e))?;
+
+        // 只有全程都是永久重定向（301/308）时才记录新地址
+        let moved_to = (Url::parse(url).ok().as_ref() != Some(response.url())
+            && !temporary_redirect.load(Ordering::SeqCst))
+        .then(|| response.url().to_string());
+
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(FeedFetch::NotModified { moved_to });
+        }
+        if !response.status().is_success() {
+            return Err(AppError::http_status(url, response.status().as_u16()));
+        }
+
+        let header = |name: reqwest::header::HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string())
+        };
+        let content_type = header(reqwest::header::CONTENT_TYPE);
+        let etag = header(reqwest::header::ETAG);
+        let last_modified = header(reqwest::header::LAST_MODIFIED);
+        let content = response
+            .bytes()
+            .await
+            .map_err(|e| AppError::request_failed(url, &e))?;
+
+        Self::reject_html_page(url, content_type.as_deref(), &content)?;
+        // 解析失败说明返回的不是RSS/Atom/JSON Feed内容
+        let feed = parser::parse_feed(content_type.as_deref(), &content)
+            .map_err(|e| AppError::not_a_feed(url, e.to_string()))?;
+
+        Ok(FeedFetch::Modified {
+            feed: Box::new(feed),
+            charset: parser::declared_charset(content_type.as_deref(), &content),
+            content_type,
+            etag,
+            last_modified,
+            moved_to,
+        })
+    }
+
+    /// 跟随重定向（最多MAX_REDIRECTS次），途中出现临时重定向时设置标记
+    fn redirect_policy(temporary_redirect: Arc<AtomicBool>) -> reqwest::redirect::Policy {
+        reqwest::redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                return attempt.error("重定向次数过多");
+            }
+            if !matches!(
+                attempt.status(),
+                reqwest::StatusCode::MOVED_PERMANENTLY | reqwest::StatusCode::PERMANENT_REDIRECT
+            ) {
+                temporary_redirect.store(true, Ordering::SeqCst);
+            }
+            attempt.follow()
+        })
+    }
+
+    /// 地址返回的是HTML网页（如错误页或网站首页）时给出明确提示，而不是底层的解析错误
+    pub(crate) fn reject_html_page(url: &str, content_type: Option<&str>, body: &[u8]) -> AppResult<()> {
+        if parser::looks_like_html(content_type, body) {
+            return Err(AppError::invalid_rss_url_with_hint(
+                url,
+                "该地址返回的是HTML网页而不是RSS源，请在网页中查找订阅链接（通常以/feed、/rss或.xml结尾）",
+            ));
+        }
+        Ok(())
+    }
+
+    /// 获取RSS源的技术信息（字符集、格式、条目统计），不订阅
+    pub async fn get_feed_info(url: &str) -> AppResult<FeedInfo> {
+        Self::inspect_feed(url, None).await.map(|(_, info)| info)
+    }
+
     /// 添加RSS源（同步版本，只创建RSS源记录，不抓取文章）
     pub async fn add_feed_sync(db: &SqlitePool, request: AddFeedRequest) -> AppResult<RssFeed> {
-        // 验证URL格式
-        let url = Url::parse(&request.url).map_err(|_| AppError::invalid_rss_url(&request.url))?;
+        Self::ensure_not_subscribed(db, &request.url).await?;
 
         // 获取RSS内容并解析基本信息
-        // 添加超时设置，避免长时间等待
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()?;
-        let response = client.get(url.as_str()).send().await?;
-        let content = response.text().await?;
-
-        let feed = parser::parse(content.as_bytes())?;
+        let auth = BasicAuth::new(request.username, request.password);
+        let feed = Self::parse_feed_from_url(&request.url, auth.as_ref()).await?;
 
         let feed_id = Uuid::new_v4().to_string();
         // 获取当前本地时间并转换为UTC时间
@@ -36,60 +477,159 @@ impl RssService {
 
         let title = feed
             .title
-            .as_ref()
-            .map(|t| t.content.clone())
             .unwrap_or_else(|| "Untitled Feed".to_string());
-        let description = feed.description.map(|d| d.content);
-        let website_url = feed.links.first().map(|l| l.href.clone());
+        let description = feed.description;
+        let website_url = feed.website_url;
+        // 使用源声明的更新时间，便于按真实的更新时间排序；没有或晚于当前时间时使用当前时间
+        let last_updated = feed.updated.filter(|updated| *updated < now).unwrap_or(now);
 
         // 插入RSS源到数据库
         sqlx::query(
-            "INSERT INTO rss_feeds (id, title, url, description, website_url, last_updated, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO rss_feeds (id, title, url, description, website_url, feed_format, username, password, last_updated, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&feed_id)
         .bind(&title)
         .bind(&request.url)
         .bind(&description)
         .bind(&website_url)
-        .bind(now.to_rfc3339())
+        .bind(&feed.format)
+        .bind(auth.as_ref().map(|a| &a.username))
+        .bind(auth.as_ref().and_then(|a| a.password.as_ref()))
+        .bind(last_updated.to_rfc3339())
         .bind(now.to_rfc3339())
         .bind(now.to_rfc3339())
         .execute(db)
         .await?;
 
-        Ok(RssFeed {
-            id: feed_id,
-            title,
-            url: request.url,
-            description,
-            website_url,
-            last_updated: Some(now),
-            is_active: true,
-            created_at: now,
-            updated_at: now,
+        Self::populate_favicon(db, &feed_id);
+
+        Self::get_feed(db, &feed_id).await
+    }
+
+    /// 检查RSS源是否已订阅，主机名大小写、www前缀和末尾斜杠不同的URL视为同一个源
+    async fn ensure_not_subscribed(db: &SqlitePool, url: &str) -> AppResult<()> {
+        if Self::get_subscribed_urls(db).await?.contains(&utils::normalize_feed_url(url)) {
+            return Err(AppError::feed_already_exists(url));
+        }
+        Ok(())
+    }
+
+    /// 验证URL格式，抓取并解析RSS源，不写入数据库
+    async fn parse_feed_from_url(url: &str, auth: Option<&BasicAuth>) -> AppResult<ParsedFeed> {
+        Self::inspect_feed(url, auth).await.map(|(feed, _)| feed)
+    }
+
+    /// 验证URL格式，抓取并解析RSS源，同时返回源的技术信息，不写入数据库
+    async fn inspect_feed(url: &str, auth: Option<&BasicAuth>) -> AppResult<(ParsedFeed, FeedInfo)> {
+        let parsed = Url::parse(url).map_err(|_| AppError::invalid_rss_url(url))?;
+        if parsed.scheme() != "http" && parsed.scheme() != "https" {
+            return Err(AppError::invalid_rss_url(url));
+        }
+
+        let FeedFetch::Modified { feed, content_type, charset, .. } =
+            Self::fetch_feed_conditional(parsed.as_str(), auth, None, None).await?
+        else {
+            return Err(AppError::internal("服务器对非条件请求返回了304"));
+        };
+
+        let entries_with_content = feed
+            .entries
+            .iter()
+            .filter(|entry| entry.content.as_ref().is_some_and(|c| !c.trim().is_empty()))
+            .count() as u32;
+        let info = FeedInfo {
+            url: url.to_string(),
+            content_type,
+            charset,
+            format: feed.format.clone(),
+            title: feed.title.clone(),
+            entry_count: feed.entries.len() as u32,
+            entries_with_content,
+            newest_entry_date: feed.entries.iter().filter_map(|entry| entry.published).max(),
+        };
+        Ok((*feed, info))
+    }
+
+    /// 读取RSS源保存的认证信息
+    async fn feed_auth(db: &SqlitePool, feed_id: &str) -> AppResult<Option<BasicAuth>> {
+        let row = sqlx::query("SELECT username, password FROM rss_feeds WHERE id = ?")
+            .bind(feed_id)
+            .fetch_optional(db)
+            .await?
+            .ok_or_else(|| AppError::feed_not_found(feed_id))?;
+        Ok(BasicAuth::new(row.get("username"), row.get("password")))
+    }
+
+    /// 设置RSS源的HTTP Basic认证信息，用户名为空时清除
+    ///
+    /// 密码以明文保存在本地数据库中（包括数据库备份），没有额外加密
+    pub async fn set_feed_credentials(
+        db: &SqlitePool,
+        feed_id: &str,
+        username: Option<String>,
+        password: Option<String>,
+    ) -> AppResult<RssFeed> {
+        let auth = BasicAuth::new(username, password);
+        let result = sqlx::query("UPDATE rss_feeds SET username = ?, password = ? WHERE id = ?")
+            .bind(auth.as_ref().map(|a| &a.username))
+            .bind(auth.as_ref().and_then(|a| a.password.as_ref()))
+            .bind(feed_id)
+            .execute(db)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::feed_not_found(feed_id));
+        }
+
+        info!("RSS源 {} 的认证信息已{}", feed_id, if auth.is_some() { "更新" } else { "清除" });
+        Self::get_feed(db, feed_id).await
+    }
+
+    /// 预览RSS源：抓取并解析，返回标题、简介和前几篇文章的标题，不写入数据库
+    /// 需要认证的源可传入HTTP Basic认证的用户名和密码
+    pub async fn preview_feed(
+        url: &str,
+        username: Option<String>,
+        password: Option<String>,
+    ) -> AppResult<FeedPreview> {
+        let auth = BasicAuth::new(username, password);
+        let feed = Self::parse_feed_from_url(url, auth.as_ref()).await?;
+
+        Ok(FeedPreview {
+            url: url.to_string(),
+            title: feed.title,
+            description: feed.description,
+            website_url: feed.website_url,
+            article_titles: feed
+                .entries
+                .iter()
+                .take(PREVIEW_ARTICLE_COUNT)
+                .map(|entry| entry.title.clone().unwrap_or_else(|| "Untitled Article".to_string()))
+                .collect(),
         })
     }
 
-    /// 异步抓取RSS文章
+    /// 异步抓取RSS文章，每篇文章之间检查取消令牌，取消后不再处理剩余文章
     pub async fn fetch_articles_async(
         db: &SqlitePool,
         feed_id: &str,
         url: &str,
         app_handle: &AppHandle,
+        cancel: &CancellationToken,
     ) -> AppResult<()> {
         // 获取RSS内容并解析
-        // 添加超时设置，避免长时间等待
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()?;
-        let response = client.get(url).send().await?;
-        let content = response.text().await?;
-        let feed = parser::parse(content.as_bytes())?;
+        let auth = Self::feed_auth(db, feed_id).await?;
+        let feed = Self::fetch_feed(url, auth.as_ref()).await?;
+        let timezone = sqlx::query_scalar::<_, Option<String>>("SELECT timezone FROM rss_feeds WHERE id = ?")
+            .bind(feed_id)
+            .fetch_optional(db)
+            .await?
+            .flatten()
+            .and_then(|tz| utils::parse_timezone(&tz).ok());
         
         // 获取当前本地时间并转换为UTC时间
         let now = Local::now().with_timezone(&Utc);
         let total_articles = feed.entries.len() as u32;
-        let feed_title = feed.title.as_ref().map(|t| t.content.clone()).unwrap_or_else(|| "Unknown".to_string());
+        let feed_title = feed.title.clone().unwrap_or_else(|| "Unknown".to_string());
         
         // 发送进度更新
         let progress = RssFetchProgress {
@@ -101,17 +641,25 @@ impl RssService {
             status: RssFetchStatus::InProgress,
         };
         let _ = app_handle.emit("rss-fetch-progress", &progress);
+        let deleted = cleanup::DeletedArticles::load(db, feed_id).await?;
         
         // 逐个处理文章
         for (index, entry) in feed.entries.iter().enumerate() {
+            if cancel.is_cancelled() {
+                info!("RSS源 {} 的抓取已取消，已处理 {} 篇文章", feed_id, index);
+                return Ok(());
+            }
             let article_id = Uuid::new_v4().to_string();
-            let title = entry.title.as_ref().map(|t| t.content.clone()).unwrap_or_else(|| "Untitled".to_string());
-            let link = entry.links.first().map(|l| l.href.clone());
-            let description = entry.summary.as_ref().map(|s| s.content.clone());
-            let author = entry.authors.first().map(|a| a.name.clone());
-            let published_at = entry.published.map(|dt| dt.with_timezone(&Utc));
+            let title = entry.title.clone().unwrap_or_else(|| "Untitled".to_string());
+            let link = entry.link.clone();
+            let description = entry.summary.clone();
+            let author = entry.author.clone();
+            let published_at = entry.published_in(timezone);
+            let updated_at = entry.updated_in(timezone);
             let guid = entry.id.clone();
-            let read_time = Self::extract_read_time(entry);
+            // 这里只保存摘要，按摘要估算阅读时间
+            let read_time = Self::extract_read_time(entry, description.as_deref());
+            let image_url = entry.image_url.clone();
             
             // 检查文章是否已存在
             let existing = sqlx::query(
@@ -121,24 +669,32 @@ impl RssService {
             .bind(feed_id)
             .fetch_optional(db)
             .await?;
+            let was_deleted = deleted.contains(&guid, link.as_deref().map(utils::normalize_article_link).as_deref());
             
-            if existing.is_none() {
+            if existing.is_none() && !was_deleted {
                 // 插入新文章
                 sqlx::query(
-                    "INSERT INTO rss_articles (id, feed_id, title, link, description, author, published_at, guid, read_time, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+                    "INSERT INTO rss_articles (id, feed_id, title, link, link_key, description, author, published_at, updated_at, guid, read_time, word_count, image_url, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
                 )
                 .bind(&article_id)
                 .bind(feed_id)
                 .bind(&title)
                 .bind(&link)
+                .bind(link.as_deref().map(utils::normalize_article_link))
                 .bind(&description)
                 .bind(&author)
                 .bind(published_at.map(|dt| dt.to_rfc3339()))
+                .bind(updated_at.map(|dt| dt.to_rfc3339()))
                 .bind(&Some(guid.clone()))
                 .bind(&read_time)
+                .bind(description.as_deref().map(utils::count_words))
+                .bind(&image_url)
                 .bind(now.to_rfc3339())
                 .execute(db)
                 .await?;
+                let duplicate_of =
+                    Self::flag_duplicate(&mut *db.acquire().await?, &article_id, feed_id, link.as_deref(), &title, published_at)
+                        .await?;
                 
                 // 创建文章对象并发送事件
                 let article = RssArticle {
@@ -150,10 +706,16 @@ impl RssService {
                     content: None,
                     author: author.clone(),
                     published_at,
+                    updated_at,
                     guid: Some(guid),
                     is_read: false,
                     is_starred: false,
                     read_time: read_time.clone(),
+                    reader_prefs: None,
+                    read_at: None,
+                    word_count: description.as_deref().map(utils::count_words),
+                    duplicate_of,
+                    image_url,
                     created_at: now,
                 };
                 
@@ -195,18 +757,11 @@ impl RssService {
 
     /// 添加RSS源（原版本，保持兼容性）
     pub async fn add_feed(db: &SqlitePool, request: AddFeedRequest) -> AppResult<RssFeed> {
-        // 验证URL格式
-        let url = Url::parse(&request.url).map_err(|_| AppError::invalid_rss_url(&request.url))?;
+        Self::ensure_not_subscribed(db, &request.url).await?;
 
         // 获取RSS内容并解析
-        // 添加超时设置，避免长时间等待
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()?;
-        let response = client.get(url.as_str()).send().await?;
-        let content = response.text().await?;
-
-        let feed = parser::parse(content.as_bytes())?;
+        let auth = BasicAuth::new(request.username, request.password);
+        let feed = Self::parse_feed_from_url(&request.url, auth.as_ref()).await?;
 
         let feed_id = Uuid::new_v4().to_string();
         // 获取当前本地时间并转换为UTC时间
@@ -214,543 +769,3539 @@ impl RssService {
 
         let title = feed
             .title
-            .map(|t| t.content)
             .unwrap_or_else(|| "Untitled Feed".to_string());
-        let description = feed.description.map(|d| d.content);
-        let website_url = feed.links.first().map(|l| l.href.clone());
+        let description = feed.description;
+        let website_url = feed.website_url;
+        // 使用源声明的更新时间，便于按真实的更新时间排序；没有或晚于当前时间时使用当前时间
+        let last_updated = feed.updated.filter(|updated| *updated < now).unwrap_or(now);
 
         // 插入RSS源到数据库
         sqlx::query(
-            "INSERT INTO rss_feeds (id, title, url, description, website_url, last_updated, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO rss_feeds (id, title, url, description, website_url, feed_format, username, password, last_updated, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&feed_id)
         .bind(&title)
         .bind(&request.url)
         .bind(&description)
         .bind(&website_url)
-        .bind(now.to_rfc3339())
+        .bind(&feed.format)
+        .bind(auth.as_ref().map(|a| &a.username))
+        .bind(auth.as_ref().and_then(|a| a.password.as_ref()))
+        .bind(last_updated.to_rfc3339())
         .bind(now.to_rfc3339())
         .bind(now.to_rfc3339())
         .execute(db)
         .await?;
 
+        Self::populate_favicon(db, &feed_id);
+
         // 解析并保存文章
         Self::save_articles(db, &feed_id, &feed.entries, &now).await?;
 
-        Ok(RssFeed {
-            id: feed_id,
-            title,
-            url: request.url,
-            description,
-            website_url,
-            last_updated: Some(now),
-            is_active: true,
-            created_at: now,
-            updated_at: now,
-        })
+        Self::get_feed(db, &feed_id).await
     }
 
     /// 获取所有RSS源
-    pub async fn get_feeds(db: &SqlitePool) -> AppResult<Vec<RssFeed>> {
-        let rows = sqlx::query(
-            "SELECT id, title, url, description, website_url, last_updated, is_active, created_at, updated_at FROM rss_feeds ORDER BY created_at DESC"
-        )
-        .fetch_all(db)
-        .await?;
+    pub async fn get_feeds(storage: &impl Storage, include_inactive: bool) -> AppResult<Vec<RssFeed>> {
+        storage.get_feeds(include_inactive).await
+    }
 
-        let mut feeds = Vec::new();
-        for row in rows {
-            let created_at_str: String = row.get("created_at");
-            let updated_at_str: String = row.get("updated_at");
-            let last_updated_str: Option<String> = row.get("last_updated");
-
-            feeds.push(RssFeed {
-                id: row.get("id"),
-                title: row.get("title"),
-                url: row.get("url"),
-                description: row.get("description"),
-                website_url: row.get("website_url"),
-                last_updated: last_updated_str.and_then(|s| {
-                    DateTime::parse_from_rfc3339(&s)
-                        .ok()
-                        .map(|dt| dt.with_timezone(&Utc))
-                }),
-                is_active: row.get("is_active"),
-                created_at: DateTime::parse_from_rfc3339(&created_at_str)
-                    .unwrap()
-                    .with_timezone(&Utc),
-                updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
-                    .unwrap()
-                    .with_timezone(&Utc),
-            });
+    /// 获取单个RSS源
+    pub async fn get_feed(storage: &impl Storage, feed_id: &str) -> AppResult<RssFeed> {
+        storage.get_feed(feed_id).await
+    }
+
+    /// 将查询结果行转换为RSS源对象（需包含FEED_COLUMNS中的字段）
+    pub(crate) fn feed_from_row(row: &SqliteRow) -> RssFeed {
+        let id: String = row.get("id");
+        let created_at = Self::parse_stored_time(row.try_get("created_at").ok(), "rss_feeds", &id);
+        let updated_at = Self::parse_stored_time(row.try_get("updated_at").ok(), "rss_feeds", &id);
+        let last_updated_str: Option<String> = row.get("last_updated");
+        let alternate_urls_str: Option<String> = row.get("alternate_urls");
+
+        RssFeed {
+            id,
+            title: row.get("title"),
+            url: row.get("url"),
+            description: row.get("description"),
+            website_url: row.get("website_url"),
+            last_updated: last_updated_str.and_then(|s| {
+                DateTime::parse_from_rfc3339(&s)
+                    .ok()
+                    .map(|dt| dt.with_timezone(&Utc))
+            }),
+            is_active: row.get("is_active"),
+            cron: row.get("cron"),
+            alternate_urls: alternate_urls_str
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or_default(),
+            last_fetched_url: row.get("last_fetched_url"),
+            timezone: row.get("timezone"),
+            title_strip_prefix: row.get("title_strip_prefix"),
+            title_strip_is_regex: row.get("title_strip_is_regex"),
+            requires_render: row.get("requires_render"),
+            max_content_bytes: row.get::<Option<i64>, _>("max_content_bytes").map(|n| n as u32),
+            feed_format: row.get("feed_format"),
+            category_id: row.get("category_id"),
+            unread_count: row.get::<i64, _>("unread_count") as u32,
+            refresh_interval_minutes: row
+                .get::<Option<i64>, _>("refresh_interval_minutes")
+                .map(|n| n as u32),
+            favicon_url: row.get("favicon_url"),
+            last_error: row.get("last_error"),
+            last_error_at: row
+                .get::<Option<String>, _>("last_error_at")
+                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
+            consecutive_failures: row.get::<i64, _>("consecutive_failures") as u32,
+            username: row.get("username"),
+            created_at,
+            updated_at,
         }
+    }
 
-        Ok(feeds)
+    /// 解析数据库中存储的时间（RFC3339，兼容SQLite的 "YYYY-MM-DD HH:MM:SS" 格式）
+    /// 缺失或格式错误时记录警告并使用当前时间，避免一条损坏的记录导致整个列表查询失败
+    fn parse_stored_time(value: Option<String>, table: &str, id: &str) -> DateTime<Utc> {
+        let parsed = value.as_deref().and_then(|s| {
+            DateTime::parse_from_rfc3339(s)
+                .map(|dt| dt.with_timezone(&Utc))
+                .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").map(|dt| dt.and_utc()))
+                .ok()
+        });
+        parsed.unwrap_or_else(|| {
+            warn!("{} 中记录 {} 的时间无效: {:?}，使用当前时间代替", table, id, value);
+            Utc::now()
+        })
     }
 
     /// 获取文章列表
     pub async fn get_articles(
-        db: &SqlitePool,
+        storage: &impl Storage,
         feed_id: Option<String>,
         limit: Option<i32>,
         offset: Option<i32>,
+        sort: Option<ArticleSort>,
+        is_read: Option<bool>,
+        is_starred: Option<bool>,
+    ) -> AppResult<Vec<RssArticle>> {
+        storage
+            .get_articles(feed_id, limit, offset, sort, is_read, is_starred)
+            .await
+    }
+
+    /// 按游标分页获取文章列表，按(published_at DESC, id DESC)排序，没有发布时间的文章视为最旧
+    /// 第一页不传游标；之后传入上一页最后一篇文章的published_at和id，返回排在它之后的文章
+    /// 翻页期间新增的文章不会导致重复或遗漏
+    pub async fn get_articles_after(
+        db: &SqlitePool,
+        cursor_published_at: Option<DateTime<Utc>>,
+        cursor_id: Option<String>,
+        limit: Option<i32>,
+        feed_id: Option<String>,
+        is_read: Option<bool>,
+        is_starred: Option<bool>,
     ) -> AppResult<Vec<RssArticle>> {
+        // 与保存时的格式一致，保证字符串比较的顺序正确
+        let cursor_published_at = cursor_published_at.map(|dt| dt.to_rfc3339());
+        let filter = ArticleFilter::new(db, feed_id.as_deref(), is_read, is_starred).await?;
+        let sql = format!(
+            "SELECT {} FROM rss_articles WHERE {}
+             AND (
+                 ? IS NULL
+                 OR (? IS NULL AND published_at IS NULL AND id < ?)
+                 OR (? IS NOT NULL AND (published_at < ? OR (published_at = ? AND id < ?) OR published_at IS NULL))
+             )
+             ORDER BY published_at IS NULL, published_at DESC, id DESC
+             LIMIT ?",
+            ARTICLE_COLUMNS,
+            filter.where_clause()
+        );
+        let rows = filter
+            .bind(sqlx::query(&sql))
+            .bind(&cursor_id)
+            .bind(&cursor_published_at)
+            .bind(&cursor_id)
+            .bind(&cursor_published_at)
+            .bind(&cursor_published_at)
+            .bind(&cursor_published_at)
+            .bind(&cursor_id)
+            .bind(limit.unwrap_or(50))
+            .fetch_all(db)
+            .await?;
+
+        Ok(rows.iter().map(Self::article_from_row).collect())
+    }
+
+    /// 分页获取文章列表，同时返回相同筛选条件下的文章总数
+    pub async fn get_articles_paginated(
+        db: &SqlitePool,
+        feed_id: Option<String>,
+        limit: Option<i32>,
+        offset: Option<i32>,
+        sort: Option<ArticleSort>,
+        is_read: Option<bool>,
+        is_starred: Option<bool>,
+    ) -> AppResult<ArticlePage> {
         let limit = limit.unwrap_or(50);
         let offset = offset.unwrap_or(0);
 
-        let query = if let Some(feed_id) = feed_id {
-            sqlx::query(
-                "SELECT id, feed_id, title, link, description, content, author, published_at, guid, is_read, is_starred, read_time, created_at FROM rss_articles WHERE feed_id = ? ORDER BY published_at DESC, created_at DESC LIMIT ? OFFSET ?"
-            )
-            .bind(feed_id)
-            .bind(limit)
-            .bind(offset)
-        } else {
-            sqlx::query(
-                "SELECT id, feed_id, title, link, description, content, author, published_at, guid, is_read, is_starred, read_time, created_at FROM rss_articles ORDER BY published_at DESC, created_at DESC LIMIT ? OFFSET ?"
-            )
-            .bind(limit)
-            .bind(offset)
-        };
+        let filter = ArticleFilter::new(db, feed_id.as_deref(), is_read, is_starred).await?;
+        let sql = format!(
+            "SELECT COUNT(*) as count FROM rss_articles WHERE {}",
+            filter.where_clause()
+        );
+        let total: i64 = filter.bind(sqlx::query(&sql)).fetch_one(db).await?.get("count");
 
-        let rows = query.fetch_all(db).await?;
-
-        let mut articles = Vec::new();
-        for row in rows {
-            let created_at_str: String = row.get("created_at");
-            let published_at_str: Option<String> = row.get("published_at");
-
-            articles.push(RssArticle {
-                id: row.get("id"),
-                feed_id: row.get("feed_id"),
-                title: row.get("title"),
-                link: row.get("link"),
-                description: row.get("description"),
-                content: row.get("content"),
-                author: row.get("author"),
-                published_at: published_at_str.and_then(|s| {
-                    DateTime::parse_from_rfc3339(&s)
-                        .ok()
-                        .map(|dt| dt.with_timezone(&Utc))
-                }),
-                guid: row.get("guid"),
-                is_read: row.get("is_read"),
-                is_starred: row.get("is_starred"),
-                read_time: row.get("read_time"),
-                created_at: DateTime::parse_from_rfc3339(&created_at_str)
-                    .unwrap()
-                    .with_timezone(&Utc),
-            });
-        }
+        let articles = Self::get_articles(db, feed_id, Some(limit), Some(offset), sort, is_read, is_starred)
+            .await?;
 
-        Ok(articles)
+        Ok(ArticlePage {
+            articles,
+            total,
+            offset,
+            limit,
+        })
     }
 
-    /// 获取统计信息
-    pub async fn get_statistics(db: &SqlitePool) -> AppResult<serde_json::Value> {
-        // 获取总文章数
-        let total_articles_row = sqlx::query("SELECT COUNT(*) as count FROM rss_articles")
-            .fetch_one(db)
-            .await?;
-        let total_articles: i64 = total_articles_row.get("count");
-
-        // 获取未读文章数
-        let unread_articles_row =
-            sqlx::query("SELECT COUNT(*) as count FROM rss_articles WHERE is_read = 0")
-                .fetch_one(db)
-                .await?;
-        let unread_articles: i64 = unread_articles_row.get("count");
+    /// 获取每个活跃RSS源最新的一篇未读文章，用于每日摘要
+    pub async fn get_top_unread_per_feed(db: &SqlitePool) -> AppResult<Vec<TopUnreadArticle>> {
+        // 使用窗口函数按源分区取最新一篇，避免逐个源查询
+        let rows = sqlx::query(&format!(
+            "SELECT f.id AS digest_feed_id, f.title AS feed_title, a.*
+             FROM rss_feeds f
+             LEFT JOIN (
+                 SELECT {}, ROW_NUMBER() OVER (
+                     PARTITION BY feed_id ORDER BY published_at DESC, created_at DESC
+                 ) AS rn
+                 FROM rss_articles
+                 WHERE is_read = 0
+             ) a ON a.feed_id = f.id AND a.rn = 1
+             WHERE f.is_active = 1
+             ORDER BY f.title",
+            ARTICLE_COLUMNS
+        ))
+        .fetch_all(db)
+        .await?;
 
-        // 获取已收藏文章数
-        let starred_articles_row =
-            sqlx::query("SELECT COUNT(*) as count FROM rss_articles WHERE is_starred = 1")
-                .fetch_one(db)
-                .await?;
-        let starred_articles: i64 = starred_articles_row.get("count");
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let article_id: Option<String> = row.get("id");
+                TopUnreadArticle {
+                    feed_id: row.get("digest_feed_id"),
+                    feed_title: row.get("feed_title"),
+                    article: article_id.map(|_| Self::article_from_row(row)),
+                }
+            })
+            .collect())
+    }
 
-        // 获取RSS源数量
-        let total_feeds_row =
-            sqlx::query("SELECT COUNT(*) as count FROM rss_feeds WHERE is_active = 1")
-                .fetch_one(db)
-                .await?;
-        let total_feeds: i64 = total_feeds_row.get("count");
+    /// 获取活跃RSS源中今天发布的文章（按指定时区划分日期，未指定时使用系统时区）
+    /// 没有发布时间的文章按入库时间计算
+    pub async fn get_todays_articles(
+        db: &SqlitePool,
+        timezone: Option<String>,
+    ) -> AppResult<Vec<RssArticleWithFeed>> {
+        let now = Utc::now();
+        let (start, end) = match timezone.as_deref().map(str::trim) {
+            Some(tz) if !tz.is_empty() => utils::local_day_bounds(now, &utils::parse_timezone(tz)?),
+            _ => utils::local_day_bounds(now, &Local),
+        };
 
-        // 获取每个RSS源的未读文章数
-        let feed_unread_rows = sqlx::query(
-            "SELECT f.id, f.title, COUNT(a.id) as unread_count 
-             FROM rss_feeds f 
-             LEFT JOIN rss_articles a ON f.id = a.feed_id AND a.is_read = 0 
-             WHERE f.is_active = 1 
-             GROUP BY f.id, f.title",
-        )
+        let rows = sqlx::query(&format!(
+            "SELECT f.title AS feed_title, a.*
+             FROM (SELECT {} FROM rss_articles) a
+             JOIN rss_feeds f ON f.id = a.feed_id
+             WHERE f.is_active = 1
+               AND datetime(COALESCE(a.published_at, a.created_at)) >= datetime(?)
+               AND datetime(COALESCE(a.published_at, a.created_at)) < datetime(?)
+             ORDER BY datetime(COALESCE(a.published_at, a.created_at)) DESC",
+            ARTICLE_COLUMNS
+        ))
+        .bind(start.to_rfc3339())
+        .bind(end.to_rfc3339())
         .fetch_all(db)
         .await?;
 
-        let mut feed_stats = Vec::new();
-        for row in feed_unread_rows {
-            feed_stats.push(serde_json::json!({
-                "id": row.get::<String, _>("id"),
+        Ok(rows
+            .iter()
+            .map(|row| RssArticleWithFeed {
+                article: Self::article_from_row(row),
+                feed_title: row.get("feed_title"),
+            })
+            .collect())
+    }
+
+    /// 专注模式：获取字数超过阈值的未读长文，按发布时间从新到旧排序
+    pub async fn get_longform_unread(
+        db: &SqlitePool,
+        min_words: Option<u32>,
+        limit: Option<i32>,
+        feed_id: Option<String>,
+    ) -> AppResult<Vec<RssArticle>> {
+        let min_words = min_words.unwrap_or(DEFAULT_LONGFORM_MIN_WORDS);
+        let limit = limit.unwrap_or(50);
+
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM rss_articles
+             WHERE is_read = 0 AND word_count > ? AND (? IS NULL OR feed_id = ?)
+             ORDER BY published_at DESC, created_at DESC LIMIT ?",
+            ARTICLE_COLUMNS
+        ))
+        .bind(min_words)
+        .bind(&feed_id)
+        .bind(&feed_id)
+        .bind(limit)
+        .fetch_all(db)
+        .await?;
+
+        Ok(rows.iter().map(Self::article_from_row).collect())
+    }
+
+    /// 获取合并收件箱：按RSS源分组的最新未读文章，只包含有未读文章的活跃源
+    /// 源按最新一篇未读文章的时间排序
+    pub async fn get_inbox(
+        db: &SqlitePool,
+        limit_per_feed: Option<u32>,
+    ) -> AppResult<Vec<InboxGroup>> {
+        let limit = limit_per_feed.unwrap_or(DEFAULT_INBOX_LIMIT_PER_FEED).max(1);
+
+        let rows = sqlx::query(&format!(
+            "SELECT a.* FROM (
+                 SELECT {}, ROW_NUMBER() OVER (
+                     PARTITION BY feed_id ORDER BY published_at DESC, created_at DESC
+                 ) AS rn
+                 FROM rss_articles
+                 WHERE is_read = 0
+             ) a
+             JOIN rss_feeds f ON f.id = a.feed_id
+             WHERE f.is_active = 1 AND a.rn <= ?
+             ORDER BY a.feed_id, a.rn",
+            ARTICLE_COLUMNS
+        ))
+        .bind(limit)
+        .fetch_all(db)
+        .await?;
+
+        let mut feeds: std::collections::HashMap<String, RssFeed> = Self::get_feeds(db, true)
+            .await?
+            .into_iter()
+            .map(|feed| (feed.id.clone(), feed))
+            .collect();
+
+        let mut groups: Vec<InboxGroup> = Vec::new();
+        for article in rows.iter().map(Self::article_from_row) {
+            match groups.last_mut() {
+                Some(group) if group.feed.id == article.feed_id => group.articles.push(article),
+                _ => {
+                    if let Some(feed) = feeds.remove(&article.feed_id) {
+                        groups.push(InboxGroup {
+                            feed,
+                            articles: vec![article],
+                        });
+                    }
+                }
+            }
+        }
+
+        // 每组第一篇即该源最新的未读文章
+        groups.sort_by(|a, b| {
+            let newest = |g: &InboxGroup| (g.articles[0].published_at, g.articles[0].created_at);
+            newest(b).cmp(&newest(a))
+        });
+
+        Ok(groups)
+    }
+
+    /// 将查询结果行转换为文章对象（需包含ARTICLE_COLUMNS中的字段）
+    /// 读取文章内容，压缩存储的内容解压后返回，无法解压时记录日志并视为没有内容
+    fn stored_content(row: &SqliteRow, article_id: &str) -> Option<String> {
+        let content: Option<String> = row.get("content");
+        if content.is_some() {
+            return content;
+        }
+        compression::decode(
+            row.get::<Option<String>, _>("content_compression").as_deref(),
+            row.get::<Option<Vec<u8>>, _>("content_compressed").as_deref(),
+        )
+        .unwrap_or_else(|e| {
+            warn!("文章 {} 的压缩内容无法解压: {}", article_id, e);
+            None
+        })
+    }
+
+    pub(crate) fn article_from_row(row: &SqliteRow) -> RssArticle {
+        let id: String = row.get("id");
+        let created_at = Self::parse_stored_time(row.try_get("created_at").ok(), "rss_articles", &id);
+        let published_at_str: Option<String> = row.get("published_at");
+        let updated_at_str: Option<String> = row.get("updated_at");
+        let reader_prefs_str: Option<String> = row.get("reader_prefs");
+        let read_at_str: Option<String> = row.get("read_at");
+        let content = Self::stored_content(row, &id);
+
+        RssArticle {
+            id,
+            feed_id: row.get("feed_id"),
+            title: row.get("title"),
+            link: row.get("link"),
+            description: row.get("description"),
+            content,
+            author: row.get("author"),
+            published_at: published_at_str.and_then(|s| {
+                DateTime::parse_from_rfc3339(&s)
+                    .ok()
+                    .map(|dt| dt.with_timezone(&Utc))
+            }),
+            updated_at: updated_at_str.and_then(|s| {
+                DateTime::parse_from_rfc3339(&s)
+                    .ok()
+                    .map(|dt| dt.with_timezone(&Utc))
+            }),
+            guid: row.get("guid"),
+            is_read: row.get("is_read"),
+            is_starred: row.get("is_starred"),
+            read_time: row.get("read_time"),
+            reader_prefs: reader_prefs_str.and_then(|s| serde_json::from_str(&s).ok()),
+            read_at: read_at_str.and_then(|s| {
+                DateTime::parse_from_rfc3339(&s)
+                    .ok()
+                    .map(|dt| dt.with_timezone(&Utc))
+            }),
+            word_count: row.get::<Option<i64>, _>("word_count").map(|n| n as u32),
+            duplicate_of: row.get("duplicate_of"),
+            image_url: row.get("image_url"),
+            created_at,
+        }
+    }
+
+    /// 获取统计信息
+    pub async fn get_statistics(db: &SqlitePool) -> AppResult<serde_json::Value> {
+        // 获取总文章数
+        let total_articles_row = sqlx::query("SELECT COUNT(*) as count FROM rss_articles")
+            .fetch_one(db)
+            .await?;
+        let total_articles: i64 = total_articles_row.get("count");
+
+        // 获取未读文章数
+        let unread_articles_row =
+            sqlx::query("SELECT COUNT(*) as count FROM rss_articles WHERE is_read = 0")
+                .fetch_one(db)
+                .await?;
+        let unread_articles: i64 = unread_articles_row.get("count");
+
+        // 获取已收藏文章数
+        let starred_articles_row =
+            sqlx::query("SELECT COUNT(*) as count FROM rss_articles WHERE is_starred = 1")
+                .fetch_one(db)
+                .await?;
+        let starred_articles: i64 = starred_articles_row.get("count");
+
+        // 获取RSS源数量
+        let total_feeds_row =
+            sqlx::query("SELECT COUNT(*) as count FROM rss_feeds WHERE is_active = 1")
+                .fetch_one(db)
+                .await?;
+        let total_feeds: i64 = total_feeds_row.get("count");
+
+        // 获取每个RSS源的未读文章数（读取缓存列）
+        let feed_unread_rows = sqlx::query(
+            "SELECT id, title, unread_count FROM rss_feeds WHERE is_active = 1",
+        )
+        .fetch_all(db)
+        .await?;
+
+        let mut feed_stats = Vec::new();
+        for row in feed_unread_rows {
+            feed_stats.push(serde_json::json!({
+                "id": row.get::<String, _>("id"),
                 "title": row.get::<String, _>("title"),
                 "unread_count": row.get::<i64, _>("unread_count")
             }));
         }
 
-        Ok(serde_json::json!({
-            "total_articles": total_articles,
-            "unread_articles": unread_articles,
-            "starred_articles": starred_articles,
-            "total_feeds": total_feeds,
-            "feed_stats": feed_stats
-        }))
-    }
+        let reading = Self::get_reading_statistics(db, Utc::now()).await?;
+
+        Ok(serde_json::json!({
+            "total_articles": total_articles,
+            "unread_articles": unread_articles,
+            "starred_articles": starred_articles,
+            "total_feeds": total_feeds,
+            "feed_stats": feed_stats,
+            "reading": reading
+        }))
+    }
+
+    /// 阅读统计：已读文章的总字数、最近7天读完的文章数和最近7天新增文章最多的源
+    async fn get_reading_statistics(db: &SqlitePool, now: DateTime<Utc>) -> AppResult<serde_json::Value> {
+        let week_ago = (now - chrono::Duration::days(7)).to_rfc3339();
+
+        let total_words_read: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(word_count), 0) FROM rss_articles WHERE is_read = 1",
+        )
+        .fetch_one(db)
+        .await?;
+
+        let articles_read_last_7_days: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM rss_articles
+             WHERE is_read = 1 AND read_at IS NOT NULL AND datetime(read_at) >= datetime(?)",
+        )
+        .bind(&week_ago)
+        .fetch_one(db)
+        .await?;
+
+        let most_active_feed = sqlx::query(
+            "SELECT f.id, f.title, COUNT(a.id) AS article_count
+             FROM rss_articles a
+             JOIN rss_feeds f ON f.id = a.feed_id
+             WHERE datetime(a.created_at) >= datetime(?)
+             GROUP BY f.id
+             ORDER BY article_count DESC, f.title
+             LIMIT 1",
+        )
+        .bind(&week_ago)
+        .fetch_optional(db)
+        .await?
+        .map(|row| {
+            serde_json::json!({
+                "id": row.get::<String, _>("id"),
+                "title": row.get::<String, _>("title"),
+                "article_count": row.get::<i64, _>("article_count")
+            })
+        });
+
+        Ok(serde_json::json!({
+            "total_words_read": total_words_read,
+            "articles_read_last_7_days": articles_read_last_7_days,
+            "most_active_feed": most_active_feed
+        }))
+    }
+
+    /// 统计每个RSS源的文章占用的近似存储空间，按占用从大到小排序
+    pub async fn get_storage_by_feed(db: &SqlitePool) -> AppResult<Vec<FeedStorage>> {
+        let rows = sqlx::query(
+            "SELECT f.id, f.title, COUNT(a.id) AS article_count,
+                    COALESCE(SUM(
+                        COALESCE(LENGTH(CAST(a.content AS BLOB)), 0)
+                        + COALESCE(LENGTH(CAST(a.content_raw AS BLOB)), 0)
+                        + COALESCE(LENGTH(a.content_compressed), 0)
+                        + COALESCE(LENGTH(CAST(a.description AS BLOB)), 0)
+                    ), 0) AS bytes
+             FROM rss_feeds f
+             LEFT JOIN rss_articles a ON a.feed_id = f.id
+             GROUP BY f.id, f.title
+             ORDER BY bytes DESC, f.title",
+        )
+        .fetch_all(db)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| FeedStorage {
+                feed_id: row.get("id"),
+                feed_title: row.get("title"),
+                article_count: row.get::<i64, _>("article_count") as u32,
+                bytes: row.get::<i64, _>("bytes") as u64,
+            })
+            .collect())
+    }
+
+    /// 分析RSS源的发文频率
+    pub async fn get_feed_cadence(db: &SqlitePool, feed_id: String) -> AppResult<FeedCadence> {
+        let exists = sqlx::query("SELECT id FROM rss_feeds WHERE id = ?")
+            .bind(&feed_id)
+            .fetch_optional(db)
+            .await?;
+        if exists.is_none() {
+            return Err(AppError::feed_not_found(&feed_id));
+        }
+
+        let rows = sqlx::query(
+            "SELECT published_at FROM rss_articles WHERE feed_id = ? AND published_at IS NOT NULL",
+        )
+        .bind(&feed_id)
+        .fetch_all(db)
+        .await?;
+
+        let published: Vec<DateTime<Utc>> = rows
+            .iter()
+            .filter_map(|row| {
+                let published_at_str: String = row.get("published_at");
+                DateTime::parse_from_rfc3339(&published_at_str)
+                    .ok()
+                    .map(|dt| dt.with_timezone(&Utc))
+            })
+            .collect();
+
+        Ok(Self::compute_feed_cadence(feed_id, published))
+    }
+
+    /// 根据发布时间计算发文频率
+    pub fn compute_feed_cadence(feed_id: String, mut published: Vec<DateTime<Utc>>) -> FeedCadence {
+        published.sort();
+
+        let mut posts_by_weekday = [0u32; 7];
+        for dt in &published {
+            posts_by_weekday[dt.weekday().num_days_from_monday() as usize] += 1;
+        }
+
+        // 相邻两篇文章之间的间隔（小时）
+        let mut gaps: Vec<f64> = published
+            .windows(2)
+            .map(|w| (w[1] - w[0]).num_minutes() as f64 / 60.0)
+            .collect();
+        gaps.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let average_gap_hours = if gaps.is_empty() {
+            None
+        } else {
+            Some(gaps.iter().sum::<f64>() / gaps.len() as f64)
+        };
+        let median_gap_hours = if gaps.is_empty() {
+            None
+        } else {
+            // 奇数个时两个下标相同
+            Some((gaps[(gaps.len() - 1) / 2] + gaps[gaps.len() / 2]) / 2.0)
+        };
+
+        // 时间跨度不足一周时按一周计算，避免夸大频率
+        let posts_per_week = match (published.first(), published.last()) {
+            (Some(first), Some(last)) => {
+                let weeks = ((*last - *first).num_minutes() as f64 / (60.0 * 24.0 * 7.0)).max(1.0);
+                published.len() as f64 / weeks
+            }
+            _ => 0.0,
+        };
+
+        FeedCadence {
+            feed_id,
+            total_articles: published.len() as u32,
+            first_published: published.first().copied(),
+            last_published: published.last().copied(),
+            posts_per_week,
+            average_gap_hours,
+            median_gap_hours,
+            posts_by_weekday,
+        }
+    }
+
+    /// 获取连续阅读天数（按本地日期计算）
+    pub async fn get_reading_streak(db: &SqlitePool) -> AppResult<ReadingStreak> {
+        let rows = sqlx::query("SELECT read_at FROM rss_articles WHERE read_at IS NOT NULL")
+            .fetch_all(db)
+            .await?;
+
+        let dates: Vec<NaiveDate> = rows
+            .iter()
+            .filter_map(|row| {
+                let read_at_str: String = row.get("read_at");
+                DateTime::parse_from_rfc3339(&read_at_str)
+                    .ok()
+                    .map(|dt| dt.with_timezone(&Local).date_naive())
+            })
+            .collect();
+
+        Ok(Self::compute_reading_streak(dates, Local::now().date_naive()))
+    }
+
+    /// 根据阅读日期计算当前和最长连续天数
+    pub fn compute_reading_streak(dates: Vec<NaiveDate>, today: NaiveDate) -> ReadingStreak {
+        let days: std::collections::BTreeSet<NaiveDate> = dates.into_iter().collect();
+
+        let mut longest_days = 0;
+        let mut run = 0;
+        let mut previous: Option<NaiveDate> = None;
+        for day in &days {
+            run = match previous {
+                Some(prev) if prev.succ_opt() == Some(*day) => run + 1,
+                _ => 1,
+            };
+            longest_days = longest_days.max(run);
+            previous = Some(*day);
+        }
+
+        // 今天还没读时，截止到昨天的连续天数仍然有效
+        let last_read_date = days.iter().next_back().copied();
+        let current_days = match last_read_date {
+            Some(last) if last == today || last.succ_opt() == Some(today) => run,
+            _ => 0,
+        };
+
+        ReadingStreak {
+            current_days,
+            longest_days,
+            last_read_date,
+        }
+    }
+
+    /// 按星期和小时统计阅读次数（按本地时间计算）
+    pub async fn get_reading_by_weekday(db: &SqlitePool) -> AppResult<ReadingHeatmap> {
+        let rows = sqlx::query("SELECT read_at FROM rss_articles WHERE read_at IS NOT NULL")
+            .fetch_all(db)
+            .await?;
+
+        let times: Vec<NaiveDateTime> = rows
+            .iter()
+            .filter_map(|row| {
+                let read_at_str: String = row.get("read_at");
+                DateTime::parse_from_rfc3339(&read_at_str)
+                    .ok()
+                    .map(|dt| dt.with_timezone(&Local).naive_local())
+            })
+            .collect();
+
+        Ok(Self::compute_reading_heatmap(&times))
+    }
+
+    /// 根据阅读时间计算星期×小时的阅读次数矩阵
+    pub fn compute_reading_heatmap(times: &[NaiveDateTime]) -> ReadingHeatmap {
+        let mut counts = [[0u32; 24]; 7];
+        for time in times {
+            counts[time.weekday().num_days_from_monday() as usize][time.hour() as usize] += 1;
+        }
+
+        ReadingHeatmap {
+            counts,
+            total: times.len() as u32,
+        }
+    }
+
+    /// 抓取RSS源和原文网页共用的客户端，超时和User-Agent来自网络设置
+    pub(crate) fn build_http_client() -> AppResult<reqwest::Client> {
+        Ok(network::client_builder().build()?)
+    }
+
+    /// 从网页中读取<link rel="canonical">地址，相对地址按页面地址解析
+    pub fn find_canonical_url(html: &str, page_url: &Url) -> Option<String> {
+        Self::find_link_rel(html, page_url, "canonical")
+    }
+
+    /// 从网页中读取<link rel="icon">图标地址，相对地址按页面地址解析
+    /// rel按空格分隔的关键字匹配，"shortcut icon"会命中，"apple-touch-icon"不会，
+    /// 因此页面中先出现的apple-touch-icon不会盖过普通图标
+    pub fn find_icon_link(html: &str, page_url: &Url) -> Option<String> {
+        Self::find_link_rel(html, page_url, "icon")
+    }
+
+    /// 返回第一个rel中包含指定关键字的<link>的地址，只接受http和https地址
+    fn find_link_rel(html: &str, page_url: &Url, rel: &str) -> Option<String> {
+        let document = Html::parse_document(html);
+        let selector = Selector::parse("link[rel][href]").ok()?;
+
+        document
+            .select(&selector)
+            .find(|element| {
+                element
+                    .value()
+                    .attr("rel")
+                    .is_some_and(|value| value.split_whitespace().any(|r| r.eq_ignore_ascii_case(rel)))
+            })
+            .and_then(|element| element.value().attr("href"))
+            .and_then(|href| page_url.join(href.trim()).ok())
+            .filter(|url| url.scheme() == "http" || url.scheme() == "https")
+            .map(|url| url.to_string())
+    }
+
+    /// 抓取网站图标，先尝试/favicon.ico，再解析首页的<link rel="icon">
+    /// 返回图标地址和base64编码的图标数据，网站没有图标时返回None
+    pub async fn fetch_favicon(website_url: &str) -> Option<(String, String)> {
+        let site_url = Url::parse(website_url).ok()?;
+        if site_url.scheme() != "http" && site_url.scheme() != "https" {
+            return None;
+        }
+        let client = Self::build_http_client().ok()?;
+
+        if let Ok(ico_url) = site_url.join("/favicon.ico") {
+            if let Some(data) = Self::download_favicon(&client, ico_url.as_str()).await {
+                return Some((ico_url.to_string(), data));
+            }
+        }
+
+        let response = client.get(site_url.clone()).send().await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        let final_url = response.url().clone();
+        let html = response.text().await.ok()?;
+        let icon_url = Self::find_icon_link(&html, &final_url)?;
+        let data = Self::download_favicon(&client, &icon_url).await?;
+        Some((icon_url, data))
+    }
+
+    /// 下载图标并编码为base64，错误页面、空内容和过大的文件视为没有图标
+    async fn download_favicon(client: &reqwest::Client, url: &str) -> Option<String> {
+        use base64::Engine;
+
+        let response = client.get(url).send().await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        // 部分网站对不存在的路径返回200的HTML页面
+        let is_html = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|ct| ct.to_ascii_lowercase().contains("text/html"));
+        if is_html {
+            return None;
+        }
+        let bytes = response.bytes().await.ok()?;
+        if bytes.is_empty() || bytes.len() > MAX_FAVICON_BYTES {
+            return None;
+        }
+        Some(base64::engine::general_purpose::STANDARD.encode(&bytes))
+    }
+
+    /// 重新抓取RSS源的网站图标，没有网站地址时使用RSS源地址所在的站点
+    /// 上次没有抓到图标时一天内不再重试；抓取失败时保留已缓存的图标
+    pub async fn refresh_favicon(db: &SqlitePool, feed_id: &str) -> AppResult<RssFeed> {
+        let row = sqlx::query("SELECT url, website_url, favicon_data, favicon_checked_at FROM rss_feeds WHERE id = ?")
+            .bind(feed_id)
+            .fetch_optional(db)
+            .await?
+            .ok_or_else(|| AppError::feed_not_found(feed_id))?;
+        let favicon_data: Option<String> = row.get("favicon_data");
+        let checked_at: Option<String> = row.get("favicon_checked_at");
+        let recently_checked = checked_at
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .is_some_and(|checked_at| {
+                Utc::now().signed_duration_since(checked_at) < chrono::Duration::hours(FAVICON_RETRY_COOLDOWN_HOURS)
+            });
+        if favicon_data.is_none() && recently_checked {
+            return Self::get_feed(db, feed_id).await;
+        }
+
+        let site = row
+            .get::<Option<String>, _>("website_url")
+            .filter(|url| !url.trim().is_empty())
+            .unwrap_or_else(|| row.get("url"));
+        let now = Utc::now().to_rfc3339();
+        match Self::fetch_favicon(&site).await {
+            Some((favicon_url, data)) => {
+                sqlx::query("UPDATE rss_feeds SET favicon_url = ?, favicon_data = ?, favicon_checked_at = ? WHERE id = ?")
+                    .bind(&favicon_url)
+                    .bind(&data)
+                    .bind(&now)
+                    .bind(feed_id)
+                    .execute(db)
+                    .await?;
+            }
+            None => {
+                info!("RSS源 {} 的网站 {} 没有可用的图标", feed_id, site);
+                sqlx::query("UPDATE rss_feeds SET favicon_checked_at = ? WHERE id = ?")
+                    .bind(&now)
+                    .bind(feed_id)
+                    .execute(db)
+                    .await?;
+            }
+        }
+
+        Self::get_feed(db, feed_id).await
+    }
+
+    /// 获取RSS源缓存的base64编码的网站图标，没有图标时返回None
+    pub async fn get_feed_favicon(db: &SqlitePool, feed_id: &str) -> AppResult<Option<String>> {
+        let favicon_data = sqlx::query_scalar("SELECT favicon_data FROM rss_feeds WHERE id = ?")
+            .bind(feed_id)
+            .fetch_optional(db)
+            .await?
+            .ok_or_else(|| AppError::feed_not_found(feed_id))?;
+        Ok(favicon_data)
+    }
+
+    /// 添加RSS源时在后台抓取网站图标，不阻塞添加，失败也不影响添加
+    fn populate_favicon(db: &SqlitePool, feed_id: &str) {
+        let db = db.clone();
+        let feed_id = feed_id.to_string();
+        tokio::spawn(async move {
+            if let Err(e) = Self::refresh_favicon(&db, &feed_id).await {
+                warn!("抓取RSS源 {} 的网站图标失败: {}", feed_id, e);
+            }
+        });
+    }
+
+    /// 从网页中读取og:image题图地址，相对地址按页面地址解析
+    pub fn find_og_image(html: &str, page_url: &Url) -> Option<String> {
+        let document = Html::parse_document(html);
+        let selector = Selector::parse("meta[content]").ok()?;
+
+        document
+            .select(&selector)
+            .find(|element| {
+                let value = element.value();
+                value
+                    .attr("property")
+                    .or_else(|| value.attr("name"))
+                    .is_some_and(|name| name.eq_ignore_ascii_case("og:image"))
+            })
+            .and_then(|element| element.value().attr("content"))
+            .and_then(|content| page_url.join(content.trim()).ok())
+            .filter(|url| url.scheme() == "http" || url.scheme() == "https")
+            .map(|url| url.to_string())
+    }
+
+    /// 抓取文章网页并将链接更新为canonical地址，没有canonical标签时保留原链接
+    /// 返回更新后的链接
+    pub async fn canonicalize_article_link(db: &SqlitePool, article_id: String) -> AppResult<String> {
+        let row = sqlx::query("SELECT link FROM rss_articles WHERE id = ?")
+            .bind(&article_id)
+            .fetch_optional(db)
+            .await?
+            .ok_or_else(|| AppError::article_not_found(&article_id))?;
+        let link: String = row
+            .get::<Option<String>, _>("link")
+            .ok_or_else(|| AppError::validation("文章没有链接"))?;
+        let page_url = Url::parse(&link).map_err(|_| AppError::invalid_rss_url(&link))?;
+
+        let response = Self::build_http_client()?
+            .get(page_url.clone())
+            .send()
+            .await
+            .map_err(|e| AppError::request_failed(&link, &e))?;
+        if !response.status().is_success() {
+            return Err(AppError::http_status(&link, response.status().as_u16()));
+        }
+        // 跟随重定向后的最终地址作为解析相对地址的基准
+        let final_url = response.url().clone();
+        let html = response.text().await?;
+
+        let canonical = match Self::find_canonical_url(&html, &final_url) {
+            Some(canonical) if canonical != link => canonical,
+            _ => return Ok(link),
+        };
+
+        sqlx::query("UPDATE rss_articles SET link = ?, link_key = ? WHERE id = ?")
+            .bind(&canonical)
+            .bind(utils::normalize_article_link(&canonical))
+            .bind(&article_id)
+            .execute(db)
+            .await?;
+
+        info!("文章 {} 的链接已更新为canonical地址: {}", article_id, canonical);
+        Ok(canonical)
+    }
+
+    /// 提取HTML内容的主要文本
+    pub async fn extract_article_content(url: &str) -> Option<String> {
+        Self::extract_article_content_detailed(url, false)
+            .await
+            .map(|extracted| extracted.content)
+    }
+
+    /// 提取全文并返回使用的提取方式
+    /// 静态提取的正文少于MIN_STATIC_EXTRACT_CHARS个字符且配置了渲染服务时，改用渲染服务返回的页面提取
+    pub async fn extract_article_content_detailed(url: &str, requires_render: bool) -> Option<ExtractedContent> {
+        let extracted = match Self::fetch_article_page(url, requires_render).await {
+            Some(html) => Self::extract_from_html(&html, url),
+            None => None,
+        };
+        Self::with_render_fallback(url, extracted).await
+    }
+
+    /// 静态提取结果过短时尝试渲染服务，渲染结果不比静态结果长时保留静态结果
+    async fn with_render_fallback(url: &str, extracted: Option<String>) -> Option<ExtractedContent> {
+        let static_chars = extracted.as_deref().map_or(0, utils::text_char_count);
+        let static_result = extracted.map(|content| ExtractedContent {
+            content,
+            source: ExtractionSource::Static,
+        });
+        if static_chars >= MIN_STATIC_EXTRACT_CHARS || render::service_url().is_none() {
+            return static_result;
+        }
+
+        let html = match render::render_via_service(url).await {
+            Ok(html) => html,
+            Err(e) => {
+                warn!("渲染服务获取页面失败，使用静态提取结果: {}", e);
+                return static_result;
+            }
+        };
+        match Self::extract_from_html(&html, url) {
+            Some(content) if utils::text_char_count(&content) > static_chars => {
+                info!("静态提取内容过短（{} 字符），已使用渲染服务的结果: {}", static_chars, url);
+                Some(ExtractedContent {
+                    content,
+                    source: ExtractionSource::Rendered,
+                })
+            }
+            _ => static_result,
+        }
+    }
+
+    /// 静态抓取网页HTML
+    async fn fetch_page_html(url: &str) -> Option<String> {
+        // 创建带有用户代理的客户端
+        let client = Self::build_http_client().ok()?;
+
+        // 获取网页内容
+        let response = match Self::fetch_with_retry(client.get(url)).await {
+            Ok(resp) => resp,
+            Err(e) => {
+                warn!("请求失败: {}", e);
+                return None;
+            }
+        };
+
+        // 错误页面的内容不是文章正文
+        if !response.status().is_success() {
+            warn!("请求返回HTTP状态: {}", response.status());
+            return None;
+        }
+
+        let html_content = match response.text().await {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("读取响应内容失败: {}", e);
+                return None;
+            }
+        };
+
+        debug!("获取到HTML内容，长度: {}", html_content.len());
+
+        Some(html_content)
+    }
+
+    /// 获取文章网页HTML，requires_render的源先用webview渲染页面，渲染失败时退回静态抓取
+    async fn fetch_article_page(url: &str, requires_render: bool) -> Option<String> {
+        if requires_render && render::is_available() {
+            match render::render_page(url).await {
+                Ok(html) => return Some(html),
+                Err(e) => warn!("webview渲染失败，改用静态抓取: {}", e),
+            }
+        }
+        debug!("开始提取文章内容: {}", url);
+        Self::fetch_page_html(url).await
+    }
+
+    /// 提取全文，requires_render的源先用webview渲染页面，渲染失败时退回静态抓取
+    pub async fn extract_article_content_for_feed(url: &str, requires_render: bool) -> Option<String> {
+        Self::extract_article_content_detailed(url, requires_render)
+            .await
+            .map(|extracted| extracted.content)
+    }
+
+    /// 从网页HTML中提取正文
+    /// 网址匹配站点规则时先按规则提取，失败后依次尝试readability、常见选择器和所有段落
+    pub(crate) fn extract_from_html(html_content: &str, url: &str) -> Option<String> {
+        if let Some(rule) = site_rules::rule_for_url(url) {
+            let extracted = match rule.selector.as_deref() {
+                Some(selector) => Self::extract_by_rule_selector(html_content, url, selector),
+                None => Self::extract_with_strategy(html_content, url, rule.strategy),
+            };
+            if extracted.is_some() {
+                return extracted;
+            }
+            debug!("站点规则 {} 提取失败，改用通用提取方式", rule.domain);
+        }
+
+        let strategies = [
+            ExtractionStrategy::Readability,
+            ExtractionStrategy::Selectors,
+            ExtractionStrategy::Paragraphs,
+        ];
+        let extracted = strategies
+            .into_iter()
+            .find_map(|strategy| Self::extract_with_strategy(html_content, url, strategy));
+        if extracted.is_none() {
+            debug!("所有提取方法都失败了");
+        }
+        extracted
+    }
+
+    /// 使用指定方式提取正文，选择器方式使用常见的文章内容选择器
+    fn extract_with_strategy(html_content: &str, url: &str, strategy: ExtractionStrategy) -> Option<String> {
+        match strategy {
+            ExtractionStrategy::Readability => Self::extract_readability(html_content, url),
+            ExtractionStrategy::Selectors => Self::extract_by_selectors(&Html::parse_document(html_content)),
+            ExtractionStrategy::Paragraphs => Self::extract_paragraphs(&Html::parse_document(html_content)),
+        }
+    }
+
+    /// 使用readability提取主要内容
+    fn extract_readability(html_content: &str, url: &str) -> Option<String> {
+        let parsed_url = url.parse::<url::Url>().ok()?;
+        match extractor::extract(&mut html_content.as_bytes(), &parsed_url) {
+            Ok(product) if !product.content.trim().is_empty() => {
+                debug!("Readability提取成功，内容长度: {}", product.content.len());
+                Some(utils::resolve_relative_urls(&product.content, &parsed_url))
+            }
+            Ok(_) => None,
+            Err(e) => {
+                debug!("Readability提取失败: {}", e);
+                None
+            }
+        }
+    }
+
+    /// 按站点规则的CSS选择器提取，保留匹配元素的HTML
+    fn extract_by_rule_selector(html_content: &str, url: &str, selector_str: &str) -> Option<String> {
+        let selector = Selector::parse(selector_str).ok()?;
+        let document = Html::parse_document(html_content);
+        let content = document
+            .select(&selector)
+            .filter(|element| !element.text().collect::<String>().trim().is_empty())
+            .map(|element| element.html())
+            .collect::<Vec<_>>()
+            .join("\n");
+        if content.is_empty() {
+            return None;
+        }
+        debug!("使用站点规则选择器 '{}' 提取成功，内容长度: {}", selector_str, content.len());
+        match url.parse::<url::Url>() {
+            Ok(base) => Some(utils::resolve_relative_urls(&content, &base)),
+            Err(_) => Some(content),
+        }
+    }
+
+    /// 尝试常见的文章内容选择器
+    fn extract_by_selectors(document: &Html) -> Option<String> {
+        for selector_str in CONTENT_SELECTORS {
+            if let Ok(selector) = Selector::parse(selector_str) {
+                if let Some(element) = document.select(&selector).next() {
+                    // 宽泛的选择器可能包含导航和侧栏，去除后再判断长度
+                    let text = utils::clean_element_text(element);
+                    if text.len() > 100 {
+                        // 确保内容足够长
+                        debug!("使用选择器 '{}' 提取成功，内容长度: {}", selector_str, text.len());
+                        return Some(text);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// 提取所有p标签的内容，跳过导航、页脚等非正文区域中的段落
+    pub(crate) fn extract_paragraphs(document: &Html) -> Option<String> {
+        let p_selector = Selector::parse("p").ok()?;
+        let paragraphs: Vec<String> = document
+            .select(&p_selector)
+            .filter(|p| !utils::in_boilerplate(*p))
+            .map(utils::clean_element_text)
+            .filter(|text| text.len() > 20)
+            .collect();
+
+        if paragraphs.is_empty() {
+            return None;
+        }
+        let content = paragraphs.join("\n\n");
+        debug!("使用p标签提取成功，内容长度: {}", content.len());
+        Some(content)
+    }
+
+    /// 获取单篇文章详细内容
+    pub async fn get_article_content(db: &SqlitePool, article_id: String) -> AppResult<RssArticle> {
+        let mut article = Self::load_article_content(db, article_id, false).await?;
+
+        // 开启了打开即已读时，同时标记已读并记录阅读时间（已有则保留）
+        if !article.is_read && settings::get_bool(db, settings::MARK_READ_ON_OPEN, false).await? {
+            let now = Local::now().with_timezone(&Utc);
+            sqlx::query("UPDATE rss_articles SET is_read = 1, read_at = COALESCE(read_at, ?) WHERE id = ?")
+                .bind(now.to_rfc3339())
+                .bind(&article.id)
+                .execute(db)
+                .await?;
+            article.is_read = true;
+            article.read_at = article.read_at.or(Some(now));
+        }
+
+        Ok(article)
+    }
+
+    /// 立即从原文重新提取全文，忽略提取失败后的冷却时间，已有内容时提取成功才会替换
+    pub async fn force_extract_content(db: &SqlitePool, article_id: String) -> AppResult<RssArticle> {
+        Self::load_article_content(db, article_id, true).await
+    }
+
+    /// 读取文章详情，内容为空时从原文提取全文（force时总是提取）
+    /// 提取失败会记录时间，冷却期内不再自动重试，避免反复请求失效的链接
+    async fn load_article_content(db: &SqlitePool, article_id: String, force: bool) -> AppResult<RssArticle> {
+        let row = sqlx::query(&format!(
+            "SELECT {}, extraction_failed_at FROM rss_articles WHERE id = ?",
+            ARTICLE_COLUMNS
+        ))
+        .bind(&article_id)
+        .fetch_one(db)
+        .await
+        .map_err(|_| AppError::article_not_found(&article_id))?;
+
+        let mut article = Self::article_from_row(&row);
+
+        // 早期保存的内容未经清理，返回前统一清理
+        article.content = article.content.map(|content| sanitize::sanitize_html(&content));
+        article.description = article.description.map(|description| sanitize::sanitize_html(&description));
+
+        info!("link is {:?}", article.link);
+
+        let extraction_failed_at: Option<String> = row.get("extraction_failed_at");
+        let in_cooldown = extraction_failed_at
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .is_some_and(|failed_at| {
+                Utc::now().signed_duration_since(failed_at) < chrono::Duration::hours(EXTRACTION_RETRY_COOLDOWN_HOURS)
+            });
+        let content_missing = article.content.as_ref().map_or(true, |c| c.trim().is_empty());
+
+        // 如果content为空且有链接，尝试获取完整内容
+        if (force || (content_missing && !in_cooldown)) && article.link.is_some() {
+            let feed_row = sqlx::query("SELECT requires_render, max_content_bytes FROM rss_feeds WHERE id = ?")
+                .bind(&article.feed_id)
+                .fetch_optional(db)
+                .await?;
+            let requires_render = feed_row
+                .as_ref()
+                .is_some_and(|row| row.get::<bool, _>("requires_render"));
+            let max_content_bytes = Self::content_limit(
+                db,
+                feed_row
+                    .as_ref()
+                    .and_then(|row| row.get::<Option<i64>, _>("max_content_bytes"))
+                    .map(|n| n as u32),
+            )
+            .await?;
+            let extracted = Self::extract_article_content_for_feed(article.link.as_ref().unwrap(), requires_render).await;
+            if extracted.is_none() {
+                warn!("文章 {} 的全文提取失败，{} 小时内不再自动重试", article_id, EXTRACTION_RETRY_COOLDOWN_HOURS);
+                sqlx::query("UPDATE rss_articles SET extraction_failed_at = ? WHERE id = ?")
+                    .bind(Utc::now().to_rfc3339())
+                    .bind(&article_id)
+                    .execute(db)
+                    .await?;
+            }
+            if let Some(extracted_content) = extracted {
+                let keep_raw = settings::get_bool(db, settings::KEEP_RAW_CONTENT, false).await?;
+                // 原始内容同样受大小上限限制
+                let raw_content = if keep_raw {
+                    Self::limit_content(Some(extracted_content.clone()), max_content_bytes)
+                } else {
+                    None
+                };
+                article.content = Self::limit_content(
+                    Some(sanitize::sanitize_html(&extracted_content)),
+                    max_content_bytes,
+                );
+
+                // 将提取的内容保存到数据库中，避免重复提取
+                let stored = compression::encode(article.content.clone(), compression::is_enabled(db).await?)?;
+                article.word_count = article.content.as_deref().map(utils::count_words);
+                let mut conn = db.acquire().await?;
+                let saved = sqlx::query(
+                    "UPDATE rss_articles SET content = ?, content_compressed = ?, content_compression = ?, content_raw = ?, word_count = ?, extraction_failed_at = NULL WHERE id = ?",
+                )
+                .bind(&stored.content)
+                .bind(&stored.compressed)
+                .bind(stored.compression)
+                .bind(&raw_content)
+                .bind(article.word_count)
+                .bind(&article_id)
+                .execute(&mut *conn)
+                .await;
+                if let (Ok(_), Some(plain)) = (saved, &stored.plain) {
+                    let _ = SearchService::index_compressed_content(&mut conn, &article_id, plain).await;
+                }
+            }
+        }
+
+        Ok(article)
+    }
+
+    /// 获取文章未经处理的原始内容（调试用，需先开启保留原始内容选项）
+    /// 开启前保存的文章没有原始内容，返回None
+    pub async fn get_article_raw_content(
+        db: &SqlitePool,
+        article_id: String,
+    ) -> AppResult<Option<String>> {
+        if !settings::get_bool(db, settings::KEEP_RAW_CONTENT, false).await? {
+            return Err(AppError::validation("查看原始内容需要先开启保留原始内容的调试选项"));
+        }
+
+        let row = sqlx::query("SELECT content_raw FROM rss_articles WHERE id = ?")
+            .bind(&article_id)
+            .fetch_optional(db)
+            .await?
+            .ok_or_else(|| AppError::article_not_found(&article_id))?;
+
+        Ok(row.get("content_raw"))
+    }
+
+    /// 设置文章的阅读模式偏好，传入None时恢复使用全局阅读主题
+    pub async fn set_article_reader_prefs(
+        db: &SqlitePool,
+        article_id: String,
+        prefs: Option<ReaderPrefs>,
+    ) -> AppResult<String> {
+        if let Some(font_size) = prefs.as_ref().and_then(|p| p.font_size) {
+            if !(8..=72).contains(&font_size) {
+                return Err(AppError::validation(format!(
+                    "字体大小必须在8到72之间: {}",
+                    font_size
+                )));
+            }
+        }
+
+        let prefs_json = prefs.as_ref().map(serde_json::to_string).transpose()?;
+
+        let result = sqlx::query("UPDATE rss_articles SET reader_prefs = ? WHERE id = ?")
+            .bind(&prefs_json)
+            .bind(&article_id)
+            .execute(db)
+            .await?;
+
+        if result.rows_affected() > 0 {
+            Ok("Reader preferences updated successfully".to_string())
+        } else {
+            Err(AppError::article_not_found(&article_id))
+        }
+    }
+
+    /// 更新文章状态
+    pub async fn update_article(
+        db: &SqlitePool,
+        request: UpdateArticleRequest,
+    ) -> AppResult<String> {
+        // 简化的更新方法
+        if let Some(is_read) = request.is_read {
+            // 标记已读时记录阅读时间（已有则保留），标记未读时清空
+            let now = Local::now().with_timezone(&Utc);
+            sqlx::query(
+                "UPDATE rss_articles SET is_read = ?, read_at = CASE WHEN ? THEN COALESCE(read_at, ?) ELSE NULL END WHERE id = ?",
+            )
+            .bind(is_read)
+            .bind(is_read)
+            .bind(now.to_rfc3339())
+            .bind(&request.id)
+            .execute(db)
+            .await?;
+        }
+
+        if let Some(is_starred) = request.is_starred {
+            sqlx::query("UPDATE rss_articles SET is_starred = ? WHERE id = ?")
+                .bind(is_starred)
+                .bind(&request.id)
+                .execute(db)
+                .await?;
+        }
+
+        Ok("Article updated successfully".to_string())
+    }
+
+    /// 批量更新多篇文章的已读和收藏状态，未设置的状态保持不变，返回被更新的文章数量
+    pub async fn update_articles_bulk(
+        db: &SqlitePool,
+        ids: Vec<String>,
+        is_read: Option<bool>,
+        is_starred: Option<bool>,
+    ) -> AppResult<u64> {
+        if ids.is_empty() || (is_read.is_none() && is_starred.is_none()) {
+            return Ok(0);
+        }
+
+        // 获取当前本地时间并转换为UTC时间
+        let now = Local::now().with_timezone(&Utc);
+        let mut updated = 0;
+        let mut tx = db.begin().await?;
+        for chunk in ids.chunks(BULK_UPDATE_CHUNK_SIZE) {
+            // 与update_article一致：标记已读时记录阅读时间（已有则保留），标记未读时清空
+            let sql = format!(
+                "UPDATE rss_articles SET
+                     is_read = COALESCE(?, is_read),
+                     read_at = CASE WHEN ? IS NULL THEN read_at WHEN ? THEN COALESCE(read_at, ?) ELSE NULL END,
+                     is_starred = COALESCE(?, is_starred)
+                 WHERE id IN ({})",
+                vec!["?"; chunk.len()].join(", ")
+            );
+            let mut query = sqlx::query(&sql)
+                .bind(is_read)
+                .bind(is_read)
+                .bind(is_read)
+                .bind(now.to_rfc3339())
+                .bind(is_starred);
+            for id in chunk {
+                query = query.bind(id);
+            }
+            updated += query.execute(&mut *tx).await?.rows_affected();
+        }
+        tx.commit().await?;
+
+        Ok(updated)
+    }
+
+    /// 开启或关闭跨RSS源合并重复文章，开启时为旧文章补全规范化链接
+    pub async fn set_dedup_across_feeds(db: &SqlitePool, enabled: bool) -> AppResult<()> {
+        settings::set_bool(db, settings::DEDUP_ACROSS_FEEDS, enabled).await?;
+        if enabled {
+            Self::backfill_link_keys(db).await?;
+        }
+        Ok(())
+    }
+
+    /// 为缺少规范化链接的文章补全link_key，返回补全的文章数
+    /// 规范化规则见utils::normalize_article_link，无法在SQL中实现，因此由应用分批计算
+    pub async fn backfill_link_keys(db: &SqlitePool) -> AppResult<u64> {
+        let mut filled = 0;
+        loop {
+            let rows = sqlx::query(
+                "SELECT id, link FROM rss_articles WHERE link IS NOT NULL AND link_key IS NULL LIMIT ?",
+            )
+            .bind(METRICS_BATCH_SIZE)
+            .fetch_all(db)
+            .await?;
+            if rows.is_empty() {
+                break;
+            }
+
+            let mut tx = db.begin().await?;
+            for row in &rows {
+                sqlx::query("UPDATE rss_articles SET link_key = ? WHERE id = ?")
+                    .bind(utils::normalize_article_link(&row.get::<String, _>("link")))
+                    .bind(row.get::<String, _>("id"))
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            tx.commit().await?;
+            filled += rows.len() as u64;
+        }
+
+        if filled > 0 {
+            info!("已为 {} 篇文章补全规范化链接", filled);
+        }
+        Ok(filled)
+    }
+
+    /// 合并同一RSS源中规范化链接相同的重复文章，保留最早保存的一篇，返回删除的文章数
+    /// 已读、收藏、阅读时间、阅读偏好和标签等用户状态先合并到保留的文章，再删除重复文章
+    pub async fn dedupe_articles_by_link(db: &SqlitePool) -> AppResult<u64> {
+        let groups: Vec<(String, String)> = sqlx::query_as(
+            "SELECT feed_id, link_key FROM rss_articles WHERE link_key IS NOT NULL
+             GROUP BY feed_id, link_key HAVING COUNT(*) > 1",
+        )
+        .fetch_all(db)
+        .await?;
+
+        let mut removed = 0;
+        for (feed_id, link_key) in groups {
+            let mut tx = db.begin().await?;
+            let ids: Vec<String> = sqlx::query_scalar(
+                "SELECT id FROM rss_articles WHERE feed_id = ? AND link_key = ? ORDER BY created_at, rowid",
+            )
+            .bind(&feed_id)
+            .bind(&link_key)
+            .fetch_all(&mut *tx)
+            .await?;
+            let Some((keep_id, duplicate_ids)) = ids.split_first() else {
+                continue;
+            };
+
+            for duplicate_id in duplicate_ids {
+                Self::merge_article_state(&mut tx, duplicate_id, keep_id).await?;
+                // 指向被删除文章的跨源重复标记改为指向保留的文章
+                sqlx::query("UPDATE rss_articles SET duplicate_of = ? WHERE duplicate_of = ?")
+                    .bind(keep_id)
+                    .bind(duplicate_id)
+                    .execute(&mut *tx)
+                    .await?;
+                sqlx::query("DELETE FROM rss_articles WHERE id = ?")
+                    .bind(duplicate_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            tx.commit().await?;
+            removed += duplicate_ids.len() as u64;
+        }
+
+        if removed > 0 {
+            info!("已合并 {} 篇链接重复的文章", removed);
+        }
+        Ok(removed)
+    }
+
+    /// 合并所有RSS源中链接重复的文章，dry_run时只返回将被删除的重复文章
+    pub async fn deduplicate_all_articles(db: &SqlitePool, dry_run: bool) -> AppResult<CleanupReport> {
+        // 与dedupe_articles_by_link相同，每组保留最早保存的一篇
+        let rows = sqlx::query(
+            "SELECT id, title FROM (
+                 SELECT id, title, created_at, rowid AS row_id,
+                        ROW_NUMBER() OVER (PARTITION BY feed_id, link_key ORDER BY created_at, rowid) AS position
+                 FROM rss_articles WHERE link_key IS NOT NULL
+             )
+             WHERE position > 1
+             ORDER BY created_at, row_id",
+        )
+        .fetch_all(db)
+        .await?;
+        let targets: Vec<(String, String)> = rows.iter().map(|row| (row.get("id"), row.get("title"))).collect();
+
+        let mut report = cleanup::report(&targets, dry_run);
+        if !dry_run && !targets.is_empty() {
+            report.count = Self::dedupe_articles_by_link(db).await? as u32;
+        }
+        Ok(report)
+    }
+
+    /// 获取单个RSS源的未读文章数，用于角标显示，读取由触发器维护的缓存值
+    pub async fn get_feed_unread_count(db: &SqlitePool, feed_id: &str) -> AppResult<i64> {
+        let count = sqlx::query_scalar("SELECT unread_count FROM rss_feeds WHERE id = ?")
+            .bind(feed_id)
+            .fetch_optional(db)
+            .await?
+            .ok_or_else(|| AppError::feed_not_found(feed_id))?;
+        Ok(count)
+    }
+
+    /// 获取全部未读文章数，用于应用角标显示，汇总各RSS源缓存的未读数
+    pub async fn get_total_unread_count(db: &SqlitePool) -> AppResult<i64> {
+        let count = sqlx::query_scalar("SELECT COALESCE(SUM(unread_count), 0) FROM rss_feeds")
+            .fetch_one(db)
+            .await?;
+        Ok(count)
+    }
+
+    /// 按文章表重新统计每个RSS源缓存的未读数，返回被修正的RSS源数量
+    /// 未读数平时由数据库触发器增量维护，只在出现偏差时需要调用
+    pub async fn recompute_unread_counts(db: &SqlitePool) -> AppResult<u64> {
+        let result = sqlx::query(
+            "UPDATE rss_feeds SET unread_count = counts.unread
+             FROM (
+                 SELECT f.id, COUNT(a.id) AS unread
+                 FROM rss_feeds f
+                 LEFT JOIN rss_articles a ON a.feed_id = f.id AND a.is_read = 0
+                 GROUP BY f.id
+             ) AS counts
+             WHERE rss_feeds.id = counts.id AND rss_feeds.unread_count != counts.unread",
+        )
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() > 0 {
+            warn!("{} 个RSS源的未读数缓存与实际不符，已修正", result.rows_affected());
+        }
+        Ok(result.rows_affected())
+    }
+
+    /// 将指定RSS源（未指定时为全部RSS源）的未读文章标记为已读，返回被标记的文章数量
+    pub async fn mark_all_read(db: &SqlitePool, feed_id: Option<String>) -> AppResult<u64> {
+        if let Some(feed_id) = &feed_id {
+            Self::get_feed(db, feed_id).await?;
+        }
+
+        // 获取当前本地时间并转换为UTC时间
+        let now = Local::now().with_timezone(&Utc);
+        let mut tx = db.begin().await?;
+        let result = sqlx::query(
+            "UPDATE rss_articles SET is_read = 1, read_at = COALESCE(read_at, ?)
+             WHERE is_read = 0 AND (? IS NULL OR feed_id = ?)",
+        )
+        .bind(now.to_rfc3339())
+        .bind(&feed_id)
+        .bind(&feed_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// 将一篇文章的阅读状态（已读、收藏、阅读时间、阅读偏好、标签）合并到另一篇文章
+    /// 用于合并重复文章时保留用户的整理结果
+    pub async fn transfer_article_state(
+        db: &SqlitePool,
+        from_id: String,
+        to_id: String,
+    ) -> AppResult<String> {
+        if from_id == to_id {
+            return Err(AppError::validation("源文章和目标文章不能相同"));
+        }
+
+        let mut tx = db.begin().await?;
+        Self::merge_article_state(&mut tx, &from_id, &to_id).await?;
+        tx.commit().await?;
+        Ok("Article state transferred successfully".to_string())
+    }
+
+    /// 在事务中把源文章的阅读状态合并到目标文章，不会覆盖目标文章已有的状态：
+    /// 任一篇已读或收藏即保留，阅读时间取较早的一个，阅读偏好优先保留目标文章的，标签取并集
+    async fn merge_article_state(
+        tx: &mut Transaction<'_, Sqlite>,
+        from_id: &str,
+        to_id: &str,
+    ) -> AppResult<()> {
+        sqlx::query("SELECT 1 FROM rss_articles WHERE id = ?")
+            .bind(from_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| AppError::article_not_found(from_id))?;
+
+        let result = sqlx::query(
+            "UPDATE rss_articles SET
+                is_read = MAX(rss_articles.is_read, f.is_read),
+                is_starred = MAX(rss_articles.is_starred, f.is_starred),
+                read_at = CASE
+                    WHEN rss_articles.read_at IS NULL OR f.read_at < rss_articles.read_at THEN COALESCE(f.read_at, rss_articles.read_at)
+                    ELSE rss_articles.read_at
+                END,
+                reader_prefs = COALESCE(rss_articles.reader_prefs, f.reader_prefs)
+             FROM (SELECT is_read, is_starred, read_at, reader_prefs FROM rss_articles WHERE id = ?) AS f
+             WHERE rss_articles.id = ?",
+        )
+        .bind(from_id)
+        .bind(to_id)
+        .execute(&mut **tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::article_not_found(to_id));
+        }
+
+        sqlx::query(
+            "INSERT OR IGNORE INTO article_tags (article_id, tag_id, created_at)
+             SELECT ?, tag_id, created_at FROM article_tags WHERE article_id = ?",
+        )
+        .bind(to_id)
+        .bind(from_id)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// 将文章移动到另一个RSS源，目标源中已有相同guid的文章时拒绝移动
+    pub async fn move_article(
+        db: &SqlitePool,
+        article_id: String,
+        target_feed_id: String,
+    ) -> AppResult<String> {
+        Self::get_feed(db, &target_feed_id).await?;
+
+        let mut tx = db.begin().await?;
+
+        let row = sqlx::query("SELECT feed_id, guid FROM rss_articles WHERE id = ?")
+            .bind(&article_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::article_not_found(&article_id))?;
+        let feed_id: String = row.get("feed_id");
+        let guid: Option<String> = row.get("guid");
+
+        if feed_id == target_feed_id {
+            return Ok("Article is already in the target feed".to_string());
+        }
+
+        let conflict = sqlx::query("SELECT id FROM rss_articles WHERE feed_id = ? AND guid = ?")
+            .bind(&target_feed_id)
+            .bind(&guid)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if let Some(conflict) = conflict {
+            return Err(AppError::validation(format!(
+                "目标RSS源中已存在相同guid的文章: {}",
+                conflict.get::<String, _>("id")
+            )));
+        }
+
+        sqlx::query("UPDATE rss_articles SET feed_id = ? WHERE id = ?")
+            .bind(&target_feed_id)
+            .bind(&article_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok("Article moved successfully".to_string())
+    }
+
+    /// 刷新RSS源（带频率限制，遵循ETag/Last-Modified缓存），进度通过rss-fetch-progress事件推送
+    pub async fn refresh_feed(db: &SqlitePool, app_handle: &AppHandle, feed_id: String) -> AppResult<String> {
+        let feed = Self::get_feed(db, &feed_id).await?;
+        let progress = FeedProgress::new(app_handle, &feed_id, &feed.title);
+        progress.emit(RssFetchStatus::Started, 0, 0, None);
+        let result = Self::try_refresh_feed(db, &feed_id, Some(&progress)).await;
+        progress.finish(&result);
+
+        match result? {
+            RefreshAttempt::TooFrequent(message) => Ok(message),
+            RefreshAttempt::Done(diff) if diff.not_modified => Ok("内容未更新，没有新文章。".to_string()),
+            RefreshAttempt::Done(diff) => Ok(format!(
+                "刷新成功！新增 {} 篇文章。",
+                diff.added.len()
+            )),
+        }
+    }
+
+    /// 按频率限制刷新RSS源（遵循ETag/Last-Modified缓存）
+    async fn try_refresh_feed(
+        db: &SqlitePool,
+        feed_id: &str,
+        progress: Option<&FeedProgress<'_>>,
+    ) -> AppResult<RefreshAttempt> {
+        // 获取RSS源信息，包括最后更新时间和缓存头
+        let row = sqlx::query(
+            "SELECT url, alternate_urls, last_updated, etag, last_modified FROM rss_feeds WHERE id = ?",
+        )
+        .bind(feed_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::feed_not_found(feed_id))?;
+
+        let url: String = row.get("url");
+        let alternate_urls = Self::parse_alternate_urls(row.get("alternate_urls"));
+        let last_updated_str: Option<String> = row.get("last_updated");
+        let etag: Option<String> = row.get("etag");
+        let last_modified: Option<String> = row.get("last_modified");
+
+        if let Some(message) = Self::check_refresh_interval(last_updated_str.as_deref()) {
+            return Ok(RefreshAttempt::TooFrequent(message));
+        }
+
+        let diff = Self::fetch_and_save_diff(
+            db,
+            feed_id,
+            &url,
+            &alternate_urls,
+            etag.as_deref(),
+            last_modified.as_deref(),
+            progress,
+        )
+        .await?;
+        Ok(RefreshAttempt::Done(diff))
+    }
+
+    /// 检查刷新间隔，防止频繁查询，距离上次刷新太近时返回提示信息
+    fn check_refresh_interval(last_updated_str: Option<&str>) -> Option<String> {
+        const MIN_REFRESH_INTERVAL_MINUTES: i64 = 5; // 最小刷新间隔5分钟
+
+        let last_updated = DateTime::parse_from_rfc3339(last_updated_str?).ok()?;
+        let last_updated_utc = last_updated.with_timezone(&Utc);
+        // 获取当前本地时间并转换为UTC时间
+        let now = Local::now().with_timezone(&Utc);
+        let duration_since_last_update = now.signed_duration_since(last_updated_utc);
+
+        if duration_since_last_update.num_minutes() < MIN_REFRESH_INTERVAL_MINUTES {
+            let remaining_minutes = MIN_REFRESH_INTERVAL_MINUTES - duration_since_last_update.num_minutes();
+            return Some(format!(
+                "刷新过于频繁，请等待 {} 分钟后再试。为了避免对RSS服务器造成过大负担，每个源最少需要间隔 {} 分钟才能刷新。",
+                remaining_minutes,
+                MIN_REFRESH_INTERVAL_MINUTES
+            ));
+        }
+        None
+    }
+
+    /// 刷新RSS源并返回本次新增、更新以及可能已从源中移除的文章
+    /// 与refresh_feed使用相同的频率限制，刷新过于频繁时返回错误
+    pub async fn refresh_feed_diff(db: &SqlitePool, feed_id: String) -> AppResult<RefreshDiff> {
+        match Self::try_refresh_feed(db, &feed_id, None).await? {
+            RefreshAttempt::TooFrequent(message) => Err(AppError::validation(message)),
+            RefreshAttempt::Done(diff) => Ok(diff),
+        }
+    }
+
+    /// 强制刷新RSS源：清除ETag/Last-Modified缓存并无条件重新抓取
+    pub async fn force_refresh_feed(db: &SqlitePool, feed_id: String) -> AppResult<String> {
+        let row = sqlx::query("SELECT url, alternate_urls FROM rss_feeds WHERE id = ?")
+            .bind(&feed_id)
+            .fetch_one(db)
+            .await
+            .map_err(|_| AppError::feed_not_found(&feed_id))?;
+        let url: String = row.get("url");
+        let alternate_urls = Self::parse_alternate_urls(row.get("alternate_urls"));
+
+        // 清除缓存头，确保下一次请求是无条件的
+        sqlx::query("UPDATE rss_feeds SET etag = NULL, last_modified = NULL WHERE id = ?")
+            .bind(&feed_id)
+            .execute(db)
+            .await?;
+
+        Self::fetch_and_save(db, &feed_id, &url, &alternate_urls, None, None).await
+    }
+
+    /// 解析数据库中存储的备用地址列表
+    fn parse_alternate_urls(value: Option<String>) -> Vec<String> {
+        value
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    /// 抓取RSS源并保存新文章，返回刷新结果描述
+    async fn fetch_and_save(
+        db: &SqlitePool,
+        feed_id: &str,
+        url: &str,
+        alternate_urls: &[String],
+        etag: Option<&str>,
+        last_modified: Option<&str>,
+    ) -> AppResult<String> {
+        let diff =
+            Self::fetch_and_save_diff(db, feed_id, url, alternate_urls, etag, last_modified, None).await?;
+        if diff.not_modified {
+            return Ok("内容未更新，没有新文章。".to_string());
+        }
+        Ok(format!(
+            "刷新成功！新增 {} 篇文章。",
+            diff.added.len()
+        ))
+    }
+
+    /// 抓取RSS源并保存新文章，记录本次刷新是否成功
+    async fn fetch_and_save_diff(
+        db: &SqlitePool,
+        feed_id: &str,
+        url: &str,
+        alternate_urls: &[String],
+        etag: Option<&str>,
+        last_modified: Option<&str>,
+        progress: Option<&FeedProgress<'_>>,
+    ) -> AppResult<RefreshDiff> {
+        let result =
+            Self::fetch_and_save_diff_unrecorded(db, feed_id, url, alternate_urls, etag, last_modified, progress)
+                .await;
+        Self::record_refresh_result(db, feed_id, result.as_ref().err(), progress).await?;
+        result
+    }
+
+    /// 记录刷新结果：失败时保存错误信息并累加连续失败次数，成功时清除
+    /// 连续失败达到设置的次数时自动停用RSS源，并推送feed-auto-disabled事件
+    async fn record_refresh_result(
+        db: &SqlitePool,
+        feed_id: &str,
+        error: Option<&AppError>,
+        progress: Option<&FeedProgress<'_>>,
+    ) -> AppResult<()> {
+        match error {
+            Some(e) => {
+                let failures: Option<i64> = sqlx::query_scalar(
+                    "UPDATE rss_feeds SET last_error = ?, last_error_at = ?, consecutive_failures = consecutive_failures + 1
+                     WHERE id = ? RETURNING consecutive_failures",
+                )
+                .bind(e.to_string())
+                .bind(Utc::now().to_rfc3339())
+                .bind(feed_id)
+                .fetch_optional(db)
+                .await?;
+
+                let threshold = Self::get_auto_disable_failures(db).await?;
+                let Some(failures) = failures.map(|n| n as u32) else {
+                    return Ok(());
+                };
+                if threshold == 0 || failures < threshold {
+                    return Ok(());
+                }
+                let disabled = sqlx::query("UPDATE rss_feeds SET is_active = 0 WHERE id = ? AND is_active = 1")
+                    .bind(feed_id)
+                    .execute(db)
+                    .await?
+                    .rows_affected()
+                    > 0;
+                if disabled {
+                    warn!("RSS源 {} 连续 {} 次刷新失败，已自动停用: {}", feed_id, failures, e);
+                    if let Some(progress) = progress {
+                        progress.emit_auto_disabled(failures, e.to_string());
+                    }
+                }
+            }
+            None => {
+                sqlx::query(
+                    "UPDATE rss_feeds SET last_error = NULL, last_error_at = NULL, consecutive_failures = 0 WHERE id = ? AND consecutive_failures > 0",
+                )
+                .bind(feed_id)
+                .execute(db)
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 获取连续失败次数不少于min_failures的RSS源，失败次数多的排在前面
+    pub async fn get_unhealthy_feeds(db: &SqlitePool, min_failures: Option<u32>) -> AppResult<Vec<RssFeed>> {
+        let min_failures = min_failures.unwrap_or(DEFAULT_UNHEALTHY_MIN_FAILURES).max(1);
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM rss_feeds WHERE consecutive_failures >= ? ORDER BY consecutive_failures DESC, last_error_at DESC",
+            FEED_COLUMNS
+        ))
+        .bind(min_failures)
+        .fetch_all(db)
+        .await?;
+
+        Ok(rows.iter().map(Self::feed_from_row).collect())
+    }
+
+    /// 抓取RSS源并保存新文章，同时记录新的缓存头
+    /// 主地址失败时依次尝试备用地址
+    async fn fetch_and_save_diff_unrecorded(
+        db: &SqlitePool,
+        feed_id: &str,
+        url: &str,
+        alternate_urls: &[String],
+        etag: Option<&str>,
+        last_modified: Option<&str>,
+        progress: Option<&FeedProgress<'_>>,
+    ) -> AppResult<RefreshDiff> {
+        // 获取RSS内容并解析
+        let mut fetched_url = url.to_string();
+        let auth = Self::feed_auth(db, feed_id).await?;
+        let mut result = Self::fetch_feed_conditional(url, auth.as_ref(), etag, last_modified).await;
+
+        if let Err(e) = &result {
+            let host = |u: &str| Url::parse(u).ok().and_then(|u| u.host_str().map(str::to_string));
+            for alternate in alternate_urls {
+                info!("主地址 {} 抓取失败（{}），尝试备用地址 {}", url, e, alternate);
+                // 缓存头只对主地址有效，备用地址使用无条件请求；认证信息只发送给同一主机
+                let alternate_auth = auth.as_ref().filter(|_| host(alternate) == host(url));
+                if let Ok(alternate_result) = Self::fetch_feed_conditional(alternate, alternate_auth, None, None).await {
+                    result = Ok(alternate_result);
+                    fetched_url = alternate.clone();
+                    break;
+                }
+            }
+        }
+        let fetched = result?;
+
+        // 主地址被永久重定向时更新保存的地址，之后的请求直接使用新地址
+        if let Some(moved_to) = fetched.moved_to().filter(|_| fetched_url == url) {
+            if Self::update_moved_feed_url(db, feed_id, url, moved_to).await? {
+                fetched_url = moved_to.to_string();
+            }
+        }
+
+        // 获取当前本地时间并转换为UTC时间
+        let now = Local::now().with_timezone(&Utc);
+
+        let (feed, etag, last_modified) = match fetched {
+            FeedFetch::NotModified { .. } => {
+                sqlx::query("UPDATE rss_feeds SET last_updated = ?, last_fetched_url = ? WHERE id = ?")
+                    .bind(now.to_rfc3339())
+                    .bind(&fetched_url)
+                    .bind(feed_id)
+                    .execute(db)
+                    .await?;
+                return Ok(RefreshDiff {
+                    feed_id: feed_id.to_string(),
+                    not_modified: true,
+                    ..Default::default()
+                });
+            }
+            FeedFetch::Modified {
+                feed,
+                etag,
+                last_modified,
+                ..
+            } => (*feed, etag, last_modified),
+        };
+
+        let diff = Self::save_refreshed_entries(db, feed_id, &feed, &now, progress).await?;
+        Self::fill_feed_metadata(db, feed_id, url, &feed).await?;
+
+        // 有新文章时检查文章总数上限，失败不影响本次刷新
+        if !diff.added.is_empty() {
+            if let Err(e) = Self::enforce_article_quota(db, false).await {
+                error!("执行文章数量上限失败: {}", e);
+            }
+        }
+
+        // 更新RSS源的最后更新时间和缓存头
+        sqlx::query(
+            "UPDATE rss_feeds SET last_updated = ?, updated_at = ?, etag = ?, last_modified = ?, last_fetched_url = ? WHERE id = ?",
+        )
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .bind(&etag)
+        .bind(&last_modified)
+        .bind(&fetched_url)
+        .bind(feed_id)
+        .execute(db)
+        .await?;
+
+        Ok(diff)
+    }
+
+    /// 首次成功抓取时用源中的信息补全订阅时没有的元数据（如批量订阅只保存了地址）
+    /// 标题仍是订阅地址时替换为源标题，描述和网站地址为空时填入
+    async fn fill_feed_metadata(db: &SqlitePool, feed_id: &str, subscribed_url: &str, feed: &ParsedFeed) -> AppResult<()> {
+        let non_empty = |value: &Option<String>| value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_string);
+        sqlx::query(
+            "UPDATE rss_feeds SET
+                title = CASE WHEN title = ?1 AND ?2 IS NOT NULL THEN ?2 ELSE title END,
+                description = COALESCE(description, ?3),
+                website_url = COALESCE(website_url, ?4)
+             WHERE id = ?5 AND last_updated IS NULL",
+        )
+        .bind(subscribed_url)
+        .bind(non_empty(&feed.title))
+        .bind(non_empty(&feed.description))
+        .bind(non_empty(&feed.website_url))
+        .bind(feed_id)
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+
+    /// 将RSS源地址更新为永久重定向后的新地址，新地址已被其他源订阅时保留原地址
+    /// 返回是否已更新
+    async fn update_moved_feed_url(db: &SqlitePool, feed_id: &str, old_url: &str, new_url: &str) -> AppResult<bool> {
+        let taken = sqlx::query("SELECT id FROM rss_feeds WHERE url = ? AND id != ?")
+            .bind(new_url)
+            .bind(feed_id)
+            .fetch_optional(db)
+            .await?
+            .is_some();
+        if taken {
+            warn!("RSS源 {} 已永久迁移到 {}，但该地址已被其他源订阅，保留原地址", old_url, new_url);
+            return Ok(false);
+        }
+
+        sqlx::query("UPDATE rss_feeds SET url = ? WHERE id = ?")
+            .bind(new_url)
+            .bind(feed_id)
+            .execute(db)
+            .await?;
+        info!("RSS源 {} 已永久迁移，地址更新为 {}", old_url, new_url);
+        Ok(true)
+    }
+
+    /// 保存刷新得到的文章并记录源格式，返回本次刷新的变化
+    /// 源格式发生变化（如RSS改为Atom）时guid通常也会改变，本次改为按链接去重，避免旧文章被当作新文章重复导入
+    pub(crate) async fn save_refreshed_entries(
+        db: &SqlitePool,
+        feed_id: &str,
+        feed: &ParsedFeed,
+        now: &DateTime<Utc>,
+        progress: Option<&FeedProgress<'_>>,
+    ) -> AppResult<RefreshDiff> {
+        let feed_row = sqlx::query("SELECT feed_format, timezone FROM rss_feeds WHERE id = ?")
+            .bind(feed_id)
+            .fetch_optional(db)
+            .await?;
+        let previous_format: Option<String> = feed_row.as_ref().and_then(|row| row.get("feed_format"));
+        let timezone = feed_row
+            .as_ref()
+            .and_then(|row| row.get::<Option<String>, _>("timezone"))
+            .and_then(|tz| utils::parse_timezone(&tz).ok());
+
+        // 上一次抓取时出现过的文章都带有相同的last_seen_at
+        let previous_seen_at: Option<String> =
+            sqlx::query_scalar("SELECT MAX(last_seen_at) FROM rss_articles WHERE feed_id = ?")
+                .bind(feed_id)
+                .fetch_one(db)
+                .await?;
+
+        match previous_format {
+            Some(previous) if previous != feed.format => {
+                warn!(
+                    "RSS源 {} 格式由 {} 变为 {}，本次刷新按链接去重",
+                    feed_id, previous, feed.format
+                );
+                let mut entries = Vec::with_capacity(feed.entries.len());
+                for entry in &feed.entries {
+                    let known = match &entry.link {
+                        Some(link) => sqlx::query("SELECT id FROM rss_articles WHERE feed_id = ? AND link_key = ?")
+                            .bind(feed_id)
+                            .bind(utils::normalize_article_link(link))
+                            .fetch_optional(db)
+                            .await?
+                            .is_some(),
+                        None => false,
+                    };
+                    if !known {
+                        entries.push(entry.clone());
+                    }
+                }
+                Self::save_articles_with_progress(db, feed_id, &entries, now, progress).await?
+            }
+            _ => Self::save_articles_with_progress(db, feed_id, &feed.entries, now, progress).await?,
+        };
+
+        let now_str = now.to_rfc3339();
+        let mut updated_ids = Vec::new();
+        for entry in &feed.entries {
+            // 已有文章的更新时间比保存的更新时，记为本次更新
+            if let Some(updated) = entry.updated_in(timezone) {
+                let row = sqlx::query(
+                    "SELECT id, updated_at FROM rss_articles WHERE feed_id = ? AND guid = ? AND created_at != ?",
+                )
+                .bind(feed_id)
+                .bind(&entry.id)
+                .bind(&now_str)
+                .fetch_optional(db)
+                .await?;
+                if let Some(row) = row {
+                    let stored = row
+                        .get::<Option<String>, _>("updated_at")
+                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                        .map(|dt| dt.with_timezone(&Utc));
+                    if stored.is_none_or(|stored| updated > stored) {
+                        let id: String = row.get("id");
+                        sqlx::query("UPDATE rss_articles SET updated_at = ? WHERE id = ?")
+                            .bind(updated.to_rfc3339())
+                            .bind(&id)
+                            .execute(db)
+                            .await?;
+                        updated_ids.push(id);
+                    }
+                }
+            }
+
+            sqlx::query(
+                "UPDATE rss_articles SET last_seen_at = ? WHERE feed_id = ? AND (guid = ? OR link_key = ?)",
+            )
+            .bind(&now_str)
+            .bind(feed_id)
+            .bind(&entry.id)
+            .bind(entry.link.as_deref().map(utils::normalize_article_link))
+            .execute(db)
+            .await?;
+        }
+
+        sqlx::query("UPDATE rss_feeds SET feed_format = ? WHERE id = ?")
+            .bind(&feed.format)
+            .bind(feed_id)
+            .execute(db)
+            .await?;
+
+        let added = sqlx::query(&format!(
+            "SELECT {} FROM rss_articles WHERE feed_id = ? AND created_at = ? ORDER BY published_at DESC",
+            ARTICLE_COLUMNS
+        ))
+        .bind(feed_id)
+        .bind(&now_str)
+        .fetch_all(db)
+        .await?;
+
+        let mut updated = Vec::with_capacity(updated_ids.len());
+        for id in &updated_ids {
+            let row = sqlx::query(&format!("SELECT {} FROM rss_articles WHERE id = ?", ARTICLE_COLUMNS))
+                .bind(id)
+                .fetch_one(db)
+                .await?;
+            updated.push(Self::article_from_row(&row));
+        }
+
+        // 上一次抓取中出现、这一次没有出现的文章（源只保留最近的条目时属于正常轮换）
+        let possibly_removed = match previous_seen_at {
+            Some(previous_seen_at) => sqlx::query(&format!(
+                "SELECT {} FROM rss_articles WHERE feed_id = ? AND last_seen_at = ? ORDER BY published_at DESC",
+                ARTICLE_COLUMNS
+            ))
+            .bind(feed_id)
+            .bind(&previous_seen_at)
+            .fetch_all(db)
+            .await?
+            .iter()
+            .map(Self::article_from_row)
+            .collect(),
+            None => Vec::new(),
+        };
+
+        Ok(RefreshDiff {
+            feed_id: feed_id.to_string(),
+            not_modified: false,
+            added: added.iter().map(Self::article_from_row).collect(),
+            updated,
+            possibly_removed,
+        })
+    }
+
+    /// 设置RSS源的cron抓取计划，传入None或空字符串时恢复使用固定刷新间隔
+    pub async fn set_feed_cron(
+        db: &SqlitePool,
+        feed_id: String,
+        expr: Option<String>,
+    ) -> AppResult<String> {
+        let expr = expr
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty());
+
+        // 保存前先验证表达式语法
+        if let Some(expr) = &expr {
+            scheduler::parse_cron(expr)?;
+        }
+
+        let result = sqlx::query("UPDATE rss_feeds SET cron = ? WHERE id = ?")
+            .bind(&expr)
+            .bind(&feed_id)
+            .execute(db)
+            .await?;
+
+        if result.rows_affected() > 0 {
+            Ok("Feed schedule updated successfully".to_string())
+        } else {
+            Err(AppError::feed_not_found(&feed_id))
+        }
+    }
+
+    /// 设置RSS源的时区，仅用于发布时间不带时区信息的源
+    /// 传入None或空字符串时恢复按UTC处理
+    pub async fn set_feed_timezone(
+        db: &SqlitePool,
+        feed_id: String,
+        tz: Option<String>,
+    ) -> AppResult<String> {
+        let tz = tz.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+
+        // 保存前先验证时区名称
+        if let Some(tz) = &tz {
+            utils::parse_timezone(tz)?;
+        }
+
+        let result = sqlx::query("UPDATE rss_feeds SET timezone = ? WHERE id = ?")
+            .bind(&tz)
+            .bind(&feed_id)
+            .execute(db)
+            .await?;
+
+        if result.rows_affected() > 0 {
+            Ok("Feed timezone updated successfully".to_string())
+        } else {
+            Err(AppError::feed_not_found(&feed_id))
+        }
+    }
+
+    /// 设置RSS源保存文章内容的最大字节数，传入None时不限制
+    /// 只影响之后保存或提取的内容
+    pub async fn set_feed_max_content(
+        db: &SqlitePool,
+        feed_id: String,
+        bytes: Option<u32>,
+    ) -> AppResult<String> {
+        if let Some(bytes) = bytes {
+            if bytes < MIN_FEED_MAX_CONTENT_BYTES {
+                return Err(AppError::validation(format!(
+                    "内容大小上限不能小于 {} 字节",
+                    MIN_FEED_MAX_CONTENT_BYTES
+                )));
+            }
+        }
+
+        let result = sqlx::query("UPDATE rss_feeds SET max_content_bytes = ? WHERE id = ?")
+            .bind(bytes)
+            .bind(&feed_id)
+            .execute(db)
+            .await?;
+
+        if result.rows_affected() > 0 {
+            Ok("Feed content size limit updated successfully".to_string())
+        } else {
+            Err(AppError::feed_not_found(&feed_id))
+        }
+    }
+
+    /// 设置RSS源的自动刷新间隔（分钟），传入None时使用全局刷新间隔
+    /// 设置了cron表达式的源仍按cron刷新
+    pub async fn set_feed_refresh_interval(
+        db: &SqlitePool,
+        feed_id: String,
+        minutes: Option<u32>,
+    ) -> AppResult<String> {
+        if let Some(minutes) = minutes {
+            if minutes < scheduler::MIN_REFRESH_INTERVAL_MINUTES {
+                return Err(AppError::validation(format!(
+                    "刷新间隔不能小于 {} 分钟",
+                    scheduler::MIN_REFRESH_INTERVAL_MINUTES
+                )));
+            }
+        }
+
+        let result = sqlx::query("UPDATE rss_feeds SET refresh_interval_minutes = ? WHERE id = ?")
+            .bind(minutes)
+            .bind(&feed_id)
+            .execute(db)
+            .await?;
+
+        if result.rows_affected() > 0 {
+            Ok("Feed refresh interval updated successfully".to_string())
+        } else {
+            Err(AppError::feed_not_found(&feed_id))
+        }
+    }
+
+    /// 按内容大小上限截断内容并追加截断标记
+    fn limit_content(content: Option<String>, max_bytes: Option<u32>) -> Option<String> {
+        match (content, max_bytes) {
+            (Some(content), Some(max)) if content.len() > max as usize => {
+                Some(utils::truncate_html(&content, max as usize, CONTENT_TRUNCATED_MARKER))
+            }
+            (content, _) => content,
+        }
+    }
+
+    /// 文章内容大小上限：RSS源单独设置的上限优先，否则使用全局设置
+    async fn content_limit(db: &SqlitePool, feed_max_bytes: Option<u32>) -> AppResult<Option<u32>> {
+        if feed_max_bytes.is_some() {
+            return Ok(feed_max_bytes);
+        }
+        let max_bytes = Self::get_max_content_bytes(db).await?;
+        Ok((max_bytes > 0).then_some(max_bytes))
+    }
+
+    /// 获取全局的文章内容大小上限（字节），0表示不限制
+    pub async fn get_max_content_bytes(db: &SqlitePool) -> AppResult<u32> {
+        settings::get_u32(db, settings::MAX_CONTENT_BYTES, DEFAULT_MAX_CONTENT_BYTES).await
+    }
+
+    /// 设置全局的文章内容大小上限，设为0时不限制，只影响之后保存或提取的内容
+    pub async fn set_max_content_bytes(db: &SqlitePool, bytes: u32) -> AppResult<()> {
+        if bytes != 0 && bytes < MIN_FEED_MAX_CONTENT_BYTES {
+            return Err(AppError::validation(format!(
+                "内容大小上限不能小于 {} 字节",
+                MIN_FEED_MAX_CONTENT_BYTES
+            )));
+        }
+        settings::set_u32(db, settings::MAX_CONTENT_BYTES, bytes).await
+    }
+
+    /// 设置RSS源文章标题需去除的前缀，传入None或空字符串时取消
+    /// 默认按普通文本匹配，is_regex为true时按正则表达式匹配
+    pub async fn set_feed_title_strip(
+        db: &SqlitePool,
+        feed_id: String,
+        pattern: Option<String>,
+        is_regex: bool,
+    ) -> AppResult<String> {
+        let pattern = pattern.filter(|p| !p.trim().is_empty());
+
+        // 保存前先验证规则语法
+        if let Some(pattern) = &pattern {
+            utils::build_title_strip_regex(pattern, is_regex)?;
+        }
+
+        let result = sqlx::query("UPDATE rss_feeds SET title_strip_prefix = ?, title_strip_is_regex = ? WHERE id = ?")
+            .bind(&pattern)
+            .bind(is_regex)
+            .bind(&feed_id)
+            .execute(db)
+            .await?;
+
+        if result.rows_affected() > 0 {
+            Ok("Feed title prefix rule updated successfully".to_string())
+        } else {
+            Err(AppError::feed_not_found(&feed_id))
+        }
+    }
+
+    /// 设置RSS源是否需要webview渲染后再提取全文，开销较大，只对脚本渲染正文的网站开启
+    pub async fn set_feed_requires_render(
+        db: &SqlitePool,
+        feed_id: String,
+        requires_render: bool,
+    ) -> AppResult<String> {
+        let result = sqlx::query("UPDATE rss_feeds SET requires_render = ? WHERE id = ?")
+            .bind(requires_render)
+            .bind(&feed_id)
+            .execute(db)
+            .await?;
+
+        if result.rows_affected() > 0 {
+            Ok("Feed render setting updated successfully".to_string())
+        } else {
+            Err(AppError::feed_not_found(&feed_id))
+        }
+    }
+
+    /// 获取所有分类
+    pub async fn get_categories(db: &SqlitePool) -> AppResult<Vec<Category>> {
+        let rows = sqlx::query("SELECT id, name, created_at FROM categories ORDER BY name")
+            .fetch_all(db)
+            .await?;
+        Ok(rows.iter().map(Self::category_from_row).collect())
+    }
+
+    fn category_from_row(row: &SqliteRow) -> Category {
+        let created_at_str: String = row.get("created_at");
+        Category {
+            id: row.get("id"),
+            name: row.get("name"),
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        }
+    }
+
+    /// 按名称获取分类（不区分大小写），不存在时创建
+    pub async fn get_or_create_category(db: &SqlitePool, name: &str) -> AppResult<Category> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::validation("分类名称不能为空"));
+        }
+
+        // 获取当前本地时间并转换为UTC时间
+        let now = Local::now().with_timezone(&Utc);
+        sqlx::query("INSERT OR IGNORE INTO categories (id, name, created_at) VALUES (?, ?, ?)")
+            .bind(Uuid::new_v4().to_string())
+            .bind(name)
+            .bind(now.to_rfc3339())
+            .execute(db)
+            .await?;
+
+        let row = sqlx::query("SELECT id, name, created_at FROM categories WHERE name = ?")
+            .bind(name)
+            .fetch_one(db)
+            .await?;
+        Ok(Self::category_from_row(&row))
+    }
+
+    /// 获取所有标签及使用该标签的文章数
+    pub async fn list_tags(db: &SqlitePool) -> AppResult<Vec<Tag>> {
+        let rows = sqlx::query(
+            "SELECT t.id, t.name, t.created_at, COUNT(at.article_id) AS article_count
+             FROM tags t
+             LEFT JOIN article_tags at ON at.tag_id = t.id
+             GROUP BY t.id
+             ORDER BY t.name",
+        )
+        .fetch_all(db)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let created_at_str: String = row.get("created_at");
+                Tag {
+                    id: row.get("id"),
+                    name: row.get("name"),
+                    article_count: row.get::<i64, _>("article_count") as u32,
+                    created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                }
+            })
+            .collect())
+    }
+
+    /// 为文章添加标签（名称不区分大小写），标签不存在时创建
+    pub async fn add_article_tag(db: &SqlitePool, article_id: String, tag: String) -> AppResult<String> {
+        let name = tag.trim();
+        if name.is_empty() {
+            return Err(AppError::validation("标签名称不能为空"));
+        }
+
+        // 获取当前本地时间并转换为UTC时间
+        let now = Local::now().with_timezone(&Utc);
+        let mut tx = db.begin().await?;
+
+        let exists = sqlx::query("SELECT id FROM rss_articles WHERE id = ?")
+            .bind(&article_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if exists.is_none() {
+            return Err(AppError::article_not_found(&article_id));
+        }
+
+        sqlx::query("INSERT OR IGNORE INTO tags (id, name, created_at) VALUES (?, ?, ?)")
+            .bind(Uuid::new_v4().to_string())
+            .bind(name)
+            .bind(now.to_rfc3339())
+            .execute(&mut *tx)
+            .await?;
+        let tag_id: String = sqlx::query_scalar("SELECT id FROM tags WHERE name = ?")
+            .bind(name)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        sqlx::query("INSERT OR IGNORE INTO article_tags (article_id, tag_id, created_at) VALUES (?, ?, ?)")
+            .bind(&article_id)
+            .bind(&tag_id)
+            .bind(now.to_rfc3339())
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok("Article tag added successfully".to_string())
+    }
+
+    /// 移除文章的标签，标签不再被任何文章使用时一并删除
+    pub async fn remove_article_tag(db: &SqlitePool, article_id: String, tag: String) -> AppResult<String> {
+        let mut tx = db.begin().await?;
+
+        let result = sqlx::query(
+            "DELETE FROM article_tags WHERE article_id = ? AND tag_id = (SELECT id FROM tags WHERE name = ?)",
+        )
+        .bind(&article_id)
+        .bind(tag.trim())
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::validation(format!("文章没有标签: {}", tag.trim())));
+        }
+
+        sqlx::query("DELETE FROM tags WHERE id NOT IN (SELECT tag_id FROM article_tags)")
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok("Article tag removed successfully".to_string())
+    }
+
+    /// 获取带有指定标签的文章（名称不区分大小写），按发布时间从新到旧排序
+    pub async fn get_articles_by_tag(
+        db: &SqlitePool,
+        tag: String,
+        limit: Option<i32>,
+        offset: Option<i32>,
+    ) -> AppResult<Vec<RssArticle>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM rss_articles
+             WHERE id IN (
+                 SELECT at.article_id FROM article_tags at
+                 JOIN tags t ON t.id = at.tag_id
+                 WHERE t.name = ?
+             )
+             ORDER BY published_at DESC, created_at DESC
+             LIMIT ? OFFSET ?",
+            ARTICLE_COLUMNS
+        ))
+        .bind(tag.trim())
+        .bind(limit.unwrap_or(50))
+        .bind(offset.unwrap_or(0))
+        .fetch_all(db)
+        .await?;
+
+        Ok(rows.iter().map(Self::article_from_row).collect())
+    }
+
+    /// 批量订阅RSS源（只创建订阅记录，不抓取文章），每个URL可以指定分类，分类不存在时创建
+    /// 与OPML导入相同，规范化后相同的URL视为已订阅
+    pub async fn add_feeds_bulk(
+        db: &SqlitePool,
+        entries: Vec<BulkFeedEntry>,
+    ) -> AppResult<Vec<BulkFeedResult>> {
+        let mut subscribed = Self::get_subscribed_urls(db).await?;
+        // 获取当前本地时间并转换为UTC时间
+        let now = Local::now().with_timezone(&Utc);
+        let mut results = Vec::with_capacity(entries.len());
+
+        for entry in entries {
+            let url = entry.url.trim().to_string();
+            let result = |status, feed, category| BulkFeedResult {
+                url: url.clone(),
+                status,
+                feed,
+                category,
+            };
+
+            let valid_url = Url::parse(&url)
+                .map(|parsed| parsed.scheme() == "http" || parsed.scheme() == "https")
+                .unwrap_or(false);
+            if !valid_url {
+                results.push(result(BulkFeedStatus::Invalid, None, None));
+                continue;
+            }
+            if !subscribed.insert(utils::normalize_feed_url(&url)) {
+                results.push(result(BulkFeedStatus::Duplicate, None, None));
+                continue;
+            }
+
+            let category = match entry.category.as_deref().map(str::trim) {
+                Some(name) if !name.is_empty() => Some(Self::get_or_create_category(db, name).await?),
+                _ => None,
+            };
+
+            // last_updated留空，保证订阅后可以立即刷新
+            let feed_id = Uuid::new_v4().to_string();
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, category_id, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)"
+            )
+            .bind(&feed_id)
+            .bind(&url)
+            .bind(&url)
+            .bind(category.as_ref().map(|c| &c.id))
+            .bind(now.to_rfc3339())
+            .bind(now.to_rfc3339())
+            .execute(db)
+            .await?;
+
+            let feed = Self::get_feed(db, &feed_id).await?;
+            results.push(result(
+                BulkFeedStatus::Added,
+                Some(feed),
+                category.map(|c| c.name),
+            ));
+        }
+
+        Ok(results)
+    }
+
+    /// 获取所有已订阅RSS源的规范化URL
+    async fn get_subscribed_urls(
+        db: &SqlitePool,
+    ) -> AppResult<std::collections::HashSet<String>> {
+        let rows = sqlx::query("SELECT url FROM rss_feeds").fetch_all(db).await?;
+        Ok(rows
+            .iter()
+            .map(|row| utils::normalize_feed_url(&row.get::<String, _>("url")))
+            .collect())
+    }
+
+    /// 预览OPML导入：解析并统计已订阅和无效的条目，不写入数据库
+    pub async fn validate_opml(db: &SqlitePool, content: &str) -> AppResult<OpmlValidation> {
+        let parsed = opml::parse_opml(content)?;
+        let subscribed = Self::get_subscribed_urls(db).await?;
+
+        let mut seen = std::collections::HashSet::new();
+        let mut feeds = Vec::new();
+        for outline in parsed.outlines {
+            let normalized = utils::normalize_feed_url(&outline.xml_url);
+            // OPML内部重复的条目只保留第一个
+            if !seen.insert(normalized.clone()) {
+                continue;
+            }
+            feeds.push(OpmlFeedPreview {
+                title: outline.title,
+                url: outline.xml_url,
+                already_subscribed: subscribed.contains(&normalized),
+            });
+        }
+
+        let already_subscribed = feeds.iter().filter(|f| f.already_subscribed).count() as u32;
+
+        Ok(OpmlValidation {
+            total: feeds.len() as u32,
+            already_subscribed,
+            feeds,
+            malformed: parsed.malformed,
+        })
+    }
+
+    /// 将所有RSS源导出为OPML 2.0文档
+    pub async fn export_opml(db: &SqlitePool) -> AppResult<String> {
+        let feeds = Self::get_feeds(db, true).await?;
+        // 获取当前本地时间并转换为UTC时间
+        let now = Local::now().with_timezone(&Utc);
+        Ok(opml::build_opml(&feeds, now))
+    }
+
+    /// 从OPML导入RSS源（只创建订阅记录，不抓取文章）
+    pub async fn import_opml(db: &SqlitePool, content: &str) -> AppResult<OpmlImportResult> {
+        let parsed = opml::parse_opml(content)?;
+        let mut subscribed = Self::get_subscribed_urls(db).await?;
+        // 获取当前本地时间并转换为UTC时间
+        let now = Local::now().with_timezone(&Utc);
+
+        let mut imported = Vec::new();
+        let mut skipped = Vec::new();
+        let invalid = parsed.malformed;
+
+        for outline in parsed.outlines {
+            // 规范化后相同的URL视为已订阅（包括OPML内部的重复条目）
+            if !subscribed.insert(utils::normalize_feed_url(&outline.xml_url)) {
+                skipped.push(outline.xml_url);
+                continue;
+            }
+
+            let feed_id = Uuid::new_v4().to_string();
+            let title = outline
+                .title
+                .clone()
+                .unwrap_or_else(|| outline.xml_url.clone());
+
+            // last_updated留空，保证导入后可以立即刷新
+            let result = sqlx::query(
+                "INSERT OR IGNORE INTO rss_feeds (id, title, url, website_url, is_active, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(&feed_id)
+            .bind(&title)
+            .bind(&outline.xml_url)
+            .bind(&outline.html_url)
+            .bind(outline.is_active)
+            .bind(now.to_rfc3339())
+            .bind(now.to_rfc3339())
+            .execute(db)
+            .await?;
+
+            if result.rows_affected() == 0 {
+                skipped.push(outline.xml_url);
+                continue;
+            }
+
+            imported.push(Self::get_feed(db, &feed_id).await?);
+        }
+
+        info!(
+            "OPML导入完成: 新增 {} 个，已存在 {} 个，无效 {} 个",
+            imported.len(),
+            skipped.len(),
+            invalid.len()
+        );
+
+        Ok(OpmlImportResult {
+            imported,
+            skipped,
+            invalid,
+        })
+    }
+
+    /// 并发刷新多个RSS源（不指定时刷新所有活跃源），返回每个源的新增文章数或错误信息
+    /// concurrency为None时使用默认并发数，单个源失败不影响其他源
+    pub async fn refresh_all_feeds(
+        db: &SqlitePool,
+        app_handle: &AppHandle,
+        feed_ids: Option<Vec<String>>,
+        concurrency: Option<usize>,
+    ) -> AppResult<Vec<(String, Result<u32, String>)>> {
+        let rows = sqlx::query("SELECT id, title FROM rss_feeds WHERE is_active = 1")
+            .fetch_all(db)
+            .await?;
+
+        let feeds: Vec<(String, String)> = rows
+            .iter()
+            .map(|row| (row.get("id"), row.get("title")))
+            .filter(|(id, _): &(String, String)| match &feed_ids {
+                Some(ids) => ids.contains(id),
+                None => true,
+            })
+            .collect();
+
+        let mut progress = RssBatchRefreshProgress {
+            total_feeds: feeds.len() as u32,
+            completed_feeds: 0,
+            failed_feeds: 0,
+            current_feed_title: None,
+        };
+        let _ = app_handle.emit("rss-batch-refresh-progress", &progress);
+
+        // 使用信号量限制并发，避免同时请求过多RSS服务器
+        let concurrency = concurrency
+            .unwrap_or(DEFAULT_REFRESH_CONCURRENCY)
+            .clamp(1, MAX_REFRESH_CONCURRENCY);
+        let semaphore = Arc::new(Semaphore::new(concurrency));
+        let mut tasks = JoinSet::new();
+
+        for (feed_id, feed_title) in feeds {
+            let db = db.clone();
+            let app_handle = app_handle.clone();
+            let semaphore = semaphore.clone();
+
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+
+                let feed_progress = FeedProgress::new(&app_handle, &feed_id, &feed_title);
+                feed_progress.emit(RssFetchStatus::Started, 0, 0, None);
+
+                let attempt = Self::try_refresh_feed(&db, &feed_id, Some(&feed_progress)).await;
+                feed_progress.finish(&attempt);
+                // 频率限制内的源视为没有新文章
+                let result = match attempt {
+                    Ok(RefreshAttempt::TooFrequent(_)) => Ok(0),
+                    Ok(RefreshAttempt::Done(diff)) => Ok(diff.added.len() as u32),
+                    Err(e) => Err(e.to_string()),
+                };
+
+                (feed_id, feed_title, result)
+            });
+        }
+
+        let mut results = Vec::with_capacity(progress.total_feeds as usize);
+        while let Some(joined) = tasks.join_next().await {
+            progress.completed_feeds += 1;
+            match joined {
+                Ok((feed_id, feed_title, result)) => {
+                    if result.is_err() {
+                        progress.failed_feeds += 1;
+                    }
+                    progress.current_feed_title = Some(feed_title);
+                    results.push((feed_id, result));
+                }
+                Err(e) => {
+                    progress.failed_feeds += 1;
+                    log::error!("刷新任务异常退出: {}", e);
+                }
+            }
+            let _ = app_handle.emit("rss-batch-refresh-progress", &progress);
+        }
+
+        Ok(results)
+    }
+
+    /// 获取OPML导入后立即刷新的并发数
+    pub async fn get_import_concurrency(db: &SqlitePool) -> AppResult<u32> {
+        settings::get_u32(db, settings::IMPORT_CONCURRENCY, DEFAULT_REFRESH_CONCURRENCY as u32).await
+    }
+
+    /// 设置OPML导入后立即刷新的并发数
+    pub async fn set_import_concurrency(db: &SqlitePool, concurrency: u32) -> AppResult<()> {
+        if !(1..=MAX_REFRESH_CONCURRENCY as u32).contains(&concurrency) {
+            return Err(AppError::validation(format!(
+                "导入并发数必须在1到{}之间: {}",
+                MAX_REFRESH_CONCURRENCY, concurrency
+            )));
+        }
+        settings::set_u32(db, settings::IMPORT_CONCURRENCY, concurrency).await
+    }
+
+    /// 设置RSS源的备用地址，按顺序在主地址失败时尝试
+    pub async fn set_feed_alternates(
+        db: &SqlitePool,
+        feed_id: String,
+        urls: Vec<String>,
+    ) -> AppResult<RssFeed> {
+        let feed = Self::get_feed(db, &feed_id).await?;
+
+        let mut alternates: Vec<String> = Vec::new();
+        for url in urls {
+            let url = url.trim().to_string();
+            if url.is_empty() || url == feed.url || alternates.contains(&url) {
+                continue;
+            }
+            let parsed = Url::parse(&url).map_err(|_| AppError::invalid_rss_url(&url))?;
+            if parsed.scheme() != "http" && parsed.scheme() != "https" {
+                return Err(AppError::invalid_rss_url(&url));
+            }
+            alternates.push(url);
+        }
+
+        let alternates_json = if alternates.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&alternates)?)
+        };
+
+        sqlx::query("UPDATE rss_feeds SET alternate_urls = ? WHERE id = ?")
+            .bind(&alternates_json)
+            .bind(&feed_id)
+            .execute(db)
+            .await?;
+
+        Self::get_feed(db, &feed_id).await
+    }
+
+    /// 清空RSS源中未收藏的文章，dry_run时只返回将要删除的文章
+    pub async fn clear_feed_articles(
+        db: &SqlitePool,
+        feed_id: String,
+        dry_run: bool,
+    ) -> AppResult<CleanupReport> {
+        Self::get_feed(db, &feed_id).await?;
+
+        let rows = sqlx::query(
+            "SELECT id, title FROM rss_articles WHERE feed_id = ? AND is_starred = 0
+             ORDER BY published_at DESC, created_at DESC",
+        )
+        .bind(&feed_id)
+        .fetch_all(db)
+        .await?;
+        let targets = rows.iter().map(|row| (row.get("id"), row.get("title"))).collect();
+
+        cleanup::apply(db, targets, dry_run).await
+    }
+
+    /// 设置保存的文章总数上限，0表示不限制
+    pub async fn set_max_total_articles(db: &SqlitePool, max: u32) -> AppResult<()> {
+        settings::set_u32(db, settings::MAX_TOTAL_ARTICLES, max).await
+    }
+
+    /// 文章总数超过上限时，按保存时间从旧到新删除已读且未收藏的文章，直到不超过上限
+    /// 可删除的文章不足时只删除能删除的部分
+    pub async fn enforce_article_quota(db: &SqlitePool, dry_run: bool) -> AppResult<CleanupReport> {
+        let max = settings::get_u32(db, settings::MAX_TOTAL_ARTICLES, 0).await?;
+        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM rss_articles")
+            .fetch_one(db)
+            .await?;
+        let excess = if max == 0 { 0 } else { (total - max as i64).max(0) };
+
+        let rows = sqlx::query(
+            "SELECT id, title FROM rss_articles WHERE is_read = 1 AND is_starred = 0
+             ORDER BY created_at, rowid LIMIT ?",
+        )
+        .bind(excess)
+        .fetch_all(db)
+        .await?;
+        if (rows.len() as i64) < excess {
+            warn!(
+                "文章总数 {} 超过上限 {}，但只有 {} 篇已读未收藏的文章可以删除",
+                total,
+                max,
+                rows.len()
+            );
+        }
+        let targets = rows.iter().map(|row| (row.get("id"), row.get("title"))).collect();
+
+        cleanup::apply(db, targets, dry_run).await
+    }
+
+    /// 删除发布时间（没有时按保存时间）早于older_than_days天前的文章，收藏的文章总是保留
+    /// only_read为true时只删除已读文章，dry_run时只返回将要删除的文章
+    pub async fn cleanup_old_articles(
+        db: &SqlitePool,
+        older_than_days: u32,
+        only_read: bool,
+        dry_run: bool,
+    ) -> AppResult<CleanupReport> {
+        if older_than_days == 0 {
+            return Err(AppError::validation("天数必须大于0"));
+        }
+        let cutoff = Utc::now() - chrono::Duration::days(older_than_days as i64);
+
+        let rows = sqlx::query(
+            "SELECT id, title FROM rss_articles
+             WHERE is_starred = 0 AND (? = 0 OR is_read = 1)
+               AND datetime(COALESCE(published_at, created_at)) < datetime(?)
+             ORDER BY datetime(COALESCE(published_at, created_at)), rowid",
+        )
+        .bind(only_read)
+        .bind(cutoff.to_rfc3339())
+        .fetch_all(db)
+        .await?;
+        let targets = rows.iter().map(|row| (row.get("id"), row.get("title"))).collect();
+
+        cleanup::apply(db, targets, dry_run).await
+    }
+
+    /// 每个RSS源只保留最新的keep_per_feed篇未收藏文章（按发布时间，没有时按保存时间），删除其余文章
+    /// 收藏的文章总是保留且不计入数量，dry_run时只返回将要删除的文章
+    pub async fn prune_articles(
+        db: &SqlitePool,
+        keep_per_feed: u32,
+        dry_run: bool,
+    ) -> AppResult<CleanupReport> {
+        let rows = sqlx::query(
+            "SELECT id, title FROM (
+                 SELECT id, title, feed_id, COALESCE(published_at, created_at) AS sort_date, rowid AS row_id,
+                        ROW_NUMBER() OVER (
+                            PARTITION BY feed_id
+                            ORDER BY datetime(COALESCE(published_at, created_at)) DESC, rowid DESC
+                        ) AS position
+                 FROM rss_articles WHERE is_starred = 0
+             )
+             WHERE position > ?
+             ORDER BY feed_id, datetime(sort_date), row_id",
+        )
+        .bind(keep_per_feed)
+        .fetch_all(db)
+        .await?;
+        let targets = rows.iter().map(|row| (row.get("id"), row.get("title"))).collect();
+
+        cleanup::apply(db, targets, dry_run).await
+    }
+
+    /// 删除RSS源
+    pub async fn delete_feed(storage: &impl Storage, feed_id: String) -> AppResult<String> {
+        storage.delete_feed(feed_id).await
+    }
+
+    /// 停用RSS源：保留源和文章，不再自动刷新，默认不出现在源列表中
+    pub async fn disable_feed(db: &SqlitePool, feed_id: String) -> AppResult<String> {
+        Self::set_feed_active(db, &feed_id, false).await?;
+        Ok("RSS feed disabled successfully".to_string())
+    }
+
+    /// 重新启用已停用的RSS源
+    pub async fn enable_feed(db: &SqlitePool, feed_id: String) -> AppResult<String> {
+        Self::set_feed_active(db, &feed_id, true).await?;
+        Ok("RSS feed enabled successfully".to_string())
+    }
+
+    /// 重新启用RSS源并清除失败记录，用于因连续失败被自动停用的源
+    pub async fn reactivate_feed(db: &SqlitePool, feed_id: String) -> AppResult<RssFeed> {
+        let result = sqlx::query(
+            "UPDATE rss_feeds SET is_active = 1, consecutive_failures = 0, last_error = NULL, last_error_at = NULL WHERE id = ?",
+        )
+        .bind(&feed_id)
+        .execute(db)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::feed_not_found(&feed_id));
+        }
+
+        Self::get_feed(db, &feed_id).await
+    }
+
+    /// 获取自动停用RSS源的连续失败次数，0表示不自动停用
+    pub async fn get_auto_disable_failures(db: &SqlitePool) -> AppResult<u32> {
+        settings::get_u32(db, settings::FEED_AUTO_DISABLE_FAILURES, DEFAULT_AUTO_DISABLE_FAILURES).await
+    }
+
+    /// 设置自动停用RSS源的连续失败次数，设为0时不自动停用
+    pub async fn set_auto_disable_failures(db: &SqlitePool, failures: u32) -> AppResult<()> {
+        settings::set_u32(db, settings::FEED_AUTO_DISABLE_FAILURES, failures).await
+    }
+
+    async fn set_feed_active(db: &SqlitePool, feed_id: &str, is_active: bool) -> AppResult<()> {
+        let result = sqlx::query("UPDATE rss_feeds SET is_active = ? WHERE id = ?")
+            .bind(is_active)
+            .bind(feed_id)
+            .execute(db)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::feed_not_found(feed_id));
+        }
+        Ok(())
+    }
+
+    /// 保存文章到数据库
+    pub async fn save_articles(
+        storage: &impl Storage,
+        feed_id: &str,
+        entries: &[ParsedEntry],
+        now: &DateTime<Utc>,
+    ) -> AppResult<i32> {
+        storage.save_articles(feed_id, entries, now).await
+    }
+
+    /// 保存文章到数据库，每处理几篇文章推送一次进度
+    pub(crate) async fn save_articles_with_progress(
+        db: &SqlitePool,
+        feed_id: &str,
+        entries: &[ParsedEntry],
+        now: &DateTime<Utc>,
+        progress: Option<&FeedProgress<'_>>,
+    ) -> AppResult<i32> {
+        let mut new_articles = 0;
+        let compress = compression::is_enabled(db).await?;
+        let keep_raw = settings::get_bool(db, settings::KEEP_RAW_CONTENT, false).await?;
+        let teaser_threshold = Self::get_teaser_threshold(db).await?;
+
+        // 设置了时区的源不带时区信息，解析器按UTC得到的发布时间需按该时区的本地时间重新解释
+        let feed_row = sqlx::query("SELECT timezone, title_strip_prefix, title_strip_is_regex, requires_render, max_content_bytes FROM rss_feeds WHERE id = ?")
+            .bind(feed_id)
+            .fetch_optional(db)
+            .await?;
+        let timezone = feed_row
+            .as_ref()
+            .and_then(|row| row.get::<Option<String>, _>("timezone"))
+            .and_then(|tz| utils::parse_timezone(&tz).ok());
+        // 标题前缀规则在保存时应用，只影响之后新增的文章
+        let title_strip = feed_row
+            .as_ref()
+            .and_then(|row| {
+                let pattern = row.get::<Option<String>, _>("title_strip_prefix")?;
+                utils::build_title_strip_regex(&pattern, row.get("title_strip_is_regex")).ok()
+            });
+        let requires_render = feed_row
+            .as_ref()
+            .is_some_and(|row| row.get::<bool, _>("requires_render"));
+        let max_content_bytes = Self::content_limit(
+            db,
+            feed_row
+                .as_ref()
+                .and_then(|row| row.get::<Option<i64>, _>("max_content_bytes"))
+                .map(|n| n as u32),
+        )
+        .await?;
+
+        // guid不稳定的源按规范化链接识别已有文章，链接相同时保留最早的一篇
+        let mut known_links: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+        let link_rows = sqlx::query(
+            "SELECT id, link FROM rss_articles WHERE feed_id = ? AND link IS NOT NULL ORDER BY created_at, rowid",
+        )
+        .bind(feed_id)
+        .fetch_all(db)
+        .await?;
+        for row in &link_rows {
+            known_links
+                .entry(utils::normalize_article_link(&row.get::<String, _>("link")))
+                .or_insert_with(|| row.get("id"));
+        }
 
-    /// 提取HTML内容的主要文本
-    pub async fn extract_article_content(url: &str) -> Option<String> {
-        println!("[DEBUG] 开始提取文章内容: {}", url);
+        // 用户删除过的文章即使仍在源中也不再保存
+        let deleted = cleanup::DeletedArticles::load(db, feed_id).await?;
 
-        // 创建带有用户代理的客户端
-        let client = reqwest::Client::builder()
-            .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36")
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .ok()?;
+        // 同一批中guid重复的条目按已有文章处理
+        let mut batch_guids: std::collections::HashMap<&str, String> = std::collections::HashMap::new();
+        let mut writes = Vec::new();
+        let total_articles = entries.len() as u32;
+        if let Some(progress) = progress {
+            progress.emit(RssFetchStatus::InProgress, total_articles, 0, None);
+        }
 
-        // 获取网页内容
-        let response = match client.get(url).send().await {
-            Ok(resp) => resp,
-            Err(e) => {
-                println!("[ERROR] 请求失败: {}", e);
-                return None;
+        for (index, entry) in entries.iter().enumerate() {
+            if let Some(progress) = progress {
+                let fetched = index + 1;
+                if fetched % PROGRESS_EMIT_INTERVAL == 0 || fetched == entries.len() {
+                    progress.emit(RssFetchStatus::InProgress, total_articles, fetched as u32, entry.title.clone());
+                }
             }
-        };
+            let article_id = Uuid::new_v4().to_string();
+            let mut article_title = entry
+                .title
+                .clone()
+                .unwrap_or_else(|| "Untitled Article".to_string());
+            let mut original_title = None;
+            if let Some(stripped) = title_strip
+                .as_ref()
+                .and_then(|regex| utils::strip_title_prefix(&article_title, regex))
+            {
+                original_title = Some(std::mem::replace(&mut article_title, stripped));
+            }
+            let link = entry.link.clone();
+            let description = entry
+                .summary
+                .as_deref()
+                .map(|summary| Self::clean_feed_html(summary, link.as_deref()));
+            let mut content = entry.content.clone();
+            let author = entry.author.clone();
+            let published_at = entry.published_in(timezone).map(|p| p.to_rfc3339());
+            let updated_at = entry.updated_in(timezone).map(|p| p.to_rfc3339());
+            let guid = entry.id.clone();
 
-        let html_content = match response.text().await {
-            Ok(content) => content,
-            Err(e) => {
-                println!("[ERROR] 读取响应内容失败: {}", e);
-                return None;
+            let existing_id = match batch_guids.get(entry.id.as_str()) {
+                Some(id) => Some(id.clone()),
+                None => sqlx::query_scalar::<_, String>(
+                    "SELECT id FROM rss_articles WHERE feed_id = ? AND guid = ?",
+                )
+                .bind(feed_id)
+                .bind(&guid)
+                .fetch_optional(db)
+                .await?,
+            };
+            let existing_id = existing_id.or_else(|| {
+                link.as_deref()
+                    .and_then(|link| known_links.get(&utils::normalize_article_link(link)))
+                    .cloned()
+            });
+            if let Some(existing_id) = existing_id {
+                // 已有文章不再重复插入，源中的完整正文有变化时更新内容
+                // 只有摘要时不覆盖之前提取的全文
+                if !Self::is_teaser(content.as_deref(), teaser_threshold) {
+                    writes.push(ArticleWrite::Update {
+                        article_id: existing_id,
+                        entry,
+                        content,
+                    });
+                }
+                continue;
+            }
+            if deleted.contains(&guid, link.as_deref().map(utils::normalize_article_link).as_deref()) {
+                continue;
             }
-        };
 
-        println!("[DEBUG] 获取到HTML内容，长度: {}", html_content.len());
-
-        // 使用readability提取主要内容
-        if let Ok(parsed_url) = url.parse::<url::Url>() {
-            match extractor::extract(&mut html_content.as_bytes(), &parsed_url) {
-                Ok(product) => {
-                    if !product.content.trim().is_empty() {
-                        println!(
-                            "[DEBUG] Readability提取成功，内容长度: {}",
-                            product.content.len()
-                        );
-                        return Some(product.content);
+            // 如果RSS中没有完整内容或只有摘要，尝试从链接获取
+            let teaser_link = link
+                .as_deref()
+                .filter(|_| Self::is_teaser(content.as_deref(), teaser_threshold));
+            let mut page_image = None;
+            if let Some(link) = teaser_link {
+                if let Some(html) = Self::fetch_article_page(link, requires_render).await {
+                    // 已经抓取了页面，顺便读取og:image作为题图的最后备选
+                    page_image = Url::parse(link)
+                        .ok()
+                        .and_then(|page_url| Self::find_og_image(&html, &page_url));
+                    let extracted = Self::with_render_fallback(link, Self::extract_from_html(&html, link)).await;
+                    if let Some(extracted_content) = extracted.map(|extracted| extracted.content) {
+                        // 提取结果不比源内容长时保留源内容
+                        let feed_words = content.as_deref().map_or(0, utils::count_words);
+                        if utils::count_words(&extracted_content) > feed_words {
+                            content = Some(extracted_content);
+                        }
                     }
                 }
-                Err(e) => {
-                    println!("[DEBUG] Readability提取失败: {}", e);
-                }
             }
-        }
 
-        // 如果readability失败，使用scraper进行简单的内容提取
-        let document = Html::parse_document(&html_content);
-
-        // 尝试常见的文章内容选择器
-        let selectors = [
-            "article",
-            ".post-content",
-            ".entry-content",
-            ".content",
-            "main",
-            ".article-body",
-            "#content",
-            ".post-body",
-            ".article-content",
-            ".post",
-            "[role='main']",
-        ];
+            let raw_content = if keep_raw {
+                Self::limit_content(content.clone(), max_content_bytes)
+            } else {
+                None
+            };
+            // 解析相对地址并清理脚本和事件属性后再截断，原始内容保留未处理的版本
+            let content = Self::limit_content(
+                content.map(|content| Self::clean_feed_html(&content, link.as_deref())),
+                max_content_bytes,
+            );
+            // 没有正文时按摘要统计字数和估算阅读时间
+            let word_count = content
+                .as_deref()
+                .or(description.as_deref())
+                .map(utils::count_words);
+            let read_time = Self::extract_read_time(entry, content.as_deref().or(description.as_deref()));
+            // 题图优先取源中的媒体信息，其次正文中的第一张图片，最后是文章页面的og:image
+            let image_url = entry
+                .image_url
+                .clone()
+                .or_else(|| content.as_deref().and_then(utils::first_image_url))
+                .or(page_image);
+            let stored = compression::encode(content, compress)?;
 
-        for selector_str in &selectors {
-            if let Ok(selector) = Selector::parse(selector_str) {
-                if let Some(element) = document.select(&selector).next() {
-                    let text = element
-                        .text()
-                        .collect::<Vec<_>>()
-                        .join(" ")
-                        .trim()
-                        .to_string();
-                    if text.len() > 100 {
-                        // 确保内容足够长
-                        println!(
-                            "[DEBUG] 使用选择器 '{}' 提取成功，内容长度: {}",
-                            selector_str,
-                            text.len()
-                        );
-                        return Some(text);
-                    }
-                }
+            batch_guids.insert(entry.id.as_str(), article_id.clone());
+            if let Some(link) = &link {
+                known_links.insert(utils::normalize_article_link(link), article_id.clone());
             }
+            writes.push(ArticleWrite::Insert(Box::new(NewArticle {
+                id: article_id,
+                title: article_title,
+                original_title,
+                link,
+                description,
+                stored,
+                raw_content,
+                author,
+                published_at,
+                updated_at,
+                guid,
+                read_time,
+                word_count,
+                image_url,
+            })));
         }
 
-        // 最后尝试提取所有p标签的内容
-        if let Ok(p_selector) = Selector::parse("p") {
-            let paragraphs: Vec<String> = document
-                .select(&p_selector)
-                .map(|element| {
-                    element
-                        .text()
-                        .collect::<Vec<_>>()
-                        .join(" ")
-                        .trim()
-                        .to_string()
-                })
-                .filter(|text| text.len() > 20)
-                .collect();
+        // 抓取全文等网络操作在事务外完成，所有写入在同一事务中提交，出错时整体回滚
+        let mut tx = db.begin().await?;
+        for write in writes {
+            let article = match write {
+                ArticleWrite::Update {
+                    article_id,
+                    entry,
+                    content,
+                } => {
+                    Self::update_existing_content(
+                        &mut tx,
+                        &article_id,
+                        entry,
+                        content,
+                        max_content_bytes,
+                        compress,
+                        keep_raw,
+                    )
+                    .await?;
+                    continue;
+                }
+                ArticleWrite::Insert(article) => article,
+            };
+
+            let result = sqlx::query(
+                "INSERT OR IGNORE INTO rss_articles (id, feed_id, title, original_title, link, link_key, description, content, content_compressed, content_compression, content_raw, author, published_at, updated_at, guid, read_time, word_count, image_url, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(&article.id)
+            .bind(feed_id)
+            .bind(&article.title)
+            .bind(&article.original_title)
+            .bind(&article.link)
+            .bind(article.link.as_deref().map(utils::normalize_article_link))
+            .bind(&article.description)
+            .bind(&article.stored.content)
+            .bind(&article.stored.compressed)
+            .bind(article.stored.compression)
+            .bind(&article.raw_content)
+            .bind(&article.author)
+            .bind(&article.published_at)
+            .bind(&article.updated_at)
+            .bind(&article.guid)
+            .bind(&article.read_time)
+            .bind(article.word_count)
+            .bind(&article.image_url)
+            .bind(now.to_rfc3339())
+            .execute(&mut *tx)
+            .await?;
 
-            if !paragraphs.is_empty() {
-                let content = paragraphs.join("\n\n");
-                println!("[DEBUG] 使用p标签提取成功，内容长度: {}", content.len());
-                return Some(content);
+            if result.rows_affected() > 0 {
+                new_articles += 1;
+                if let Some(plain) = &article.stored.plain {
+                    SearchService::index_compressed_content(&mut tx, &article.id, plain).await?;
+                }
+                let published_at = article
+                    .published_at
+                    .as_deref()
+                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                    .map(|dt| dt.with_timezone(&Utc));
+                Self::flag_duplicate(&mut tx, &article.id, feed_id, article.link.as_deref(), &article.title, published_at)
+                    .await?;
             }
         }
+        tx.commit().await?;
 
-        println!("[DEBUG] 所有提取方法都失败了");
-        None
+        Ok(new_articles)
     }
 
-    /// 获取单篇文章详细内容
-    pub async fn get_article_content(db: &SqlitePool, article_id: String) -> AppResult<RssArticle> {
-        let row = sqlx::query(
-            "SELECT id, feed_id, title, link, description, content, author, published_at, guid, is_read, is_starred, read_time, created_at FROM rss_articles WHERE id = ?"
-        )
-        .bind(&article_id)
-        .fetch_one(db)
-        .await
-        .map_err(|_| AppError::article_not_found(&article_id))?;
-
-        let created_at_str: String = row.get("created_at");
-        let published_at_str: Option<String> = row.get("published_at");
-
-        // 如果content为空，尝试从原始链接获取完整内容
-        let mut content: Option<String> = row.get("content");
-        let link: Option<String> = row.get("link");
+    /// 处理源提供的HTML：相对地址按文章链接解析为绝对地址，再清理脚本和事件属性
+    fn clean_feed_html(html: &str, link: Option<&str>) -> String {
+        let resolved = match link.and_then(|link| Url::parse(link).ok()) {
+            Some(base) => utils::resolve_relative_urls(html, &base),
+            None => html.to_string(),
+        };
+        sanitize::sanitize_html(&resolved)
+    }
 
-        info!("link is {:?}", link);
+    /// 源中已有文章的正文发生变化时更新存储的内容、摘要和阅读指标，内容相同时不写入
+    async fn update_existing_content(
+        conn: &mut SqliteConnection,
+        article_id: &str,
+        entry: &ParsedEntry,
+        content: Option<String>,
+        max_content_bytes: Option<u32>,
+        compress: bool,
+        keep_raw: bool,
+    ) -> AppResult<bool> {
+        let Some(raw) = content else {
+            return Ok(false);
+        };
+        let content = Self::limit_content(
+            Some(Self::clean_feed_html(&raw, entry.link.as_deref())),
+            max_content_bytes,
+        )
+            .unwrap_or_default();
 
-        // 如果content为空且有链接，尝试获取完整内容
-        if (content.is_none() || content.as_ref().map_or(true, |c| c.trim().is_empty()))
-            && link.is_some()
-        {
-            if let Some(extracted_content) =
-                Self::extract_article_content(link.as_ref().unwrap()).await
-            {
-                content = Some(extracted_content);
+        let row = sqlx::query(
+            "SELECT content, content_compressed, content_compression FROM rss_articles WHERE id = ?",
+        )
+        .bind(article_id)
+        .fetch_one(&mut *conn)
+        .await?;
+        let stored = match row.get::<Option<String>, _>("content") {
+            Some(stored) => Some(stored),
+            None => compression::decode(
+                row.get::<Option<String>, _>("content_compression").as_deref(),
+                row.get::<Option<Vec<u8>>, _>("content_compressed").as_deref(),
+            )?,
+        };
+        if stored.as_deref() == Some(content.as_str()) {
+            return Ok(false);
+        }
 
-                // 将提取的内容保存到数据库中，避免重复提取
-                let _ = sqlx::query("UPDATE rss_articles SET content = ? WHERE id = ?")
-                    .bind(&content)
-                    .bind(&article_id)
-                    .execute(db)
-                    .await;
-            }
+        let raw_content = if keep_raw { Self::limit_content(Some(raw), max_content_bytes) } else { None };
+        let word_count = utils::count_words(&content);
+        let read_time = Self::extract_read_time(entry, Some(&content));
+        let encoded = compression::encode(Some(content), compress)?;
+        sqlx::query(
+            "UPDATE rss_articles SET content = ?, content_compressed = ?, content_compression = ?,
+                content_raw = COALESCE(?, content_raw), description = COALESCE(?, description),
+                read_time = ?, word_count = ?
+             WHERE id = ?",
+        )
+        .bind(&encoded.content)
+        .bind(&encoded.compressed)
+        .bind(encoded.compression)
+        .bind(&raw_content)
+        .bind(
+            entry
+                .summary
+                .as_deref()
+                .map(|summary| Self::clean_feed_html(summary, entry.link.as_deref())),
+        )
+        .bind(read_time)
+        .bind(word_count)
+        .bind(article_id)
+        .execute(&mut *conn)
+        .await?;
+        if let Some(plain) = &encoded.plain {
+            SearchService::index_compressed_content(conn, article_id, plain).await?;
         }
 
-        Ok(RssArticle {
-            id: row.get("id"),
-            feed_id: row.get("feed_id"),
-            title: row.get("title"),
-            link,
-            description: row.get("description"),
-            content,
-            author: row.get("author"),
-            published_at: published_at_str.and_then(|s| {
-                DateTime::parse_from_rfc3339(&s)
-                    .ok()
-                    .map(|dt| dt.with_timezone(&Utc))
-            }),
-            guid: row.get("guid"),
-            is_read: row.get("is_read"),
-            is_starred: row.get("is_starred"),
-            read_time: row.get("read_time"),
-            created_at: DateTime::parse_from_rfc3339(&created_at_str)
-                .unwrap()
-                .with_timezone(&Utc),
-        })
+        info!("文章 {} 的内容在源中已更新", article_id);
+        Ok(true)
     }
 
-    /// 更新文章状态
-    pub async fn update_article(
-        db: &SqlitePool,
-        request: UpdateArticleRequest,
-    ) -> AppResult<String> {
-        // 简化的更新方法
-        if let Some(is_read) = request.is_read {
-            sqlx::query("UPDATE rss_articles SET is_read = ? WHERE id = ?")
-                .bind(is_read)
-                .bind(&request.id)
-                .execute(db)
-                .await?;
+    /// 检查新文章是否与其他源中的文章重复：规范化链接相同，或标题相同（忽略大小写）且发布时间接近
+    /// 只有标题相同不算重复，避免“Weekly Roundup”之类的通用标题被误判
+    /// 疑似重复时只记录duplicate_of，不隐藏也不删除，由用户确认
+    async fn flag_duplicate(
+        conn: &mut SqliteConnection,
+        article_id: &str,
+        feed_id: &str,
+        link: Option<&str>,
+        title: &str,
+        published_at: Option<DateTime<Utc>>,
+    ) -> AppResult<Option<String>> {
+        let mut original = None;
+        if let Some(link_key) = link.map(utils::normalize_article_link) {
+            original = sqlx::query_scalar::<_, String>(
+                "SELECT id FROM rss_articles
+                 WHERE link_key = ? AND id != ? AND feed_id != ? AND duplicate_of IS NULL
+                 ORDER BY created_at, rowid LIMIT 1",
+            )
+            .bind(link_key)
+            .bind(article_id)
+            .bind(feed_id)
+            .fetch_optional(&mut *conn)
+            .await?;
+        }
+        if let (None, Some(published_at)) = (&original, published_at) {
+            let window = chrono::Duration::hours(DUPLICATE_TITLE_WINDOW_HOURS);
+            original = sqlx::query_scalar::<_, String>(
+                "SELECT id FROM rss_articles
+                 WHERE published_at BETWEEN ? AND ? AND id != ? AND feed_id != ? AND duplicate_of IS NULL
+                   AND lower(trim(title)) = lower(trim(?))
+                 ORDER BY created_at, rowid LIMIT 1",
+            )
+            .bind((published_at - window).to_rfc3339())
+            .bind((published_at + window).to_rfc3339())
+            .bind(article_id)
+            .bind(feed_id)
+            .bind(title)
+            .fetch_optional(&mut *conn)
+            .await?;
         }
 
-        if let Some(is_starred) = request.is_starred {
-            sqlx::query("UPDATE rss_articles SET is_starred = ? WHERE id = ?")
-                .bind(is_starred)
-                .bind(&request.id)
-                .execute(db)
+        if let Some(original_id) = &original {
+            sqlx::query("UPDATE rss_articles SET duplicate_of = ? WHERE id = ?")
+                .bind(original_id)
+                .bind(article_id)
+                .execute(&mut *conn)
                 .await?;
         }
-
-        Ok("Article updated successfully".to_string())
+        Ok(original)
     }
 
-    /// 刷新RSS源（带频率限制）
-    pub async fn refresh_feed(db: &SqlitePool, feed_id: String) -> AppResult<String> {
-        // 获取RSS源信息，包括最后更新时间
-        let row = sqlx::query("SELECT url, last_updated FROM rss_feeds WHERE id = ?")
-            .bind(&feed_id)
-            .fetch_one(db)
-            .await
-            .map_err(|_| AppError::feed_not_found(&feed_id))?;
+    /// 获取所有被标记为疑似重复的文章及其原文章
+    pub async fn get_flagged_duplicates(db: &SqlitePool) -> AppResult<Vec<FlaggedDuplicate>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM rss_articles WHERE duplicate_of IS NOT NULL ORDER BY created_at DESC",
+            ARTICLE_COLUMNS
+        ))
+        .fetch_all(db)
+        .await?;
 
-        let url: String = row.get("url");
-        let last_updated_str: Option<String> = row.get("last_updated");
-        
-        // 检查刷新间隔，防止频繁查询
-        const MIN_REFRESH_INTERVAL_MINUTES: i64 = 5; // 最小刷新间隔5分钟
-        
-        if let Some(last_updated_str) = last_updated_str {
-            if let Ok(last_updated) = DateTime::parse_from_rfc3339(&last_updated_str) {
-                let last_updated_utc = last_updated.with_timezone(&Utc);
-                // 获取当前本地时间并转换为UTC时间
-                let now = Local::now().with_timezone(&Utc);
-                let duration_since_last_update = now.signed_duration_since(last_updated_utc);
-                
-                if duration_since_last_update.num_minutes() < MIN_REFRESH_INTERVAL_MINUTES {
-                    let remaining_minutes = MIN_REFRESH_INTERVAL_MINUTES - duration_since_last_update.num_minutes();
-                    return Ok(format!(
-                        "刷新过于频繁，请等待 {} 分钟后再试。为了避免对RSS服务器造成过大负担，每个源最少需要间隔 {} 分钟才能刷新。",
-                        remaining_minutes,
-                        MIN_REFRESH_INTERVAL_MINUTES
-                    ));
-                }
-            }
+        let mut flagged = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let article = Self::article_from_row(row);
+            let original_row = sqlx::query(&format!(
+                "SELECT {} FROM rss_articles WHERE id = ?",
+                ARTICLE_COLUMNS
+            ))
+            .bind(&article.duplicate_of)
+            .fetch_optional(db)
+            .await?;
+            // 原文章已删除时一并返回，便于用户忽略该标记
+            flagged.push(FlaggedDuplicate {
+                article,
+                original: original_row.as_ref().map(Self::article_from_row),
+            });
         }
+        Ok(flagged)
+    }
 
-        // 获取RSS内容并解析
-        // 添加超时设置，避免长时间等待
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()?;
-        let response = client.get(&url).send().await?;
-        let content = response.text().await?;
-
-        let feed = parser::parse(content.as_bytes())?;
-
-        // 获取当前本地时间并转换为UTC时间
-        let now = Local::now().with_timezone(&Utc);
-        let new_articles = Self::save_articles(db, &feed_id, &feed.entries, &now).await?;
-
-        // 更新RSS源的最后更新时间
-        sqlx::query("UPDATE rss_feeds SET last_updated = ?, updated_at = ? WHERE id = ?")
-            .bind(now.to_rfc3339())
-            .bind(now.to_rfc3339())
-            .bind(&feed_id)
-            .execute(db)
+    /// 确认疑似重复：删除该文章
+    pub async fn confirm_duplicate(db: &SqlitePool, article_id: String) -> AppResult<String> {
+        let mut tx = db.begin().await?;
+        let result = sqlx::query("SELECT 1 FROM rss_articles WHERE id = ? AND duplicate_of IS NOT NULL")
+            .bind(&article_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if result.is_none() {
+            return Err(Self::not_flagged_error(db, &article_id).await);
+        }
+        cleanup::record_deleted(&mut tx, &article_id).await?;
+        sqlx::query("DELETE FROM rss_articles WHERE id = ?")
+            .bind(&article_id)
+            .execute(&mut *tx)
             .await?;
+        tx.commit().await?;
+        Ok("Duplicate article deleted".to_string())
+    }
 
-        Ok(format!(
-            "刷新成功！新增 {} 篇文章。",
-            new_articles
-        ))
+    /// 忽略疑似重复：保留文章并清除标记
+    pub async fn dismiss_duplicate(db: &SqlitePool, article_id: String) -> AppResult<String> {
+        let result = sqlx::query(
+            "UPDATE rss_articles SET duplicate_of = NULL WHERE id = ? AND duplicate_of IS NOT NULL",
+        )
+        .bind(&article_id)
+        .execute(db)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(Self::not_flagged_error(db, &article_id).await);
+        }
+        Ok("Duplicate flag dismissed".to_string())
     }
 
-    /// 删除RSS源
-    pub async fn delete_feed(db: &SqlitePool, feed_id: String) -> AppResult<String> {
-        let result = sqlx::query("DELETE FROM rss_feeds WHERE id = ?")
-            .bind(&feed_id)
-            .execute(db)
-            .await?;
+    /// 区分文章不存在和文章未被标记为重复两种情况
+    async fn not_flagged_error(db: &SqlitePool, article_id: &str) -> AppError {
+        match sqlx::query("SELECT id FROM rss_articles WHERE id = ?")
+            .bind(article_id)
+            .fetch_optional(db)
+            .await
+        {
+            Ok(Some(_)) => AppError::validation("该文章没有被标记为疑似重复"),
+            Ok(None) => AppError::article_not_found(article_id),
+            Err(e) => e.into(),
+        }
+    }
 
-        if result.rows_affected() > 0 {
-            Ok("RSS feed deleted successfully".to_string())
-        } else {
-            Err(AppError::feed_not_found(&feed_id))
+    /// 判断源提供的内容是否需要从原文提取全文：内容为空、字数低于阈值或明显被截断
+    /// 阈值为0时只看是否为空，源提供了完整正文时不再抓取网页
+    pub fn is_teaser(content: Option<&str>, threshold: u32) -> bool {
+        match content {
+            None => true,
+            Some(content) if content.trim().is_empty() => true,
+            Some(_) if threshold == 0 => false,
+            Some(content) => utils::count_words(content) < threshold || utils::looks_truncated(content),
         }
     }
 
-    /// 保存文章到数据库
-    pub async fn save_articles(
-        db: &SqlitePool,
-        feed_id: &str,
-        entries: &[feed_rs::model::Entry],
-        now: &DateTime<Utc>,
-    ) -> AppResult<i32> {
-        let mut new_articles = 0;
+    /// 获取摘要判断字数阈值
+    pub async fn get_teaser_threshold(db: &SqlitePool) -> AppResult<u32> {
+        settings::get_u32(db, settings::TEASER_WORD_THRESHOLD, DEFAULT_TEASER_WORD_THRESHOLD).await
+    }
 
-        for entry in entries {
-            let article_id = Uuid::new_v4().to_string();
-            let article_title = entry
-                .title
-                .as_ref()
-                .map(|t| t.content.clone())
-                .unwrap_or_else(|| "Untitled Article".to_string());
-            let link = entry.links.first().map(|l| l.href.clone());
-            let description = entry.summary.as_ref().map(|s| s.content.clone());
-            let mut content = entry
-                .content
-                .as_ref()
-                .map(|c| c.body.clone().unwrap_or_default());
-            let author = entry.authors.first().map(|a| a.name.clone());
-            let published_at = entry.published.map(|p| p.to_rfc3339());
-            let guid = entry.id.clone();
+    /// 设置摘要判断字数阈值，设为0时只在内容为空时提取全文
+    pub async fn set_teaser_threshold(db: &SqlitePool, words: u32) -> AppResult<()> {
+        settings::set_u32(db, settings::TEASER_WORD_THRESHOLD, words).await
+    }
 
-            // 如果RSS中没有完整内容，尝试从链接获取
-            if (content.is_none() || content.as_ref().map_or(true, |c| c.trim().is_empty()))
-                && link.is_some()
-            {
-                if let Some(extracted_content) =
-                    Self::extract_article_content(link.as_ref().unwrap()).await
-                {
-                    content = Some(extracted_content);
-                }
-            }
+    /// 根据已存储的正文（没有正文时用摘要）重新计算文章的字数和阅读时间
+    /// 每批在一个事务中更新，每处理一批调用一次进度回调
+    pub async fn recompute_reading_metrics<F>(
+        db: &SqlitePool,
+        feed_id: Option<String>,
+        on_progress: F,
+    ) -> AppResult<ReadingMetricsProgress>
+    where
+        F: Fn(&ReadingMetricsProgress),
+    {
+        if let Some(feed_id) = &feed_id {
+            Self::get_feed(db, feed_id).await?;
+        }
 
-            // 尝试从RSS entry中提取readTime信息
-            let read_time = Self::extract_read_time(&entry);
+        let total: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM rss_articles WHERE ? IS NULL OR feed_id = ?")
+                .bind(&feed_id)
+                .bind(&feed_id)
+                .fetch_one(db)
+                .await?;
 
-            let result = sqlx::query(
-                "INSERT OR IGNORE INTO rss_articles (id, feed_id, title, link, description, content, author, published_at, guid, read_time, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        let mut progress = ReadingMetricsProgress {
+            processed: 0,
+            total: total as u32,
+        };
+        on_progress(&progress);
+
+        let mut last_rowid: i64 = 0;
+        loop {
+            let rows = sqlx::query(
+                "SELECT rowid, description, content, content_compressed, content_compression FROM rss_articles
+                 WHERE rowid > ? AND (? IS NULL OR feed_id = ?)
+                 ORDER BY rowid LIMIT ?",
             )
-            .bind(&article_id)
-            .bind(feed_id)
-            .bind(&article_title)
-            .bind(&link)
-            .bind(&description)
-            .bind(&content)
-            .bind(&author)
-            .bind(&published_at)
-            .bind(&Some(guid))
-            .bind(&read_time)
-            .bind(now.to_rfc3339())
-            .execute(db)
+            .bind(last_rowid)
+            .bind(&feed_id)
+            .bind(&feed_id)
+            .bind(METRICS_BATCH_SIZE)
+            .fetch_all(db)
             .await?;
 
-            if result.rows_affected() > 0 {
-                new_articles += 1;
+            if rows.is_empty() {
+                break;
+            }
+
+            let mut tx = db.begin().await?;
+            for row in &rows {
+                last_rowid = row.get("rowid");
+                let content = match row.get::<Option<String>, _>("content") {
+                    Some(content) => Some(content),
+                    None => compression::decode(
+                        row.get::<Option<String>, _>("content_compression").as_deref(),
+                        row.get::<Option<Vec<u8>>, _>("content_compressed").as_deref(),
+                    )?,
+                };
+                let word_count = content
+                    .or_else(|| row.get("description"))
+                    .map(|text| utils::count_words(&text));
+
+                sqlx::query("UPDATE rss_articles SET word_count = ?, read_time = ? WHERE rowid = ?")
+                    .bind(word_count)
+                    .bind(word_count.and_then(utils::format_read_time))
+                    .bind(last_rowid)
+                    .execute(&mut *tx)
+                    .await?;
             }
+            tx.commit().await?;
+
+            progress.processed += rows.len() as u32;
+            on_progress(&progress);
         }
 
-        Ok(new_articles)
+        info!("阅读指标重新计算完成，共 {} 篇文章", progress.processed);
+        Ok(progress)
     }
 
-    /// 从RSS entry中提取readTime信息
-    fn extract_read_time(entry: &feed_rs::model::Entry) -> Option<String> {
+    /// 从RSS entry中提取readTime信息，标题中没有时按最终保存的正文（没有正文时为摘要）估算
+    fn extract_read_time(entry: &ParsedEntry, text: Option<&str>) -> Option<String> {
         // 尝试从title或summary中查找阅读时间信息
         if let Some(title) = &entry.title {
             // 使用简单的字符串匹配而不是regex
-            if title.content.contains("min read") {
+            if title.contains("min read") {
                 // 尝试提取数字
-                let words: Vec<&str> = title.content.split_whitespace().collect();
+                let words: Vec<&str> = title.split_whitespace().collect();
                 for (i, word) in words.iter().enumerate() {
                     if word == &"min" && i > 0 {
                         if let Ok(minutes) = words[i-1].parse::<i32>() {
@@ -761,27 +4312,8 @@ impl RssService {
             }
         }
         
-        // 如果没有找到readTime，尝试从内容长度估算
-        if let Some(content) = &entry.content {
-            if let Some(body) = &content.body {
-                let word_count = body.split_whitespace().count();
-                if word_count > 0 {
-                    // 假设平均阅读速度为200词/分钟
-                    let read_minutes = (word_count as f64 / 200.0).ceil() as i32;
-                    return Some(format!("{} min read", read_minutes.max(1)));
-                }
-            }
-        }
-        
-        // 如果内容为空，尝试从摘要估算
-        if let Some(summary) = &entry.summary {
-            let word_count = summary.content.split_whitespace().count();
-            if word_count > 0 {
-                let read_minutes = (word_count as f64 / 200.0).ceil() as i32;
-                return Some(format!("{} min read", read_minutes.max(1)));
-            }
-        }
-        
-        None
+        // 如果没有找到readTime，从去除HTML后的文本估算
+        text.map(utils::estimate_read_time)
+            .filter(|read_time| !read_time.is_empty())
     }
 }
@@ -0,0 +1,22 @@
+use ammonia::Builder;
+use std::collections::HashSet;
+
+/// 文章HTML的白名单清理规则：在ammonia默认规则基础上保留常见的多媒体标签
+/// 脚本、样式、事件属性和javascript:链接都会被移除
+fn builder() -> Builder<'static> {
+    let mut builder = Builder::default();
+    builder
+        .add_tags(&["figure", "figcaption", "picture", "source", "video", "audio"])
+        .add_tag_attributes("img", &["srcset", "loading"])
+        .add_tag_attributes("source", &["src", "srcset", "type", "media"])
+        .add_tag_attributes("video", &["src", "poster", "controls"])
+        .add_tag_attributes("audio", &["src", "controls"])
+        .url_schemes(HashSet::from(["http", "https", "mailto"]))
+        .link_rel(Some("noopener noreferrer"));
+    builder
+}
+
+/// 按白名单清理HTML
+pub fn sanitize_html(html: &str) -> String {
+    builder().clean(html).to_string()
+}
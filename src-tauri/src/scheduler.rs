@@ -0,0 +1,183 @@
+use crate::backup;
+use crate::digest;
+use crate::error::{AppError, AppResult};
+use crate::rss::RssService;
+use crate::settings;
+use chrono::{DateTime, Local, Utc};
+use cron::Schedule;
+use log::{error, info};
+use sqlx::{Row, SqlitePool};
+use tauri::{AppHandle, Emitter};
+use std::str::FromStr;
+
+/// 调度器检查间隔（秒）
+const SCHEDULER_TICK_SECONDS: u64 = 60;
+
+/// 未设置cron表达式时的默认刷新间隔（分钟）
+pub const DEFAULT_REFRESH_INTERVAL_MINUTES: u32 = 60;
+
+/// 可设置的最小刷新间隔（分钟），与手动刷新的频率限制一致
+pub const MIN_REFRESH_INTERVAL_MINUTES: u32 = 5;
+
+/// 解析cron表达式，支持标准5段格式（分 时 日 月 周）以及带秒的6/7段格式
+pub fn parse_cron(expr: &str) -> AppResult<Schedule> {
+    let expr = expr.trim();
+    let normalized = if expr.split_whitespace().count() == 5 {
+        format!("0 {}", expr)
+    } else {
+        expr.to_string()
+    };
+
+    Schedule::from_str(&normalized)
+        .map_err(|e| AppError::validation(format!("无效的cron表达式 '{}': {}", expr, e)))
+}
+
+/// 判断RSS源当前是否需要刷新，没有cron表达式时按interval_minutes间隔刷新
+pub fn is_feed_due(
+    cron: Option<&str>,
+    last_updated: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    interval_minutes: u32,
+) -> bool {
+    let last_updated = match last_updated {
+        Some(last_updated) => last_updated,
+        None => return true,
+    };
+
+    if let Some(expr) = cron {
+        match parse_cron(expr) {
+            Ok(schedule) => {
+                // cron按本地时间解释，取上次刷新之后的下一个触发点
+                return schedule
+                    .after(&last_updated.with_timezone(&Local))
+                    .next()
+                    .is_some_and(|next| next.with_timezone(&Utc) <= now);
+            }
+            Err(e) => {
+                error!("{}，改用刷新间隔", e);
+            }
+        }
+    }
+
+    now.signed_duration_since(last_updated).num_minutes() >= interval_minutes as i64
+}
+
+/// 获取后台自动刷新的间隔（分钟）
+pub async fn get_refresh_interval(db: &SqlitePool) -> AppResult<u32> {
+    settings::get_u32(db, settings::REFRESH_INTERVAL_MINUTES, DEFAULT_REFRESH_INTERVAL_MINUTES).await
+}
+
+/// 设置后台自动刷新的间隔（分钟），调度器每轮重新读取，无需重启即可生效
+pub async fn set_refresh_interval(db: &SqlitePool, minutes: u32) -> AppResult<()> {
+    if minutes < MIN_REFRESH_INTERVAL_MINUTES {
+        return Err(AppError::validation(format!(
+            "刷新间隔不能小于 {} 分钟",
+            MIN_REFRESH_INTERVAL_MINUTES
+        )));
+    }
+    settings::set_u32(db, settings::REFRESH_INTERVAL_MINUTES, minutes).await?;
+    info!("后台自动刷新间隔已设置为 {} 分钟", minutes);
+    Ok(())
+}
+
+/// 暂停或恢复后台自动刷新（持久化，重启后保持）
+pub async fn set_paused(db: &SqlitePool, paused: bool) -> AppResult<()> {
+    settings::set_bool(db, settings::SCHEDULER_PAUSED, paused).await?;
+    info!("后台自动刷新已{}", if paused { "暂停" } else { "恢复" });
+    Ok(())
+}
+
+/// 后台自动刷新是否已暂停
+pub async fn is_paused(db: &SqlitePool) -> AppResult<bool> {
+    settings::get_bool(db, settings::SCHEDULER_PAUSED, false).await
+}
+
+/// 启动后台调度器，定期刷新到期的RSS源，执行自动备份并生成摘要
+pub fn start(db: SqlitePool, app_handle: AppHandle) -> tauri::async_runtime::JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(SCHEDULER_TICK_SECONDS));
+        loop {
+            interval.tick().await;
+
+            // 自动备份不受暂停刷新的影响
+            if let Err(e) = backup::run_auto_backup_if_due(&db).await {
+                error!("数据库自动备份失败: {}", e);
+            }
+
+            // 摘要通过事件交给前端展示通知
+            match digest::generate_digest_if_due(&db).await {
+                Ok(Some(digest)) => {
+                    let _ = app_handle.emit("digest-ready", &digest);
+                }
+                Ok(None) => {}
+                Err(e) => error!("生成摘要失败: {}", e),
+            }
+
+            // 暂停时跳过本轮，手动刷新不受影响
+            match is_paused(&db).await {
+                Ok(true) => continue,
+                Ok(false) => {}
+                Err(e) => error!("读取调度器状态失败: {}", e),
+            }
+
+            if let Err(e) = refresh_due_feeds(&db, &app_handle).await {
+                error!("调度器检查RSS源失败: {}", e);
+            }
+        }
+    })
+}
+
+/// 获取当前到期需要刷新的活跃RSS源
+/// 设置了cron的源按cron判断，否则按源自己的刷新间隔，未设置时使用全局刷新间隔
+pub async fn get_due_feed_ids(db: &SqlitePool, now: DateTime<Utc>) -> AppResult<Vec<String>> {
+    let rows = sqlx::query(
+        "SELECT id, last_updated, cron, refresh_interval_minutes FROM rss_feeds WHERE is_active = 1",
+    )
+    .fetch_all(db)
+    .await?;
+    let interval_minutes = get_refresh_interval(db).await?;
+
+    let mut due_feed_ids = Vec::new();
+    for row in rows {
+        let feed_id: String = row.get("id");
+        let last_updated_str: Option<String> = row.get("last_updated");
+        let cron: Option<String> = row.get("cron");
+        let feed_interval = row
+            .get::<Option<i64>, _>("refresh_interval_minutes")
+            .map_or(interval_minutes, |n| n as u32);
+
+        let last_updated = last_updated_str.and_then(|s| {
+            DateTime::parse_from_rfc3339(&s)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc))
+        });
+
+        if is_feed_due(cron.as_deref(), last_updated, now, feed_interval) {
+            due_feed_ids.push(feed_id);
+        }
+    }
+
+    Ok(due_feed_ids)
+}
+
+/// 刷新所有到期的RSS源，进度通过rss-fetch-progress事件推送
+async fn refresh_due_feeds(db: &SqlitePool, app_handle: &AppHandle) -> AppResult<()> {
+    // 获取当前本地时间并转换为UTC时间
+    let now = Local::now().with_timezone(&Utc);
+    let due_feed_ids = get_due_feed_ids(db, now).await?;
+
+    if due_feed_ids.is_empty() {
+        return Ok(());
+    }
+
+    let results = RssService::refresh_all_feeds(db, app_handle, Some(due_feed_ids), None).await?;
+    for (feed_id, result) in results {
+        match result {
+            Ok(added) => info!("定时刷新RSS源 {}: 新增 {} 篇文章", feed_id, added),
+            Err(e) => error!("定时刷新RSS源 {} 失败: {}", feed_id, e),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,288 @@
+use crate::compression;
+use crate::error::{AppError, AppResult};
+use crate::models::{RssArticle, SearchDirection, SearchFilters, SearchIndexProgress};
+use crate::rss::{RssService, ARTICLE_COLUMNS};
+use log::{info, warn};
+use sqlx::{Row, SqliteConnection, SqlitePool};
+
+/// 重建索引时每批处理的文章数
+const REBUILD_BATCH_SIZE: i64 = 500;
+
+/// 搜索结果默认每页数量
+const DEFAULT_SEARCH_LIMIT: i32 = 50;
+
+/// 按相关度排序的搜索结果（参数: ?1 查询, ?2 feed_id, ?3 is_read, ?4 is_starred）
+const RANKED_RESULTS_CTE: &str = "WITH matches AS (
+         SELECT a.id, a.published_at, bm25(rss_articles_fts) AS score
+         FROM rss_articles_fts
+         JOIN rss_articles a ON a.rowid = rss_articles_fts.rowid
+         WHERE rss_articles_fts MATCH ?1
+           AND (?2 IS NULL OR a.feed_id = ?2)
+           AND (?3 IS NULL OR a.is_read = ?3)
+           AND (?4 IS NULL OR a.is_starred = ?4)
+     ),
+     ranked AS (
+         SELECT id, ROW_NUMBER() OVER (ORDER BY score, published_at DESC, id) AS position
+         FROM matches
+     )";
+
+/// 全文搜索服务
+pub struct SearchService;
+
+impl SearchService {
+    /// 将用户输入转换为安全的FTS5查询：每个词加引号并支持前缀匹配
+    pub fn build_match_query(query: &str) -> AppResult<String> {
+        let terms: Vec<String> = query
+            .split_whitespace()
+            .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+            .collect();
+
+        if terms.is_empty() {
+            return Err(AppError::validation("搜索关键词不能为空"));
+        }
+
+        Ok(terms.join(" "))
+    }
+
+    /// 按相关度搜索文章标题、摘要、正文和作者，支持分页和过滤
+    /// 全文索引不可用时退回LIKE匹配（按发布时间排序）
+    pub async fn search_articles(
+        db: &SqlitePool,
+        query: &str,
+        filters: &SearchFilters,
+        limit: Option<i32>,
+        offset: Option<i32>,
+    ) -> AppResult<Vec<RssArticle>> {
+        let match_query = Self::build_match_query(query)?;
+        let limit = limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+        let offset = offset.unwrap_or(0);
+
+        let result = sqlx::query(&format!(
+            "{}
+             SELECT a.* FROM ranked r
+             JOIN (SELECT {} FROM rss_articles) a ON a.id = r.id
+             ORDER BY r.position LIMIT ?5 OFFSET ?6",
+            RANKED_RESULTS_CTE, ARTICLE_COLUMNS
+        ))
+        .bind(&match_query)
+        .bind(&filters.feed_id)
+        .bind(filters.is_read)
+        .bind(filters.is_starred)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await;
+
+        match result {
+            Ok(rows) => Ok(rows.iter().map(RssService::article_from_row).collect()),
+            Err(e) if Self::is_fts_unavailable(&e) => {
+                warn!("全文索引不可用，改用LIKE搜索: {}", e);
+                Self::search_articles_like(db, query, filters, limit, offset).await
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 全文索引表或FTS5模块不存在
+    fn is_fts_unavailable(error: &sqlx::Error) -> bool {
+        match error {
+            sqlx::Error::Database(e) => {
+                let message = e.message();
+                message.contains("rss_articles_fts") || message.contains("fts5")
+            }
+            _ => false,
+        }
+    }
+
+    /// 不使用全文索引的搜索：所有关键词都需出现在标题、摘要、正文或作者中
+    /// 压缩存储的正文无法在SQL中匹配，这些文章解压后再按关键词筛选，因此分页在筛选之后进行
+    async fn search_articles_like(
+        db: &SqlitePool,
+        query: &str,
+        filters: &SearchFilters,
+        limit: i32,
+        offset: i32,
+    ) -> AppResult<Vec<RssArticle>> {
+        let keywords: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        let terms: Vec<String> = query
+            .split_whitespace()
+            .map(|term| {
+                let escaped = term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+                format!("%{}%", escaped)
+            })
+            .collect();
+
+        let mut sql = format!(
+            "SELECT {} FROM rss_articles
+             WHERE (? IS NULL OR feed_id = ?) AND (? IS NULL OR is_read = ?) AND (? IS NULL OR is_starred = ?)",
+            ARTICLE_COLUMNS
+        );
+        for _ in &terms {
+            sql.push_str(
+                " AND (title LIKE ? ESCAPE '\\' OR description LIKE ? ESCAPE '\\'
+                   OR content LIKE ? ESCAPE '\\' OR author LIKE ? ESCAPE '\\'
+                   OR content_compression IS NOT NULL)",
+            );
+        }
+        sql.push_str(" ORDER BY published_at DESC, created_at DESC");
+
+        let mut query = sqlx::query(&sql)
+            .bind(&filters.feed_id)
+            .bind(&filters.feed_id)
+            .bind(filters.is_read)
+            .bind(filters.is_read)
+            .bind(filters.is_starred)
+            .bind(filters.is_starred);
+        for term in &terms {
+            query = query.bind(term).bind(term).bind(term).bind(term);
+        }
+        let rows = query.fetch_all(db).await?;
+
+        Ok(rows
+            .iter()
+            .map(RssService::article_from_row)
+            .filter(|article| Self::contains_keywords(article, &keywords))
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .collect())
+    }
+
+    /// 每个关键词（已转为小写）都出现在文章的标题、摘要、正文或作者中
+    fn contains_keywords(article: &RssArticle, keywords: &[String]) -> bool {
+        let fields: Vec<String> = [
+            Some(&article.title),
+            article.description.as_ref(),
+            article.content.as_ref(),
+            article.author.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|field| field.to_lowercase())
+        .collect();
+        keywords
+            .iter()
+            .all(|keyword| fields.iter().any(|field| field.contains(keyword.as_str())))
+    }
+
+    /// 获取搜索结果中与当前文章相邻的文章，到达结果两端时返回None
+    pub async fn get_adjacent(
+        db: &SqlitePool,
+        current_id: &str,
+        query: &str,
+        filters: &SearchFilters,
+        direction: SearchDirection,
+    ) -> AppResult<Option<RssArticle>> {
+        let match_query = Self::build_match_query(query)?;
+        let offset: i64 = match direction {
+            SearchDirection::Next => 1,
+            SearchDirection::Previous => -1,
+        };
+
+        let row = sqlx::query(&format!(
+            "{}
+             SELECT {} FROM rss_articles WHERE id = (
+                 SELECT target.id FROM ranked AS current
+                 JOIN ranked AS target ON target.position = current.position + ?5
+                 WHERE current.id = ?6
+             )",
+            RANKED_RESULTS_CTE, ARTICLE_COLUMNS
+        ))
+        .bind(&match_query)
+        .bind(&filters.feed_id)
+        .bind(filters.is_read)
+        .bind(filters.is_starred)
+        .bind(offset)
+        .bind(current_id)
+        .fetch_optional(db)
+        .await?;
+
+        Ok(row.as_ref().map(RssService::article_from_row))
+    }
+
+    /// 将压缩存储的文章正文写入全文索引，替换该文章已有的索引
+    /// content为空的压缩文章不经过触发器索引，需在写入文章后调用
+    pub(crate) async fn index_compressed_content(
+        conn: &mut SqliteConnection,
+        article_id: &str,
+        content: &str,
+    ) -> AppResult<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO rss_articles_fts (rowid, title, description, content, author)
+             SELECT rowid, title, description, ?, author FROM rss_articles WHERE id = ?",
+        )
+        .bind(content)
+        .bind(article_id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    /// 在一个事务中从rss_articles重建全文索引，每处理一批调用一次进度回调
+    pub async fn rebuild_index<F>(db: &SqlitePool, on_progress: F) -> AppResult<SearchIndexProgress>
+    where
+        F: Fn(&SearchIndexProgress),
+    {
+        let total: i64 = sqlx::query("SELECT COUNT(*) as count FROM rss_articles")
+            .fetch_one(db)
+            .await?
+            .get("count");
+
+        let mut progress = SearchIndexProgress {
+            indexed: 0,
+            total: total as u32,
+        };
+        on_progress(&progress);
+
+        let mut tx = db.begin().await?;
+
+        // 清空外部内容表的索引数据
+        sqlx::query("INSERT INTO rss_articles_fts (rss_articles_fts) VALUES ('delete-all')")
+            .execute(&mut *tx)
+            .await?;
+
+        let mut last_rowid: i64 = 0;
+        loop {
+            let rows = sqlx::query(
+                "SELECT rowid, title, description, content, content_compressed, content_compression, author
+                 FROM rss_articles WHERE rowid > ? ORDER BY rowid LIMIT ?",
+            )
+            .bind(last_rowid)
+            .bind(REBUILD_BATCH_SIZE)
+            .fetch_all(&mut *tx)
+            .await?;
+
+            if rows.is_empty() {
+                break;
+            }
+
+            for row in &rows {
+                last_rowid = row.get("rowid");
+                let content = match row.get::<Option<String>, _>("content") {
+                    Some(content) => Some(content),
+                    None => compression::decode(
+                        row.get::<Option<String>, _>("content_compression").as_deref(),
+                        row.get::<Option<Vec<u8>>, _>("content_compressed").as_deref(),
+                    )?,
+                };
+                sqlx::query(
+                    "INSERT INTO rss_articles_fts (rowid, title, description, content, author) VALUES (?, ?, ?, ?, ?)",
+                )
+                .bind(last_rowid)
+                .bind(row.get::<String, _>("title"))
+                .bind(row.get::<Option<String>, _>("description"))
+                .bind(content)
+                .bind(row.get::<Option<String>, _>("author"))
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            progress.indexed += rows.len() as u32;
+            on_progress(&progress);
+        }
+
+        tx.commit().await?;
+
+        info!("全文索引重建完成，共 {} 篇文章", progress.indexed);
+        Ok(progress)
+    }
+}
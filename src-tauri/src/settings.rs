@@ -0,0 +1,127 @@
+use crate::error::AppResult;
+use chrono::{Local, Utc};
+use sqlx::{Row, SqlitePool};
+
+/// 调度器是否暂停
+pub const SCHEDULER_PAUSED: &str = "scheduler_paused";
+
+/// 后台自动刷新的间隔（分钟），对没有设置cron的源生效
+pub const REFRESH_INTERVAL_MINUTES: &str = "refresh_interval_minutes";
+
+/// 新写入的文章内容是否压缩存储
+pub const COMPRESS_CONTENT: &str = "compress_content";
+
+/// 是否额外保留未经处理的原始文章内容（调试用）
+pub const KEEP_RAW_CONTENT: &str = "keep_raw_content";
+
+/// OPML导入后立即刷新时的最大并发数
+pub const IMPORT_CONCURRENCY: &str = "import_concurrency";
+
+/// 字数低于该值的源内容视为摘要，改为从原文提取全文（0表示关闭）
+pub const TEASER_WORD_THRESHOLD: &str = "teaser_word_threshold";
+
+/// 是否每日自动备份数据库
+pub const AUTO_BACKUP_ENABLED: &str = "auto_backup_enabled";
+
+/// 自动备份保留的文件数
+pub const AUTO_BACKUP_KEEP_COUNT: &str = "auto_backup_keep_count";
+
+/// 最近一次自动备份的时间
+pub const AUTO_BACKUP_LAST_AT: &str = "auto_backup_last_at";
+
+/// 保存的文章总数上限（0表示不限制）
+pub const MAX_TOTAL_ARTICLES: &str = "max_total_articles";
+
+/// 是否只通过IPv4抓取
+pub const IPV4_ONLY: &str = "ipv4_only";
+
+/// HTTP请求超时时间（秒）
+pub const HTTP_TIMEOUT_SECS: &str = "http_timeout_secs";
+
+/// 自定义的HTTP User-Agent，为空时使用默认值
+pub const HTTP_USER_AGENT: &str = "http_user_agent";
+
+/// HTTP请求使用的代理地址，为空时使用环境变量中的代理
+pub const HTTP_PROXY: &str = "http_proxy";
+
+/// HTTPS请求使用的代理地址，为空时使用环境变量中的代理
+pub const HTTPS_PROXY: &str = "https_proxy";
+
+/// 代理认证的用户名和密码
+pub const PROXY_USERNAME: &str = "proxy_username";
+pub const PROXY_PASSWORD: &str = "proxy_password";
+
+/// 外部渲染服务地址，静态提取的正文过短时通过它获取渲染后的页面（为空表示不使用）
+pub const RENDER_SERVICE_URL: &str = "render_service_url";
+
+/// 不按RSS源筛选时，是否合并不同源中链接相同的文章（只保留最早的一篇）
+pub const DEDUP_ACROSS_FEEDS: &str = "dedup_across_feeds";
+
+/// 打开文章详情时是否自动标记为已读
+pub const MARK_READ_ON_OPEN: &str = "mark_read_on_open";
+
+/// 保存文章内容的最大字节数，RSS源单独设置的上限优先（0表示不限制）
+pub const MAX_CONTENT_BYTES: &str = "max_content_bytes";
+
+/// RSS源连续刷新失败达到该次数后自动停用（0表示不自动停用）
+pub const FEED_AUTO_DISABLE_FAILURES: &str = "feed_auto_disable_failures";
+
+/// 是否定期生成新文章摘要
+pub const DIGEST_ENABLED: &str = "digest_enabled";
+
+/// 最近一次生成摘要的时间
+pub const DIGEST_LAST_AT: &str = "digest_last_at";
+
+/// 读取设置项
+pub async fn get_setting(db: &SqlitePool, key: &str) -> AppResult<Option<String>> {
+    let row = sqlx::query("SELECT value FROM app_settings WHERE key = ?")
+        .bind(key)
+        .fetch_optional(db)
+        .await?;
+
+    Ok(row.map(|row| row.get("value")))
+}
+
+/// 写入设置项
+pub async fn set_setting(db: &SqlitePool, key: &str, value: &str) -> AppResult<()> {
+    // 获取当前本地时间并转换为UTC时间
+    let now = Local::now().with_timezone(&Utc);
+
+    sqlx::query(
+        "INSERT INTO app_settings (key, value, updated_at) VALUES (?, ?, ?)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+    )
+    .bind(key)
+    .bind(value)
+    .bind(now.to_rfc3339())
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// 读取布尔类型的设置项
+pub async fn get_bool(db: &SqlitePool, key: &str, default: bool) -> AppResult<bool> {
+    Ok(get_setting(db, key)
+        .await?
+        .map(|value| value == "true")
+        .unwrap_or(default))
+}
+
+/// 写入布尔类型的设置项
+pub async fn set_bool(db: &SqlitePool, key: &str, value: bool) -> AppResult<()> {
+    set_setting(db, key, if value { "true" } else { "false" }).await
+}
+
+/// 读取整数类型的设置项，无法解析时使用默认值
+pub async fn get_u32(db: &SqlitePool, key: &str, default: u32) -> AppResult<u32> {
+    Ok(get_setting(db, key)
+        .await?
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default))
+}
+
+/// 写入整数类型的设置项
+pub async fn set_u32(db: &SqlitePool, key: &str, value: u32) -> AppResult<()> {
+    set_setting(db, key, &value.to_string()).await
+}
@@ -0,0 +1,133 @@
+use crate::error::{AppError, AppResult};
+use crate::models::{ExtractionStrategy, SiteRule, SiteRuleRequest};
+use chrono::{DateTime, Local, Utc};
+use log::{info, warn};
+use scraper::Selector;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqlitePool};
+use std::sync::RwLock;
+use url::Url;
+
+/// 站点规则缓存，启动时从数据库加载，修改规则时同步更新
+/// 全文提取的调用方不一定持有数据库连接，因此提取时只读取缓存
+static RULES: RwLock<Vec<SiteRule>> = RwLock::new(Vec::new());
+
+/// 从数据库加载站点规则，在应用启动时调用
+pub async fn load_rules(db: &SqlitePool) -> AppResult<()> {
+    let rules = get_rules(db).await?;
+    if let Ok(mut current) = RULES.write() {
+        *current = rules;
+    }
+    Ok(())
+}
+
+/// 获取所有站点规则
+pub async fn get_rules(db: &SqlitePool) -> AppResult<Vec<SiteRule>> {
+    let rows = sqlx::query("SELECT domain, strategy, selector, created_at FROM site_rules ORDER BY domain")
+        .fetch_all(db)
+        .await?;
+    Ok(rows.iter().map(rule_from_row).collect())
+}
+
+fn rule_from_row(row: &SqliteRow) -> SiteRule {
+    let domain: String = row.get("domain");
+    let strategy: String = row.get("strategy");
+    let created_at_str: String = row.get("created_at");
+    SiteRule {
+        strategy: ExtractionStrategy::parse(&strategy).unwrap_or_else(|| {
+            warn!("站点规则 {} 的提取方式无效: {}，使用readability代替", domain, strategy);
+            ExtractionStrategy::Readability
+        }),
+        domain,
+        selector: row.get("selector"),
+        created_at: DateTime::parse_from_rfc3339(&created_at_str)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now()),
+    }
+}
+
+/// 新增或修改站点规则，同一域名只保留一条规则
+///
+/// 域名可以填写完整网址，选择器方式必须提供有效的CSS选择器，其他方式忽略选择器
+pub async fn set_rule(db: &SqlitePool, request: SiteRuleRequest) -> AppResult<SiteRule> {
+    let domain = normalize_domain(&request.domain)?;
+    let selector = match request.strategy {
+        ExtractionStrategy::Selectors => {
+            let selector = request
+                .selector
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .ok_or_else(|| AppError::validation("选择器提取方式需要填写CSS选择器"))?;
+            Selector::parse(&selector).map_err(|_| AppError::validation(format!("无效的CSS选择器: {}", selector)))?;
+            Some(selector)
+        }
+        _ => None,
+    };
+
+    // 获取当前本地时间并转换为UTC时间
+    let now = Local::now().with_timezone(&Utc);
+    sqlx::query(
+        "INSERT INTO site_rules (domain, strategy, selector, created_at) VALUES (?, ?, ?, ?)
+         ON CONFLICT(domain) DO UPDATE SET strategy = excluded.strategy, selector = excluded.selector",
+    )
+    .bind(&domain)
+    .bind(request.strategy.as_str())
+    .bind(&selector)
+    .bind(now.to_rfc3339())
+    .execute(db)
+    .await?;
+
+    let row = sqlx::query("SELECT domain, strategy, selector, created_at FROM site_rules WHERE domain = ?")
+        .bind(&domain)
+        .fetch_one(db)
+        .await?;
+    let rule = rule_from_row(&row);
+
+    if let Ok(mut rules) = RULES.write() {
+        rules.retain(|r| r.domain != rule.domain);
+        rules.push(rule.clone());
+    }
+    info!("站点规则已保存: {} -> {}", rule.domain, rule.strategy.as_str());
+    Ok(rule)
+}
+
+/// 删除站点规则，返回规则是否存在
+pub async fn delete_rule(db: &SqlitePool, domain: &str) -> AppResult<bool> {
+    let domain = normalize_domain(domain)?;
+    let result = sqlx::query("DELETE FROM site_rules WHERE domain = ?")
+        .bind(&domain)
+        .execute(db)
+        .await?;
+
+    if let Ok(mut rules) = RULES.write() {
+        rules.retain(|r| r.domain != domain);
+    }
+    Ok(result.rows_affected() > 0)
+}
+
+/// 查找适用于网址的站点规则，子域名使用上级域名的规则，多条匹配时使用最具体的规则
+pub fn rule_for_url(url: &str) -> Option<SiteRule> {
+    let parsed = Url::parse(url).ok()?;
+    let host = parsed.host_str()?.to_lowercase();
+    let rules = RULES.read().ok()?;
+    rules
+        .iter()
+        .filter(|rule| host == rule.domain || host.ends_with(&format!(".{}", rule.domain)))
+        .max_by_key(|rule| rule.domain.len())
+        .cloned()
+}
+
+/// 规范化域名，支持填写完整网址
+fn normalize_domain(input: &str) -> AppResult<String> {
+    let input = input.trim().to_lowercase();
+    let candidate = if input.contains("://") {
+        input.clone()
+    } else {
+        format!("https://{}", input.trim_end_matches('/'))
+    };
+    Url::parse(&candidate)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .filter(|host| !host.is_empty())
+        .ok_or_else(|| AppError::validation(format!("无效的域名: {}", input)))
+}
@@ -0,0 +1,129 @@
+use crate::error::{AppError, AppResult};
+use crate::models::{ArticleSort, RssArticle, RssFeed};
+use crate::parser::ParsedEntry;
+use crate::rss::{ArticleFilter, RssService, ARTICLE_COLUMNS, FEED_COLUMNS};
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use std::future::Future;
+
+/// RSS源和文章的存储操作，默认实现为SQLite
+/// RssService中对应的函数通过该trait读写，可以换用其他存储（如测试用的内存存储）
+pub trait Storage: Send + Sync {
+    /// 获取RSS源，include_inactive为false时只返回启用的源
+    fn get_feeds(&self, include_inactive: bool) -> impl Future<Output = AppResult<Vec<RssFeed>>> + Send;
+
+    /// 获取单个RSS源
+    fn get_feed(&self, feed_id: &str) -> impl Future<Output = AppResult<RssFeed>> + Send;
+
+    /// 分页获取文章，可按RSS源、已读和收藏状态筛选
+    fn get_articles(
+        &self,
+        feed_id: Option<String>,
+        limit: Option<i32>,
+        offset: Option<i32>,
+        sort: Option<ArticleSort>,
+        is_read: Option<bool>,
+        is_starred: Option<bool>,
+    ) -> impl Future<Output = AppResult<Vec<RssArticle>>> + Send;
+
+    /// 保存解析得到的文章，返回新增的文章数
+    fn save_articles(
+        &self,
+        feed_id: &str,
+        entries: &[ParsedEntry],
+        now: &DateTime<Utc>,
+    ) -> impl Future<Output = AppResult<i32>> + Send;
+
+    /// 删除RSS源及其文章
+    fn delete_feed(&self, feed_id: String) -> impl Future<Output = AppResult<String>> + Send;
+}
+
+impl Storage for SqlitePool {
+    async fn get_feeds(&self, include_inactive: bool) -> AppResult<Vec<RssFeed>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM rss_feeds WHERE ? OR is_active = 1 ORDER BY created_at DESC",
+            FEED_COLUMNS
+        ))
+        .bind(include_inactive)
+        .fetch_all(self)
+        .await?;
+
+        Ok(rows.iter().map(RssService::feed_from_row).collect())
+    }
+
+    async fn get_feed(&self, feed_id: &str) -> AppResult<RssFeed> {
+        let row = sqlx::query(&format!("SELECT {} FROM rss_feeds WHERE id = ?", FEED_COLUMNS))
+            .bind(feed_id)
+            .fetch_optional(self)
+            .await?
+            .ok_or_else(|| AppError::feed_not_found(feed_id))?;
+
+        Ok(RssService::feed_from_row(&row))
+    }
+
+    async fn get_articles(
+        &self,
+        feed_id: Option<String>,
+        limit: Option<i32>,
+        offset: Option<i32>,
+        sort: Option<ArticleSort>,
+        is_read: Option<bool>,
+        is_starred: Option<bool>,
+    ) -> AppResult<Vec<RssArticle>> {
+        let limit = limit.unwrap_or(50);
+        let offset = offset.unwrap_or(0);
+        // 没有修改时间的文章排在最后
+        let order_by = match sort.unwrap_or_default() {
+            ArticleSort::Published => "published_at DESC, created_at DESC",
+            ArticleSort::Updated => "updated_at DESC, published_at DESC, created_at DESC",
+        };
+
+        let filter = ArticleFilter::new(self, feed_id.as_deref(), is_read, is_starred).await?;
+        let sql = format!(
+            "SELECT {} FROM rss_articles WHERE {} ORDER BY {} LIMIT ? OFFSET ?",
+            ARTICLE_COLUMNS,
+            filter.where_clause(),
+            order_by
+        );
+        let rows = filter
+            .bind(sqlx::query(&sql))
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(self)
+            .await?;
+
+        Ok(rows.iter().map(RssService::article_from_row).collect())
+    }
+
+    async fn save_articles(
+        &self,
+        feed_id: &str,
+        entries: &[ParsedEntry],
+        now: &DateTime<Utc>,
+    ) -> AppResult<i32> {
+        RssService::save_articles_with_progress(self, feed_id, entries, now, None).await
+    }
+
+    async fn delete_feed(&self, feed_id: String) -> AppResult<String> {
+        // 不依赖外键级联，显式删除文章，和RSS源在同一事务中提交
+        let mut tx = self.begin().await?;
+        sqlx::query("DELETE FROM rss_articles WHERE feed_id = ?")
+            .bind(&feed_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM deleted_articles WHERE feed_id = ?")
+            .bind(&feed_id)
+            .execute(&mut *tx)
+            .await?;
+        let result = sqlx::query("DELETE FROM rss_feeds WHERE id = ?")
+            .bind(&feed_id)
+            .execute(&mut *tx)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::feed_not_found(&feed_id));
+        }
+        tx.commit().await?;
+        Ok("RSS feed deleted successfully".to_string())
+    }
+}
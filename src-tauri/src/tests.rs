@@ -2,20 +2,37 @@
 mod tests {
     use crate::rss::RssService;
     use sqlx::SqlitePool;
-    use tempfile::NamedTempFile;
+    use tempfile::TempDir;
 
-    async fn setup_test_db() -> SqlitePool {
-        let temp_file = NamedTempFile::new().unwrap();
-        let db_path = temp_file.path().to_str().unwrap();
+    /// 修改network模块全局设置的测试持有该锁，避免并行执行时互相覆盖
+    static NETWORK_SETTINGS_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
 
-        let pool = SqlitePool::connect(&format!("sqlite:{}", db_path))
+    /// 数据库文件放在临时目录中，连接池之后新建的连接仍会打开同一个文件
+    /// 返回的TempDir需要和连接池一起保留，测试结束时删除目录
+    async fn setup_test_db() -> (SqlitePool, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+
+        let pool = SqlitePool::connect(&format!("sqlite:{}?mode=rwc", db_path.to_str().unwrap()))
             .await
             .unwrap();
 
         // 运行迁移
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
 
-        pool
+        (pool, temp_dir)
+    }
+
+    async fn insert_test_feed(db: &SqlitePool, id: &str, url: &str) {
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query("INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES (?, 'Feed', ?, ?, ?)")
+            .bind(id)
+            .bind(url)
+            .bind(&now)
+            .bind(&now)
+            .execute(db)
+            .await
+            .unwrap();
     }
 
     #[tokio::test]
@@ -43,7 +60,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_article_content_with_extraction() {
-        let db = setup_test_db().await;
+        let (db, _db_dir) = setup_test_db().await;
 
         // 创建一个测试RSS源
         let feed_id = "test-feed-id";
@@ -207,4 +224,4618 @@ mod tests {
             assert!(content.contains("third paragraph"), "应该包含第三段");
         }
     }
+
+    #[test]
+    fn test_parse_feed_selects_parser_by_content() {
+        use crate::parser::{parse_feed, select_parser};
+
+        let rss_fixture = r#"<?xml version="1.0"?>
+        <rss version="2.0">
+            <channel>
+                <title>Fixture RSS</title>
+                <link>https://example.com</link>
+                <description>RSS fixture</description>
+                <item>
+                    <title>First post</title>
+                    <link>https://example.com/1</link>
+                    <guid>rss-1</guid>
+                    <description>Hello from RSS</description>
+                </item>
+            </channel>
+        </rss>"#;
+
+        let json_fixture = r#"{
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "Fixture JSON",
+            "home_page_url": "https://example.org",
+            "items": [
+                {
+                    "id": 42,
+                    "url": "https://example.org/42",
+                    "title": "JSON post",
+                    "content_html": "<p>Hello from JSON</p>",
+                    "date_published": "2024-01-02T03:04:05Z"
+                }
+            ]
+        }"#;
+
+        assert_eq!(select_parser(None, rss_fixture.as_bytes()).name(), "feed-rs");
+        assert_eq!(select_parser(None, json_fixture.as_bytes()).name(), "json-feed");
+        assert_eq!(
+            select_parser(Some("application/feed+json"), b"").name(),
+            "json-feed"
+        );
+
+        let rss = parse_feed(Some("application/rss+xml"), rss_fixture.as_bytes()).unwrap();
+        assert_eq!(rss.title.as_deref(), Some("Fixture RSS"));
+        assert_eq!(rss.format, "RSS 2.0");
+        assert_eq!(rss.entries.len(), 1);
+        assert_eq!(rss.entries[0].id, "rss-1");
+        assert_eq!(rss.entries[0].link.as_deref(), Some("https://example.com/1"));
+
+        let json = parse_feed(None, json_fixture.as_bytes()).unwrap();
+        assert_eq!(json.title.as_deref(), Some("Fixture JSON"));
+        assert_eq!(json.format, "JSON Feed");
+        assert_eq!(json.website_url.as_deref(), Some("https://example.org"));
+        assert_eq!(json.entries[0].id, "42");
+        assert_eq!(json.entries[0].content.as_deref(), Some("<p>Hello from JSON</p>"));
+        assert!(json.entries[0].published.is_some());
+    }
+
+    #[test]
+    fn test_cron_schedule_due() {
+        use crate::scheduler::{is_feed_due, parse_cron, DEFAULT_REFRESH_INTERVAL_MINUTES};
+        use chrono::{Duration, Utc};
+
+        assert!(parse_cron("0 8,18 * * *").is_ok());
+        assert!(parse_cron("0 0 8 * * *").is_ok());
+        assert!(parse_cron("not a cron").is_err());
+
+        let now = Utc::now();
+        let interval = DEFAULT_REFRESH_INTERVAL_MINUTES;
+        // 从未刷新过的源总是到期
+        assert!(is_feed_due(None, None, now, interval));
+        // 没有cron时按刷新间隔判断
+        assert!(!is_feed_due(None, Some(now - Duration::minutes(5)), now, interval));
+        assert!(is_feed_due(None, Some(now - Duration::hours(2)), now, interval));
+        assert!(is_feed_due(None, Some(now - Duration::minutes(15)), now, 10));
+        // 每分钟触发的cron在两分钟后必然到期
+        assert!(is_feed_due(Some("* * * * *"), Some(now - Duration::minutes(2)), now, interval));
+        // 每年一次的cron在刚刷新后不会到期
+        assert!(!is_feed_due(Some("0 0 1 1 *"), Some(now), now, 1));
+    }
+
+    #[test]
+    fn test_compute_feed_cadence() {
+        use chrono::{TimeZone, Utc};
+
+        // 2024-01-01 是周一，每两天发一篇，共8篇
+        let published: Vec<_> = (0..8)
+            .map(|i| Utc.with_ymd_and_hms(2024, 1, 1 + i * 2, 9, 0, 0).unwrap())
+            .collect();
+
+        let cadence = RssService::compute_feed_cadence("feed".to_string(), published);
+        assert_eq!(cadence.total_articles, 8);
+        assert_eq!(cadence.median_gap_hours, Some(48.0));
+        assert_eq!(cadence.average_gap_hours, Some(48.0));
+        assert!((cadence.posts_per_week - 8.0 / 2.0).abs() < 1e-9);
+        assert_eq!(cadence.posts_by_weekday.iter().sum::<u32>(), 8);
+        assert_eq!(cadence.posts_by_weekday[0], 2);
+
+        let empty = RssService::compute_feed_cadence("feed".to_string(), Vec::new());
+        assert_eq!(empty.total_articles, 0);
+        assert_eq!(empty.median_gap_hours, None);
+        assert_eq!(empty.posts_per_week, 0.0);
+    }
+
+    #[test]
+    fn test_parse_opml_nested_outlines() {
+        use crate::opml::parse_opml;
+
+        let opml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <opml version="2.0">
+            <head><title>Subscriptions</title></head>
+            <body>
+                <outline text="Tech">
+                    <outline text="Example" title="Example Blog" type="rss"
+                             xmlUrl="https://example.com/feed.xml" htmlUrl="https://example.com"/>
+                    <outline text="No URL"/>
+                </outline>
+                <outline text="Top level" xmlUrl="https://example.org/rss"/>
+            </body>
+        </opml>"#;
+
+        let parsed = parse_opml(opml).unwrap();
+        let outlines = parsed.outlines;
+        assert_eq!(outlines.len(), 2);
+        assert_eq!(outlines[0].title.as_deref(), Some("Example Blog"));
+        assert_eq!(outlines[0].xml_url, "https://example.com/feed.xml");
+        assert_eq!(outlines[0].html_url.as_deref(), Some("https://example.com"));
+        assert_eq!(outlines[1].title.as_deref(), Some("Top level"));
+        assert_eq!(parsed.malformed, vec!["No URL".to_string()]);
+
+        assert!(parse_opml("<rss></rss>").is_err());
+        assert!(parse_opml("not xml").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_pause_persists() {
+        use crate::scheduler;
+
+        let (db, _db_dir) = setup_test_db().await;
+
+        assert!(!scheduler::is_paused(&db).await.unwrap());
+        scheduler::set_paused(&db, true).await.unwrap();
+        assert!(scheduler::is_paused(&db).await.unwrap());
+        scheduler::set_paused(&db, false).await.unwrap();
+        assert!(!scheduler::is_paused(&db).await.unwrap());
+    }
+
+    #[test]
+    fn test_app_error_serializes_code_and_message() {
+        use crate::error::AppError;
+
+        let value = serde_json::to_value(AppError::not_a_feed("https://example.com", "bad xml")).unwrap();
+        assert_eq!(value["code"], "not_a_feed");
+        assert!(value["message"].as_str().unwrap().contains("https://example.com"));
+
+        assert_eq!(AppError::feed_already_exists("u").code(), "feed_already_exists");
+        assert_eq!(AppError::invalid_rss_url("u").code(), "invalid_url");
+        assert_eq!(AppError::http_status("u", 404).code(), "http_status");
+        assert_eq!(AppError::feed_unreachable("u", "dns").code(), "feed_unreachable");
+    }
+
+    #[test]
+    fn test_compute_reading_streak() {
+        use chrono::NaiveDate;
+
+        let day = |d: u32| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+
+        // 1-3号连续3天，5-6号连续2天（同一天多次阅读只算一次）
+        let dates = vec![day(1), day(2), day(2), day(3), day(5), day(6)];
+
+        let streak = RssService::compute_reading_streak(dates.clone(), day(6));
+        assert_eq!(streak.current_days, 2);
+        assert_eq!(streak.longest_days, 3);
+        assert_eq!(streak.last_read_date, Some(day(6)));
+
+        // 今天还没读，昨天读过，连续天数保留
+        assert_eq!(RssService::compute_reading_streak(dates.clone(), day(7)).current_days, 2);
+        // 中断一天以上则清零
+        assert_eq!(RssService::compute_reading_streak(dates, day(8)).current_days, 0);
+
+        let empty = RssService::compute_reading_streak(Vec::new(), day(1));
+        assert_eq!(empty.current_days, 0);
+        assert_eq!(empty.longest_days, 0);
+    }
+
+    #[tokio::test]
+    async fn test_set_feed_alternates() {
+        let (db, _db_dir) = setup_test_db().await;
+
+        let feed_id = "alt-feed-id";
+        sqlx::query(
+            "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES (?, ?, ?, ?, ?)"
+        )
+        .bind(feed_id)
+        .bind("Mirrored Feed")
+        .bind("https://primary.example.com/rss")
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(&db)
+        .await
+        .unwrap();
+
+        // 主地址和重复地址会被忽略
+        let feed = RssService::set_feed_alternates(
+            &db,
+            feed_id.to_string(),
+            vec![
+                "https://mirror1.example.com/rss".to_string(),
+                "https://primary.example.com/rss".to_string(),
+                "https://mirror1.example.com/rss".to_string(),
+                "https://mirror2.example.com/rss".to_string(),
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            feed.alternate_urls,
+            vec![
+                "https://mirror1.example.com/rss".to_string(),
+                "https://mirror2.example.com/rss".to_string(),
+            ]
+        );
+
+        let invalid =
+            RssService::set_feed_alternates(&db, feed_id.to_string(), vec!["ftp://x".to_string()]).await;
+        assert!(invalid.is_err());
+
+        let cleared = RssService::set_feed_alternates(&db, feed_id.to_string(), Vec::new())
+            .await
+            .unwrap();
+        assert!(cleared.alternate_urls.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_search_index() {
+        use crate::search::SearchService;
+        use sqlx::Row;
+
+        let (db, _db_dir) = setup_test_db().await;
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        for (id, title) in [("a1", "Rust async runtime"), ("a2", "Gardening tips")] {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, guid, created_at) VALUES (?, 'f', ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(title)
+            .bind(id)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let calls = std::cell::Cell::new(0);
+        let progress = SearchService::rebuild_index(&db, |_| calls.set(calls.get() + 1))
+            .await
+            .unwrap();
+        assert_eq!(progress.indexed, 2);
+        assert_eq!(progress.total, 2);
+        assert!(calls.get() >= 2);
+
+        let count: i64 = sqlx::query(
+            "SELECT COUNT(*) as count FROM rss_articles_fts WHERE rss_articles_fts MATCH 'rust'",
+        )
+        .fetch_one(&db)
+        .await
+        .unwrap()
+        .get("count");
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_build_match_query_quotes_terms() {
+        use crate::search::SearchService;
+
+        assert_eq!(
+            SearchService::build_match_query("rust  async").unwrap(),
+            "\"rust\"* \"async\"*"
+        );
+        // 引号和FTS运算符不会破坏查询语法
+        assert_eq!(
+            SearchService::build_match_query("say \"hi\" OR").unwrap(),
+            "\"say\"* \"\"\"hi\"\"\"* \"OR\"*"
+        );
+        assert!(SearchService::build_match_query("   ").is_err());
+    }
+
+    #[test]
+    fn test_normalize_feed_url() {
+        use crate::utils::normalize_feed_url;
+
+        assert_eq!(
+            normalize_feed_url("https://www.Example.com/feed/"),
+            normalize_feed_url("http://example.com/feed#top")
+        );
+        assert_ne!(
+            normalize_feed_url("https://example.com/feed?lang=en"),
+            normalize_feed_url("https://example.com/feed?lang=zh")
+        );
+        assert_eq!(normalize_feed_url("https://example.com:8080/rss"), "example.com:8080/rss");
+    }
+
+    #[tokio::test]
+    async fn test_compress_existing_content() {
+        use crate::compression;
+        use crate::models::SearchFilters;
+        use crate::search::SearchService;
+
+        let (db, _db_dir) = setup_test_db().await;
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+
+        let long_content = "<p>Lorem ipsum dolor sit amet.</p>".repeat(100);
+        for (id, content) in [("long", long_content.as_str()), ("short", "<p>short</p>")] {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, content, guid, created_at) VALUES (?, 'f', ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(content)
+            .bind(id)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let report = compression::compress_existing_content(&db).await.unwrap();
+        assert_eq!(report.compressed_articles, 1);
+        assert!(report.bytes_saved > 0);
+        assert_eq!(report.bytes_before - report.bytes_after, report.bytes_saved);
+
+        // 压缩的内容在返回时透明解压
+        let stored: Option<String> = sqlx::query_scalar("SELECT content FROM rss_articles WHERE id = 'long'")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert!(stored.is_none());
+        let listed = RssService::get_articles(&db, None, None, None, None, None, None).await.unwrap();
+        assert_eq!(listed.iter().find(|a| a.id == "long").unwrap().content.as_deref(), Some(long_content.as_str()));
+        let article = RssService::get_article_content(&db, "long".to_string()).await.unwrap();
+        assert_eq!(article.content.as_deref(), Some(long_content.as_str()));
+
+        // 压缩后的正文仍在全文索引中
+        let found = SearchService::search_articles(&db, "ipsum", &SearchFilters::default(), None, None).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, "long");
+        SearchService::rebuild_index(&db, |_| {}).await.unwrap();
+        let found = SearchService::search_articles(&db, "ipsum", &SearchFilters::default(), None, None).await.unwrap();
+        assert_eq!(found.len(), 1);
+        // 没有全文索引时LIKE搜索解压后匹配
+        sqlx::query("DROP TABLE rss_articles_fts").execute(&db).await.unwrap();
+        let fallback = SearchService::search_articles(&db, "IPSUM", &SearchFilters::default(), None, None).await.unwrap();
+        assert_eq!(fallback.len(), 1);
+        assert_eq!(fallback[0].id, "long");
+
+        // 再次执行没有需要迁移的内容
+        let again = compression::compress_existing_content(&db).await.unwrap();
+        assert_eq!(again.compressed_articles, 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_article_raw_content_requires_debug_option() {
+        use crate::settings;
+
+        let (db, _db_dir) = setup_test_db().await;
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        sqlx::query(
+            "INSERT INTO rss_articles (id, feed_id, title, content, content_raw, guid, created_at) VALUES ('a', 'f', 'A', '<p>clean</p>', '<p onclick=\"x()\">raw</p>', 'a', ?)"
+        )
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(&db)
+        .await
+        .unwrap();
+
+        // 未开启调试选项时拒绝访问
+        assert!(RssService::get_article_raw_content(&db, "a".to_string()).await.is_err());
+
+        settings::set_bool(&db, settings::KEEP_RAW_CONTENT, true).await.unwrap();
+        let raw = RssService::get_article_raw_content(&db, "a".to_string()).await.unwrap();
+        assert_eq!(raw.as_deref(), Some("<p onclick=\"x()\">raw</p>"));
+
+        let missing = RssService::get_article_raw_content(&db, "missing".to_string()).await;
+        assert_eq!(missing.unwrap_err().code(), "article_not_found");
+    }
+
+    #[tokio::test]
+    async fn test_feed_timezone_applies_to_naive_dates() {
+        use crate::parser::parse_feed;
+        use sqlx::Row;
+
+        let (db, _db_dir) = setup_test_db().await;
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+
+        assert!(RssService::set_feed_timezone(&db, "f".to_string(), Some("Mars/Olympus".to_string()))
+            .await
+            .is_err());
+        RssService::set_feed_timezone(&db, "f".to_string(), Some("Asia/Shanghai".to_string()))
+            .await
+            .unwrap();
+        let feed = RssService::get_feed(&db, "f").await.unwrap();
+        assert_eq!(feed.timezone.as_deref(), Some("Asia/Shanghai"));
+
+        // 不带时区的发布时间按源的时区解释，带偏移的保持不变
+        let json = r#"{
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "Local times",
+            "items": [
+                { "id": "1", "title": "Morning", "content_html": "<p>x</p>", "date_published": "2024-05-01T08:00:00" },
+                { "id": "2", "title": "Offset", "content_html": "<p>x</p>", "date_published": "2024-05-01T08:00:00+02:00" }
+            ]
+        }"#;
+        let rss = r#"<?xml version="1.0"?>
+            <rss version="2.0"><channel><title>Local times</title>
+                <item><guid>3</guid><title>Naive</title><description>x</description><pubDate>Wed, 01 May 2024 09:00:00</pubDate></item>
+                <item><guid>4</guid><title>Zoned</title><description>x</description><pubDate>Wed, 01 May 2024 09:00:00 GMT</pubDate></item>
+            </channel></rss>"#;
+        for body in [json, rss] {
+            let parsed = parse_feed(None, body.as_bytes()).unwrap();
+            RssService::save_articles(&db, "f", &parsed.entries, &chrono::Utc::now())
+                .await
+                .unwrap();
+        }
+
+        let published: Vec<(String, String)> =
+            sqlx::query("SELECT guid, published_at FROM rss_articles WHERE feed_id = 'f' ORDER BY guid")
+                .fetch_all(&db)
+                .await
+                .unwrap()
+                .iter()
+                .map(|row| (row.get("guid"), row.get("published_at")))
+                .collect();
+        assert_eq!(
+            published,
+            [
+                ("1".to_string(), "2024-05-01T00:00:00+00:00".to_string()),
+                ("2".to_string(), "2024-05-01T06:00:00+00:00".to_string()),
+                ("3".to_string(), "2024-05-01T01:00:00+00:00".to_string()),
+                ("4".to_string(), "2024-05-01T09:00:00+00:00".to_string()),
+            ]
+        );
+
+        RssService::set_feed_timezone(&db, "f".to_string(), None).await.unwrap();
+        assert!(RssService::get_feed(&db, "f").await.unwrap().timezone.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_inbox_groups_unread_by_feed() {
+        let (db, _db_dir) = setup_test_db().await;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        for (id, active) in [("old", true), ("new", true), ("read", true), ("inactive", false)] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, is_active, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(format!("https://example.com/{}", id))
+            .bind(active)
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let articles = [
+            ("o1", "old", "2024-01-01T00:00:00+00:00", false),
+            ("o2", "old", "2024-01-02T00:00:00+00:00", false),
+            ("o3", "old", "2024-01-03T00:00:00+00:00", false),
+            ("n1", "new", "2024-02-01T00:00:00+00:00", false),
+            ("r1", "read", "2024-03-01T00:00:00+00:00", true),
+            ("i1", "inactive", "2024-04-01T00:00:00+00:00", false),
+        ];
+        for (id, feed_id, published_at, is_read) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, published_at, guid, is_read, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(feed_id)
+            .bind(id)
+            .bind(published_at)
+            .bind(id)
+            .bind(is_read)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let inbox = RssService::get_inbox(&db, Some(2)).await.unwrap();
+        let feed_ids: Vec<&str> = inbox.iter().map(|g| g.feed.id.as_str()).collect();
+        assert_eq!(feed_ids, vec!["new", "old"]);
+
+        let old_ids: Vec<&str> = inbox[1].articles.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(old_ids, vec!["o3", "o2"]);
+    }
+
+    #[tokio::test]
+    async fn test_import_concurrency_setting() {
+        let (db, _db_dir) = setup_test_db().await;
+
+        assert_eq!(
+            RssService::get_import_concurrency(&db).await.unwrap(),
+            crate::rss::DEFAULT_REFRESH_CONCURRENCY as u32
+        );
+        RssService::set_import_concurrency(&db, 12).await.unwrap();
+        assert_eq!(RssService::get_import_concurrency(&db).await.unwrap(), 12);
+
+        assert!(RssService::set_import_concurrency(&db, 0).await.is_err());
+        assert!(RssService::set_import_concurrency(&db, 1000).await.is_err());
+        assert_eq!(RssService::get_import_concurrency(&db).await.unwrap(), 12);
+    }
+
+    #[test]
+    fn test_declared_charset() {
+        use crate::parser::declared_charset;
+
+        assert_eq!(
+            declared_charset(Some("application/rss+xml; charset=\"GBK\""), b"").as_deref(),
+            Some("GBK")
+        );
+        // 响应头优先于XML声明
+        assert_eq!(
+            declared_charset(Some("text/xml; charset=utf-8"), b"<?xml version='1.0' encoding='ISO-8859-1'?>").as_deref(),
+            Some("utf-8")
+        );
+        assert_eq!(
+            declared_charset(Some("text/xml"), b"<?xml version='1.0' encoding='ISO-8859-1'?><rss/>").as_deref(),
+            Some("ISO-8859-1")
+        );
+        assert_eq!(declared_charset(None, b"<?xml version=\"1.0\"?><rss/>"), None);
+        assert_eq!(declared_charset(None, b"{\"version\": \"1\"}"), None);
+    }
+
+    #[test]
+    fn test_count_words() {
+        use crate::utils::count_words;
+
+        assert_eq!(count_words("<p>Hello <b>brave</b> new world</p>"), 4);
+        // 词内的标点不拆分单词
+        assert_eq!(count_words("<p>It's 2024</p>"), 2);
+        // 中文按字计数
+        assert_eq!(count_words("<p>你好世界 hello</p>"), 5);
+        assert_eq!(count_words(""), 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_longform_unread() {
+        let (db, _db_dir) = setup_test_db().await;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        for feed_id in ["f", "g"] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(feed_id)
+            .bind(feed_id)
+            .bind(format!("https://example.com/{}", feed_id))
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let articles = [
+            ("essay", "f", 3000, false, "2024-01-01T00:00:00+00:00"),
+            ("newer-essay", "f", 2000, false, "2024-01-02T00:00:00+00:00"),
+            ("blurb", "f", 120, false, "2024-01-03T00:00:00+00:00"),
+            ("read-essay", "f", 5000, true, "2024-01-04T00:00:00+00:00"),
+            ("other-essay", "g", 4000, false, "2024-01-05T00:00:00+00:00"),
+        ];
+        for (id, feed_id, word_count, is_read, published_at) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, guid, word_count, is_read, published_at, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(feed_id)
+            .bind(id)
+            .bind(id)
+            .bind(word_count)
+            .bind(is_read)
+            .bind(published_at)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let longform = RssService::get_longform_unread(&db, Some(1000), None, Some("f".to_string()))
+            .await
+            .unwrap();
+        let ids: Vec<&str> = longform.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["newer-essay", "essay"]);
+        assert_eq!(longform[0].word_count, Some(2000));
+
+        let all = RssService::get_longform_unread(&db, Some(1000), Some(1), None).await.unwrap();
+        assert_eq!(all[0].id, "other-essay");
+    }
+
+    #[tokio::test]
+    async fn test_transfer_article_state() {
+        let (db, _db_dir) = setup_test_db().await;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        sqlx::query(
+            "INSERT INTO rss_articles (id, feed_id, title, guid, is_read, is_starred, read_at, reader_prefs, created_at)
+             VALUES ('dup', 'f', 'Dup', 'dup', 1, 1, ?, '{\"reader_theme\":\"sepia\",\"font_size\":18}', ?)"
+        )
+        .bind(&now)
+        .bind(&now)
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO rss_articles (id, feed_id, title, guid, created_at) VALUES ('keep', 'f', 'Keep', 'keep', ?)"
+        )
+        .bind(&now)
+        .execute(&db)
+        .await
+        .unwrap();
+
+        RssService::transfer_article_state(&db, "dup".to_string(), "keep".to_string())
+            .await
+            .unwrap();
+
+        let kept = RssService::get_articles(&db, None, None, None, None, None, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|a| a.id == "keep")
+            .unwrap();
+        assert!(kept.is_read);
+        assert!(kept.is_starred);
+        assert!(kept.read_at.is_some());
+        assert_eq!(kept.reader_prefs.unwrap().font_size, Some(18));
+
+        // 合并而不是覆盖：未读、未收藏的源文章不会把目标文章改回未读或取消收藏
+        sqlx::query(
+            "INSERT INTO rss_articles (id, feed_id, title, guid, created_at) VALUES ('fresh', 'f', 'Fresh', 'fresh', ?)"
+        )
+        .bind(&now)
+        .execute(&db)
+        .await
+        .unwrap();
+        RssService::transfer_article_state(&db, "fresh".to_string(), "keep".to_string())
+            .await
+            .unwrap();
+        let kept = RssService::get_article_content(&db, "keep".to_string()).await.unwrap();
+        assert!(kept.is_read);
+        assert!(kept.is_starred);
+        assert!(kept.read_at.is_some());
+
+        let missing =
+            RssService::transfer_article_state(&db, "dup".to_string(), "missing".to_string()).await;
+        assert_eq!(missing.unwrap_err().code(), "article_not_found");
+        assert!(RssService::transfer_article_state(&db, "dup".to_string(), "dup".to_string())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_auto_backup_rotation() {
+        use crate::backup;
+
+        let (db, _db_dir) = setup_test_db().await;
+        let dir = tempfile::tempdir().unwrap();
+
+        // 非自动备份文件不会被清理
+        std::fs::write(dir.path().join("manual.db"), b"").unwrap();
+        for name in ["auto-20240101-000000.db", "auto-20240102-000000.db", "auto-20240103-000000.db"] {
+            std::fs::write(dir.path().join(name), b"").unwrap();
+        }
+
+        let path = backup::create_auto_backup(&db, dir.path(), 2).await.unwrap();
+        assert!(path.exists());
+
+        let mut names: Vec<String> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names.len(), 3);
+        assert_eq!(names[0], "auto-20240103-000000.db");
+        assert_eq!(names[2], "manual.db");
+
+        // 备份文件是可用的数据库
+        let restored = SqlitePool::connect(&format!("sqlite:{}", path.display())).await.unwrap();
+        sqlx::query("SELECT COUNT(*) FROM rss_feeds").fetch_one(&restored).await.unwrap();
+
+        assert!(backup::set_auto_backup(&db, true, 0).await.is_err());
+    }
+
+    #[test]
+    fn test_compute_reading_heatmap() {
+        use chrono::NaiveDate;
+
+        let at = |d: u32, h: u32| NaiveDate::from_ymd_opt(2024, 3, d).unwrap().and_hms_opt(h, 30, 0).unwrap();
+
+        // 2024-03-04 是周一，2024-03-10 是周日
+        let times = vec![at(4, 8), at(4, 8), at(4, 22), at(10, 0), at(11, 8)];
+        let heatmap = RssService::compute_reading_heatmap(&times);
+
+        assert_eq!(heatmap.total, 5);
+        assert_eq!(heatmap.counts[0][8], 3);
+        assert_eq!(heatmap.counts[0][22], 1);
+        assert_eq!(heatmap.counts[6][0], 1);
+        assert_eq!(heatmap.counts.iter().flatten().sum::<u32>(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_feed_title_strip_prefix() {
+        use crate::parser::ParsedEntry;
+        use sqlx::Row;
+
+        let (db, _db_dir) = setup_test_db().await;
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+
+        assert!(RssService::set_feed_title_strip(&db, "f".to_string(), Some("(".to_string()), true)
+            .await
+            .is_err());
+        // 普通文本中的正则特殊字符按字面匹配
+        RssService::set_feed_title_strip(&db, "f".to_string(), Some("(".to_string()), false)
+            .await
+            .unwrap();
+        assert!(RssService::get_feed(&db, "f").await.unwrap().title_strip_prefix.is_some());
+        RssService::set_feed_title_strip(&db, "f".to_string(), Some("My Blog:".to_string()), false)
+            .await
+            .unwrap();
+
+        let entry = |id: &str, title: &str| ParsedEntry {
+            id: id.to_string(),
+            title: Some(title.to_string()),
+            content: Some("<p>body</p>".to_string()),
+            ..Default::default()
+        };
+        let entries = vec![
+            entry("1", "My Blog: Actual Title"),
+            entry("2", "Unprefixed"),
+            entry("3", "My Blog:"),
+        ];
+        RssService::save_articles(&db, "f", &entries, &chrono::Utc::now())
+            .await
+            .unwrap();
+
+        let rows = sqlx::query("SELECT title, original_title FROM rss_articles ORDER BY guid")
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        let titles: Vec<(String, Option<String>)> = rows
+            .iter()
+            .map(|row| (row.get("title"), row.get("original_title")))
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                ("Actual Title".to_string(), Some("My Blog: Actual Title".to_string())),
+                ("Unprefixed".to_string(), None),
+                // 去除后为空时保留原标题
+                ("My Blog:".to_string(), None),
+            ]
+        );
+
+        let regex = crate::utils::build_title_strip_regex(r"\[\w+\]", true).unwrap();
+        assert_eq!(crate::utils::strip_title_prefix("[News] Headline", &regex).as_deref(), Some("Headline"));
+        let literal = crate::utils::build_title_strip_regex("[News]", false).unwrap();
+        assert_eq!(crate::utils::strip_title_prefix("[News] Headline", &literal).as_deref(), Some("Headline"));
+        assert!(crate::utils::strip_title_prefix("N Headline", &literal).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_export_starred_atom() {
+        use crate::export::export_starred_atom;
+        use crate::parser::parse_feed;
+
+        let (db, _db_dir) = setup_test_db().await;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        for (id, title, starred) in [("s", "Tom & Jerry <3", true), ("u", "Not starred", false)] {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, link, content, author, guid, is_starred, published_at, created_at)
+                 VALUES (?, 'f', ?, 'https://example.com/a?x=1&y=2', '<p>Hello &amp; <b>bye</b></p>', 'Ann', ?, ?, '2024-01-01T00:00:00+00:00', ?)"
+            )
+            .bind(id)
+            .bind(title)
+            .bind(id)
+            .bind(starred)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let atom = export_starred_atom(&db).await.unwrap();
+        let parsed = parse_feed(Some("application/atom+xml"), atom.as_bytes()).unwrap();
+        assert_eq!(parsed.format, "Atom");
+        assert_eq!(parsed.entries.len(), 1);
+
+        let entry = &parsed.entries[0];
+        assert_eq!(entry.title.as_deref(), Some("Tom & Jerry <3"));
+        assert_eq!(entry.link.as_deref(), Some("https://example.com/a?x=1&y=2"));
+        assert_eq!(entry.content.as_deref(), Some("<p>Hello &amp; <b>bye</b></p>"));
+        assert_eq!(entry.author.as_deref(), Some("Ann"));
+        assert!(entry.published.is_some());
+    }
+
+    #[test]
+    fn test_is_teaser() {
+        let teaser = "<p>A short intro. <a href=\"#\">Read more</a></p>";
+        let full = "<p>word </p>".repeat(80);
+
+        assert!(RssService::is_teaser(None, 50));
+        assert!(RssService::is_teaser(Some("   "), 50));
+        assert!(RssService::is_teaser(Some(teaser), 50));
+        assert!(!RssService::is_teaser(Some(&full), 50));
+        // 字数足够但以“阅读全文”或省略号结尾的内容视为被截断
+        let truncated = format!("{}<p>… <a href=\"/post\">Continue reading &raquo;</a></p>", full);
+        assert!(RssService::is_teaser(Some(&truncated), 50));
+        assert!(RssService::is_teaser(Some(&format!("{}<p>and then [...]</p>", full)), 50));
+        assert!(RssService::is_teaser(Some(&format!("{}<p>阅读全文</p>", full)), 50));
+        // 阈值为0时只在内容为空时提取
+        assert!(!RssService::is_teaser(Some(teaser), 0));
+        assert!(RssService::is_teaser(Some(""), 0));
+    }
+
+    #[tokio::test]
+    async fn test_get_storage_by_feed() {
+        let (db, _db_dir) = setup_test_db().await;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        for id in ["small", "big", "empty"] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(format!("https://example.com/{}", id))
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        let long = "x".repeat(100);
+        let articles = [
+            ("s1", "small", "abc", None),
+            ("b1", "big", long.as_str(), Some("yy")),
+            ("b2", "big", "中文", None),
+        ];
+        for (id, feed_id, content, description) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, content, description, guid, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(feed_id)
+            .bind(id)
+            .bind(content)
+            .bind(description)
+            .bind(id)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let storage = RssService::get_storage_by_feed(&db).await.unwrap();
+        let summary: Vec<(&str, u32, u64)> = storage
+            .iter()
+            .map(|s| (s.feed_id.as_str(), s.article_count, s.bytes))
+            .collect();
+        // 按UTF-8字节计算，两个汉字占6字节
+        assert_eq!(summary, vec![("big", 2, 108), ("small", 1, 3), ("empty", 0, 0)]);
+    }
+
+    #[tokio::test]
+    async fn test_move_article() {
+        let (db, _db_dir) = setup_test_db().await;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        for id in ["from", "to"] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(format!("https://example.com/{}", id))
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        for (id, feed_id, guid) in [("a", "from", "g1"), ("b", "from", "g2"), ("c", "to", "g2")] {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, guid, created_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(feed_id)
+            .bind(id)
+            .bind(guid)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        RssService::move_article(&db, "a".to_string(), "to".to_string()).await.unwrap();
+        let moved = RssService::get_articles(&db, Some("to".to_string()), None, None, None, None, None).await.unwrap();
+        assert!(moved.iter().any(|a| a.id == "a"));
+
+        // guid冲突时拒绝移动
+        let conflict = RssService::move_article(&db, "b".to_string(), "to".to_string()).await;
+        assert_eq!(conflict.unwrap_err().code(), "validation");
+
+        let missing_feed = RssService::move_article(&db, "b".to_string(), "nope".to_string()).await;
+        assert_eq!(missing_feed.unwrap_err().code(), "feed_not_found");
+        let missing_article = RssService::move_article(&db, "nope".to_string(), "to".to_string()).await;
+        assert_eq!(missing_article.unwrap_err().code(), "article_not_found");
+    }
+
+    #[tokio::test]
+    async fn test_clear_feed_articles_dry_run() {
+        let (db, _db_dir) = setup_test_db().await;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        for (id, starred) in [("a", false), ("b", false), ("keep", true)] {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, guid, is_starred, created_at) VALUES (?, 'f', ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(id)
+            .bind(starred)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let preview = RssService::clear_feed_articles(&db, "f".to_string(), true).await.unwrap();
+        assert!(preview.dry_run);
+        assert_eq!(preview.count, 2);
+        assert_eq!(preview.sample.len(), 2);
+        assert_eq!(RssService::get_articles(&db, None, None, None, None, None, None).await.unwrap().len(), 3);
+
+        let report = RssService::clear_feed_articles(&db, "f".to_string(), false).await.unwrap();
+        assert!(!report.dry_run);
+        assert_eq!(report.count, 2);
+        let remaining = RssService::get_articles(&db, None, None, None, None, None, None).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, "keep");
+
+        // 删除的文章仍在源中时，刷新不会重新保存
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+        let entry = |id: &str| crate::parser::ParsedEntry {
+            id: id.to_string(),
+            title: Some(id.to_string()),
+            content: Some("<p>body</p>".to_string()),
+            ..Default::default()
+        };
+        let added = RssService::save_articles(&db, "f", &[entry("a"), entry("b"), entry("new")], &chrono::Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(added, 1);
+        assert_eq!(RssService::get_articles(&db, None, None, None, None, None, None).await.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_find_canonical_url() {
+        let page = url::Url::parse("https://amp.example.com/amp/post/1?utm_source=x").unwrap();
+
+        let html = r#"<html><head>
+            <link rel="stylesheet" href="/style.css">
+            <link rel="Canonical" href="https://example.com/post/1">
+        </head><body></body></html>"#;
+        assert_eq!(
+            RssService::find_canonical_url(html, &page).as_deref(),
+            Some("https://example.com/post/1")
+        );
+
+        // 相对地址按页面地址解析
+        let relative = r#"<html><head><link rel="canonical" href="/post/1"></head></html>"#;
+        assert_eq!(
+            RssService::find_canonical_url(relative, &page).as_deref(),
+            Some("https://amp.example.com/post/1")
+        );
+
+        assert_eq!(RssService::find_canonical_url("<html><head></head></html>", &page), None);
+    }
+
+    #[test]
+    fn test_find_icon_link() {
+        let page = url::Url::parse("https://example.com/blog/").unwrap();
+
+        // apple-touch-icon在前时仍返回普通图标
+        let html = r#"<html><head>
+            <link rel="apple-touch-icon" href="/apple.png">
+            <link rel="shortcut icon" href="favicon.png">
+        </head></html>"#;
+        assert_eq!(
+            RssService::find_icon_link(html, &page).as_deref(),
+            Some("https://example.com/blog/favicon.png")
+        );
+
+        let apple_only = r#"<html><head><link rel="apple-touch-icon" href="/apple.png"></head></html>"#;
+        assert_eq!(RssService::find_icon_link(apple_only, &page), None);
+    }
+
+    #[tokio::test]
+    async fn test_article_updated_at_sorting() {
+        use crate::models::ArticleSort;
+        use crate::parser::parse_feed;
+
+        let (db, _db_dir) = setup_test_db().await;
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+
+        let atom = r#"<?xml version="1.0" encoding="utf-8"?>
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <id>urn:test</id><title>Test</title><updated>2024-03-01T00:00:00Z</updated>
+            <entry>
+                <id>urn:old-edited</id><title>Old but edited</title>
+                <published>2024-01-01T00:00:00Z</published><updated>2024-03-01T00:00:00Z</updated>
+                <content type="html">body</content>
+            </entry>
+            <entry>
+                <id>urn:new</id><title>New</title>
+                <published>2024-02-01T00:00:00Z</published><updated>2024-02-01T00:00:00Z</updated>
+                <content type="html">body</content>
+            </entry>
+        </feed>"#;
+        let parsed = parse_feed(None, atom.as_bytes()).unwrap();
+        RssService::save_articles(&db, "f", &parsed.entries, &chrono::Utc::now())
+            .await
+            .unwrap();
+
+        let by_published = RssService::get_articles(&db, None, None, None, None, None, None).await.unwrap();
+        assert_eq!(by_published[0].guid.as_deref(), Some("urn:new"));
+
+        let by_updated = RssService::get_articles(&db, None, None, None, Some(ArticleSort::Updated), None, None)
+            .await
+            .unwrap();
+        assert_eq!(by_updated[0].guid.as_deref(), Some("urn:old-edited"));
+        assert_eq!(
+            by_updated[0].updated_at.map(|dt| dt.to_rfc3339()).as_deref(),
+            Some("2024-03-01T00:00:00+00:00")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_article_links() {
+        use crate::links::{self, classify_status};
+        use sqlx::Row;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        assert_eq!(classify_status(Some(200)), "ok");
+        assert_eq!(classify_status(Some(301)), "ok");
+        assert_eq!(classify_status(Some(404)), "not_found");
+        assert_eq!(classify_status(Some(410)), "gone");
+        assert_eq!(classify_status(Some(500)), "error");
+        assert_eq!(classify_status(None), "error");
+
+        // 本地HTTP服务器按路径返回状态码
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let status = if request.contains(" /missing ") {
+                    "404 Not Found"
+                } else if request.contains(" /gone ") {
+                    "410 Gone"
+                } else {
+                    "200 OK"
+                };
+                let response = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        let links = [
+            ("ok", format!("http://{}/ok", addr)),
+            ("missing", format!("http://{}/missing", addr)),
+            ("gone", format!("http://{}/gone", addr)),
+            ("invalid", "not a url".to_string()),
+        ];
+        for (id, link) in &links {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, link, guid, created_at) VALUES (?, 'f', ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(link)
+            .bind(id)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let calls = std::cell::Cell::new(0);
+        let summary = links::check_article_links(&db, None, Some(2), |_| calls.set(calls.get() + 1))
+            .await
+            .unwrap();
+        assert_eq!(summary.checked, 4);
+        assert_eq!(summary.ok, 1);
+        let mut dead: Vec<(String, String)> = summary
+            .dead
+            .iter()
+            .map(|d| (d.article_id.clone(), d.status.clone()))
+            .collect();
+        dead.sort();
+        assert_eq!(
+            dead,
+            vec![
+                ("gone".to_string(), "gone".to_string()),
+                ("invalid".to_string(), "error".to_string()),
+                ("missing".to_string(), "not_found".to_string()),
+            ]
+        );
+        assert_eq!(calls.get(), 5);
+
+        let status: String = sqlx::query("SELECT link_status FROM rss_articles WHERE id = 'missing'")
+            .fetch_one(&db)
+            .await
+            .unwrap()
+            .get("link_status");
+        assert_eq!(status, "not_found");
+    }
+
+    #[tokio::test]
+    async fn test_flag_and_review_duplicates() {
+        use crate::parser::ParsedEntry;
+        use chrono::TimeZone;
+
+        let (db, _db_dir) = setup_test_db().await;
+        // 阈值为0时不会尝试从链接提取全文
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+
+        let now = chrono::Utc::now().to_rfc3339();
+        for id in ["a", "b"] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(format!("https://{}.example.com/rss", id))
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let published = |day: u32| chrono::Utc.with_ymd_and_hms(2024, 5, day, 12, 0, 0).single();
+        let entry = |id: &str, title: &str, link: &str, day: Option<u32>| ParsedEntry {
+            id: id.to_string(),
+            title: Some(title.to_string()),
+            link: Some(link.to_string()),
+            content: Some("<p>body</p>".to_string()),
+            published: day.and_then(published),
+            ..Default::default()
+        };
+        RssService::save_articles(
+            &db,
+            "a",
+            &[
+                entry("1", "Shared Story", "https://news.example.com/story?utm_source=a", None),
+                entry("2", "Weekly Notes", "https://a.example.com/notes", Some(1)),
+                entry("3", "Changelog", "https://a.example.com/changelog", None),
+                entry("4", "Weekly Roundup", "https://a.example.com/roundup", Some(1)),
+            ],
+            &chrono::Utc::now(),
+        )
+        .await
+        .unwrap();
+        RssService::save_articles(
+            &db,
+            "b",
+            &[
+                entry("1", "Shared story (syndicated)", "https://news.example.com/story", None),
+                entry("2", "weekly notes", "https://b.example.com/notes", Some(1)),
+                entry("3", "Unique", "https://b.example.com/unique", None),
+                // 只有标题相同，或标题相同但发布时间相差很远的文章不算重复
+                entry("4", "Changelog", "https://b.example.com/changelog", None),
+                entry("5", "Weekly Roundup", "https://b.example.com/roundup", Some(20)),
+            ],
+            &chrono::Utc::now(),
+        )
+        .await
+        .unwrap();
+
+        let flagged = RssService::get_flagged_duplicates(&db).await.unwrap();
+        assert_eq!(flagged.len(), 2);
+        for item in &flagged {
+            assert_eq!(item.article.feed_id, "b");
+            let original = item.original.as_ref().unwrap();
+            assert_eq!(original.feed_id, "a");
+            assert_eq!(item.article.duplicate_of.as_deref(), Some(original.id.as_str()));
+        }
+
+        let story = flagged
+            .iter()
+            .find(|item| item.article.guid.as_deref() == Some("1"))
+            .unwrap();
+        let notes = flagged
+            .iter()
+            .find(|item| item.article.guid.as_deref() == Some("2"))
+            .unwrap();
+
+        RssService::confirm_duplicate(&db, story.article.id.clone()).await.unwrap();
+        RssService::dismiss_duplicate(&db, notes.article.id.clone()).await.unwrap();
+        assert!(RssService::get_flagged_duplicates(&db).await.unwrap().is_empty());
+
+        let remaining = RssService::get_articles(&db, Some("b".to_string()), None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(remaining.len(), 4);
+
+        // 确认删除的重复文章不会在下次刷新时重新出现
+        let added = RssService::save_articles(
+            &db,
+            "b",
+            &[entry("1", "Shared story (syndicated)", "https://news.example.com/story", None)],
+            &chrono::Utc::now(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(added, 0);
+
+        // 未标记的文章不能确认或忽略
+        assert!(RssService::dismiss_duplicate(&db, notes.article.id.clone()).await.is_err());
+        assert!(RssService::confirm_duplicate(&db, "missing".to_string()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_set_feed_requires_render() {
+        let (db, _db_dir) = setup_test_db().await;
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+
+        assert!(!RssService::get_feed(&db, "f").await.unwrap().requires_render);
+        RssService::set_feed_requires_render(&db, "f".to_string(), true)
+            .await
+            .unwrap();
+        assert!(RssService::get_feed(&db, "f").await.unwrap().requires_render);
+
+        assert!(RssService::set_feed_requires_render(&db, "missing".to_string(), true)
+            .await
+            .is_err());
+        // 测试环境中没有webview，渲染不可用
+        assert!(!crate::render::is_available());
+    }
+
+    #[tokio::test]
+    async fn test_digest_since() {
+        use crate::digest;
+
+        let (db, _db_dir) = setup_test_db().await;
+
+        let now = chrono::Utc::now();
+        let old = (now - chrono::Duration::days(2)).to_rfc3339();
+        let recent = (now - chrono::Duration::hours(1)).to_rfc3339();
+        for (id, active) in [("a", true), ("b", true), ("inactive", false)] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, is_active, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(format!("https://{}.example.com/rss", id))
+            .bind(active)
+            .bind(&old)
+            .bind(&old)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        let articles = [
+            ("a1", "a", &recent, None),
+            ("a2", "a", &recent, None),
+            ("a-old", "a", &old, None),
+            ("b1", "b", &recent, None),
+            ("b-dup", "b", &recent, Some("a1")),
+            ("i1", "inactive", &recent, None),
+        ];
+        for (id, feed_id, created_at, duplicate_of) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, guid, duplicate_of, created_at) VALUES (?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(feed_id)
+            .bind(id)
+            .bind(id)
+            .bind(duplicate_of)
+            .bind(created_at)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let result = digest::get_digest_since(&db, now - chrono::Duration::days(1))
+            .await
+            .unwrap();
+        assert_eq!(result.total, 3);
+        let counts: Vec<(String, u32)> = result
+            .groups
+            .iter()
+            .map(|g| (g.feed.id.clone(), g.count))
+            .collect();
+        assert_eq!(counts, vec![("a".to_string(), 2), ("b".to_string(), 1)]);
+
+        // 未开启时不生成，开启后生成一次并记录时间
+        assert!(digest::generate_digest_if_due(&db).await.unwrap().is_none());
+        digest::set_digest_enabled(&db, true).await.unwrap();
+        let generated = digest::generate_digest_if_due(&db).await.unwrap().unwrap();
+        assert_eq!(generated.total, 3);
+        assert!(digest::generate_digest_if_due(&db).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_feed_max_content_bytes() {
+        use crate::parser::ParsedEntry;
+        use crate::utils::truncate_to_bytes;
+
+        assert_eq!(truncate_to_bytes("hello", 10), "hello");
+        assert_eq!(truncate_to_bytes("hello", 3), "hel");
+        // 多字节字符不会被截断到中间
+        assert_eq!(truncate_to_bytes("你好", 4), "你");
+
+        let (db, _db_dir) = setup_test_db().await;
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+
+        assert!(RssService::set_feed_max_content(&db, "f".to_string(), Some(10))
+            .await
+            .is_err());
+        RssService::set_feed_max_content(&db, "f".to_string(), Some(2048))
+            .await
+            .unwrap();
+        assert_eq!(
+            RssService::get_feed(&db, "f").await.unwrap().max_content_bytes,
+            Some(2048)
+        );
+
+        let entries = vec![
+            ParsedEntry {
+                id: "long".to_string(),
+                title: Some("Long".to_string()),
+                content: Some("word ".repeat(1000)),
+                ..Default::default()
+            },
+            ParsedEntry {
+                id: "short".to_string(),
+                title: Some("Short".to_string()),
+                content: Some("short body".to_string()),
+                ..Default::default()
+            },
+        ];
+        RssService::save_articles(&db, "f", &entries, &chrono::Utc::now())
+            .await
+            .unwrap();
+
+        let articles = RssService::get_articles(&db, Some("f".to_string()), None, None, None, None, None)
+            .await
+            .unwrap();
+        let content = |guid: &str| {
+            articles
+                .iter()
+                .find(|a| a.guid.as_deref() == Some(guid))
+                .and_then(|a| a.content.clone())
+                .unwrap()
+        };
+        assert!(content("long").len() <= 2048);
+        assert!(content("long").ends_with(crate::rss::CONTENT_TRUNCATED_MARKER));
+        assert_eq!(content("short"), "short body");
+    }
+
+    #[tokio::test]
+    async fn test_feed_format_change_dedups_by_link() {
+        use crate::parser::{ParsedEntry, ParsedFeed};
+
+        let (db, _db_dir) = setup_test_db().await;
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+
+        let entry = |guid: &str, link: &str| ParsedEntry {
+            id: guid.to_string(),
+            title: Some(link.to_string()),
+            link: Some(format!("https://example.com/{}", link)),
+            content: Some("<p>body</p>".to_string()),
+            ..Default::default()
+        };
+        let rss = ParsedFeed {
+            format: "RSS 2.0".to_string(),
+            entries: vec![entry("1", "first"), entry("2", "second")],
+            ..Default::default()
+        };
+        let diff = RssService::save_refreshed_entries(&db, "f", &rss, &chrono::Utc::now(), None)
+            .await
+            .unwrap();
+        assert_eq!(diff.added.len(), 2);
+        assert_eq!(
+            RssService::get_feed(&db, "f").await.unwrap().feed_format.as_deref(),
+            Some("RSS 2.0")
+        );
+
+        // 改版为Atom后guid全部变化，只有新链接被保存
+        // 链接按规范化后的形式比较，末尾斜杠和跟踪参数不同也视为同一篇文章
+        let atom = ParsedFeed {
+            format: "Atom".to_string(),
+            entries: vec![
+                entry("tag:example.com,1", "first"),
+                entry("tag:example.com,2", "second/?utm_source=atom"),
+                entry("tag:example.com,3", "third"),
+            ],
+            ..Default::default()
+        };
+        let diff = RssService::save_refreshed_entries(&db, "f", &atom, &chrono::Utc::now(), None)
+            .await
+            .unwrap();
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(
+            RssService::get_feed(&db, "f").await.unwrap().feed_format.as_deref(),
+            Some("Atom")
+        );
+
+        // 旧文章按规范化链接记为本次仍在源中出现
+        let stale: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM rss_articles WHERE feed_id = 'f'
+             AND last_seen_at != (SELECT MAX(last_seen_at) FROM rss_articles WHERE feed_id = 'f')",
+        )
+        .fetch_one(&db)
+        .await
+        .unwrap();
+        assert_eq!(stale, 0);
+    }
+
+    #[test]
+    fn test_sanitize_html() {
+        use crate::sanitize::sanitize_html;
+
+        assert_eq!(sanitize_html("<p>Hello <b>world</b></p>"), "<p>Hello <b>world</b></p>");
+        assert_eq!(sanitize_html("<p>a<script>alert(1)</script>b</p>"), "<p>ab</p>");
+        // 嵌套和畸形的script标签
+        let nested = sanitize_html("<div><script><script>alert(1)</script></script>text</div>");
+        assert!(!nested.contains("script"));
+        assert!(nested.contains("text"));
+        assert!(!sanitize_html("<p>x<scr<script>ipt>alert(1)</script></p>").contains("<script"));
+        assert_eq!(sanitize_html("<p>unclosed <em>tag"), "<p>unclosed <em>tag</em></p>");
+
+        assert_eq!(sanitize_html("<img src=\"a.png\" onerror=\"alert(1)\">"), "<img src=\"a.png\">");
+        assert_eq!(sanitize_html("<p onclick=\"alert(1)\">x</p>"), "<p>x</p>");
+        assert_eq!(sanitize_html("<p>x<iframe src=\"https://evil.example\"></iframe></p>"), "<p>x</p>");
+        assert_eq!(
+            sanitize_html("<a href=\"javascript:alert(1)\">x</a>"),
+            "<a rel=\"noopener noreferrer\">x</a>"
+        );
+        assert_eq!(
+            sanitize_html("<a href=\"https://example.com\">x</a>"),
+            "<a href=\"https://example.com\" rel=\"noopener noreferrer\">x</a>"
+        );
+        assert!(!sanitize_html("<style>body{}</style><p style=\"color:red\">x</p>").contains("style"));
+        assert_eq!(
+            sanitize_html("<figure><img src=\"a.png\"><figcaption>c</figcaption></figure>"),
+            "<figure><img src=\"a.png\"><figcaption>c</figcaption></figure>"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_enforce_article_quota() {
+        let (db, _db_dir) = setup_test_db().await;
+
+        let now = chrono::Utc::now();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        // (id, 已读, 收藏, 保存于几天前)
+        let articles = [
+            ("oldest-unread", false, false, 10),
+            ("old-starred", true, true, 9),
+            ("old-read", true, false, 8),
+            ("mid-read", true, false, 7),
+            ("new-read", true, false, 1),
+            ("new-unread", false, false, 0),
+        ];
+        for (id, is_read, is_starred, days) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, guid, is_read, is_starred, created_at) VALUES (?, 'f', ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(id)
+            .bind(is_read)
+            .bind(is_starred)
+            .bind((now - chrono::Duration::days(days)).to_rfc3339())
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        // 未设置上限时不删除
+        assert_eq!(RssService::enforce_article_quota(&db, false).await.unwrap().count, 0);
+
+        RssService::set_max_total_articles(&db, 4).await.unwrap();
+        let preview = RssService::enforce_article_quota(&db, true).await.unwrap();
+        let ids: Vec<&str> = preview.sample.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["old-read", "mid-read"]);
+
+        RssService::enforce_article_quota(&db, false).await.unwrap();
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM rss_articles")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(count, 4);
+
+        // 可删除的文章不足时只删除已读未收藏的
+        RssService::set_max_total_articles(&db, 1).await.unwrap();
+        let report = RssService::enforce_article_quota(&db, false).await.unwrap();
+        assert_eq!(report.count, 1);
+        let remaining: Vec<String> = sqlx::query_scalar("SELECT id FROM rss_articles ORDER BY id")
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        assert_eq!(remaining, vec!["new-unread", "old-starred", "oldest-unread"]);
+    }
+
+    #[tokio::test]
+    async fn test_export_opml_round_trip() {
+        use crate::opml;
+
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        let feeds = [
+            ("a", "Tom & Jerry's <Blog>", "https://a.example.com/rss?x=1&y=2", Some("https://a.example.com"), true),
+            ("b", "Paused", "https://b.example.com/rss", None, false),
+        ];
+        for (id, title, url, website_url, is_active) in feeds {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, website_url, is_active, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(title)
+            .bind(url)
+            .bind(website_url)
+            .bind(is_active)
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let xml = RssService::export_opml(&db).await.unwrap();
+        assert!(xml.contains("<opml version=\"2.0\">"));
+        assert!(xml.contains("Tom &amp; Jerry&apos;s &lt;Blog&gt;"));
+        assert!(xml.contains("xmlUrl=\"https://a.example.com/rss?x=1&amp;y=2\""));
+
+        let parsed = opml::parse_opml(&xml).unwrap();
+        assert!(parsed.malformed.is_empty());
+        assert_eq!(parsed.outlines.len(), 2);
+        let outline = |url: &str| parsed.outlines.iter().find(|o| o.xml_url == url).unwrap();
+        let a = outline("https://a.example.com/rss?x=1&y=2");
+        assert_eq!(a.title.as_deref(), Some("Tom & Jerry's <Blog>"));
+        assert_eq!(a.html_url.as_deref(), Some("https://a.example.com"));
+        assert!(a.is_active);
+        assert!(!outline("https://b.example.com/rss").is_active);
+
+        // 导入到新数据库后恢复停用状态
+        let (restored_db, _restored_dir) = setup_test_db().await;
+        let result = RssService::import_opml(&restored_db, &xml).await.unwrap();
+        assert_eq!(result.imported.len(), 2);
+        let paused = result.imported.iter().find(|f| f.title == "Paused").unwrap();
+        assert!(!paused.is_active);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_diff() {
+        use crate::parser::{ParsedEntry, ParsedFeed};
+
+        let (db, _db_dir) = setup_test_db().await;
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+
+        let first_refresh = chrono::Utc::now() - chrono::Duration::hours(1);
+        sqlx::query(
+            "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES ('f', 'Feed', 'https://example.com/rss', ?, ?)"
+        )
+        .bind(first_refresh.to_rfc3339())
+        .bind(first_refresh.to_rfc3339())
+        .execute(&db)
+        .await
+        .unwrap();
+
+        let t0 = first_refresh - chrono::Duration::days(1);
+        let entry = |guid: &str, updated: chrono::DateTime<chrono::Utc>| ParsedEntry {
+            id: guid.to_string(),
+            title: Some(guid.to_string()),
+            link: Some(format!("https://example.com/{}", guid)),
+            content: Some("<p>body</p>".to_string()),
+            updated: Some(updated),
+            ..Default::default()
+        };
+        let feed = |entries: Vec<ParsedEntry>| ParsedFeed {
+            format: "RSS 2.0".to_string(),
+            entries,
+            ..Default::default()
+        };
+        let guids = |articles: &[crate::models::RssArticle]| {
+            let mut guids: Vec<String> = articles.iter().filter_map(|a| a.guid.clone()).collect();
+            guids.sort();
+            guids
+        };
+
+        let diff = RssService::save_refreshed_entries(
+            &db,
+            "f",
+            &feed(vec![entry("1", t0), entry("2", t0), entry("3", t0)]),
+            &first_refresh,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(guids(&diff.added), vec!["1", "2", "3"]);
+        assert!(diff.updated.is_empty());
+        assert!(diff.possibly_removed.is_empty());
+
+        // 第二次抓取：1从源中消失，3被更新，4是新文章
+        let t1 = t0 + chrono::Duration::hours(2);
+        let diff = RssService::save_refreshed_entries(
+            &db,
+            "f",
+            &feed(vec![entry("2", t0), entry("3", t1), entry("4", t1)]),
+            &chrono::Utc::now(),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(guids(&diff.added), vec!["4"]);
+        assert_eq!(guids(&diff.updated), vec!["3"]);
+        assert_eq!(diff.updated[0].updated_at, Some(t1));
+        assert_eq!(guids(&diff.possibly_removed), vec!["1"]);
+    }
+
+    #[tokio::test]
+    async fn test_check_database_integrity() {
+        let (db, _db_dir) = setup_test_db().await;
+
+        let report = crate::database::check_integrity(&db).await.unwrap();
+        assert!(report.ok);
+        assert!(report.integrity_errors.is_empty());
+        assert!(report.foreign_key_errors.is_empty());
+        assert!(report.page_count > 0);
+        assert!(report.page_size > 0);
+
+        // 关闭外键约束后插入引用不存在的RSS源的文章
+        let mut conn = db.acquire().await.unwrap();
+        sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await.unwrap();
+        sqlx::query(
+            "INSERT INTO rss_articles (id, feed_id, title, guid, created_at) VALUES ('a', 'missing', 't', 'g', ?)"
+        )
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+        sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await.unwrap();
+        drop(conn);
+
+        let report = crate::database::check_integrity(&db).await.unwrap();
+        assert!(!report.ok);
+        assert_eq!(report.foreign_key_errors.len(), 1);
+        assert!(report.suggestion.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_search_articles() {
+        use crate::models::SearchFilters;
+        use crate::search::SearchService;
+
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        for id in ["a", "b"] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(format!("https://{}.example.com/rss", id))
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        let articles = [
+            ("title-hit", "a", "Async Rust in depth", "intro"),
+            ("body-hit", "a", "Weekly notes", "a short mention of async rust"),
+            ("other-feed", "b", "Rust async runtimes", "compare"),
+            ("miss", "a", "Gardening", "tomatoes 100%"),
+        ];
+        for (id, feed_id, title, description) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, description, guid, created_at) VALUES (?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(feed_id)
+            .bind(title)
+            .bind(description)
+            .bind(id)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let ids = |articles: Vec<crate::models::RssArticle>| {
+            let mut ids: Vec<String> = articles.into_iter().map(|a| a.id).collect();
+            ids.sort();
+            ids
+        };
+
+        let all = SearchService::search_articles(&db, "async rust", &SearchFilters::default(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(ids(all), vec!["body-hit", "other-feed", "title-hit"]);
+
+        let filters = SearchFilters {
+            feed_id: Some("a".to_string()),
+            ..Default::default()
+        };
+        let in_feed = SearchService::search_articles(&db, "async", &filters, None, None)
+            .await
+            .unwrap();
+        assert_eq!(ids(in_feed), vec!["body-hit", "title-hit"]);
+
+        let page = SearchService::search_articles(&db, "async", &SearchFilters::default(), Some(2), Some(2))
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 1);
+
+        // 删除全文索引后退回LIKE搜索
+        sqlx::query("DROP TABLE rss_articles_fts").execute(&db).await.unwrap();
+        let fallback = SearchService::search_articles(&db, "async rust", &filters, None, None)
+            .await
+            .unwrap();
+        assert_eq!(ids(fallback), vec!["body-hit", "title-hit"]);
+        let percent = SearchService::search_articles(&db, "100%", &SearchFilters::default(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(ids(percent), vec!["miss"]);
+    }
+
+    #[tokio::test]
+    async fn test_ipv4_only_setting() {
+        use crate::network;
+
+        let _guard = NETWORK_SETTINGS_LOCK.lock().await;
+        let (db, _db_dir) = setup_test_db().await;
+        network::load_settings(&db).await.unwrap();
+        assert!(!network::is_ipv4_only());
+
+        network::set_ipv4_only(&db, true).await.unwrap();
+        assert!(network::is_ipv4_only());
+        assert!(network::client_builder().build().is_ok());
+        assert!(crate::settings::get_bool(&db, crate::settings::IPV4_ONLY, false)
+            .await
+            .unwrap());
+
+        network::set_ipv4_only(&db, false).await.unwrap();
+        assert!(!network::is_ipv4_only());
+    }
+
+    #[tokio::test]
+    async fn test_add_feeds_bulk_with_categories() {
+        use crate::models::{BulkFeedEntry, BulkFeedStatus};
+
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query(
+            "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES ('existing', 'Existing', 'https://existing.example.com/rss', ?, ?)"
+        )
+        .bind(&now)
+        .bind(&now)
+        .execute(&db)
+        .await
+        .unwrap();
+
+        let entry = |url: &str, category: Option<&str>| BulkFeedEntry {
+            url: url.to_string(),
+            category: category.map(|c| c.to_string()),
+        };
+        let results = RssService::add_feeds_bulk(
+            &db,
+            vec![
+                entry("https://a.example.com/rss", Some("Rust")),
+                entry("https://b.example.com/rss", Some(" rust ")),
+                entry("https://c.example.com/rss", None),
+                entry("https://existing.example.com/rss/", Some("Rust")),
+                entry("https://a.example.com/rss", Some("News")),
+                entry("ftp://bad.example.com", Some("News")),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let statuses: Vec<BulkFeedStatus> = results.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                BulkFeedStatus::Added,
+                BulkFeedStatus::Added,
+                BulkFeedStatus::Added,
+                BulkFeedStatus::Duplicate,
+                BulkFeedStatus::Duplicate,
+                BulkFeedStatus::Invalid,
+            ]
+        );
+
+        // 分类名不区分大小写，只创建一次；被跳过的条目不会创建分类
+        let categories = RssService::get_categories(&db).await.unwrap();
+        assert_eq!(categories.len(), 1);
+        assert_eq!(categories[0].name, "Rust");
+        assert_eq!(results[0].category.as_deref(), Some("Rust"));
+        assert_eq!(results[1].category.as_deref(), Some("Rust"));
+        assert_eq!(
+            results[1].feed.as_ref().unwrap().category_id.as_deref(),
+            Some(categories[0].id.as_str())
+        );
+        assert!(results[2].category.is_none());
+        assert!(results[2].feed.as_ref().unwrap().category_id.is_none());
+
+        assert!(RssService::get_or_create_category(&db, "  ").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mark_all_read() {
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        for id in ["a", "b"] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(format!("https://{}.example.com/rss", id))
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        let articles = [("a1", "a", false), ("a2", "a", false), ("a3", "a", true), ("b1", "b", false)];
+        for (id, feed_id, is_read) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, guid, is_read, created_at) VALUES (?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(feed_id)
+            .bind(id)
+            .bind(id)
+            .bind(is_read)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        // 只统计原本未读的文章
+        let marked = RssService::mark_all_read(&db, Some("a".to_string())).await.unwrap();
+        assert_eq!(marked, 2);
+        let unread_b: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM rss_articles WHERE feed_id = 'b' AND is_read = 0")
+                .fetch_one(&db)
+                .await
+                .unwrap();
+        assert_eq!(unread_b, 1);
+
+        let marked = RssService::mark_all_read(&db, None).await.unwrap();
+        assert_eq!(marked, 1);
+        assert_eq!(RssService::mark_all_read(&db, None).await.unwrap(), 0);
+
+        assert!(RssService::mark_all_read(&db, Some("missing".to_string())).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_todays_articles() {
+        use chrono::{Duration, TimeZone, Utc};
+
+        // 按本地日期划分：UTC 20点在上海已经是第二天
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 20, 0, 0).unwrap();
+        let shanghai = crate::utils::parse_timezone("Asia/Shanghai").unwrap();
+        let (start, end) = crate::utils::local_day_bounds(now, &shanghai);
+        assert_eq!(start, Utc.with_ymd_and_hms(2026, 10, 17, 16, 0, 0).unwrap());
+        assert_eq!(end, Utc.with_ymd_and_hms(2026, 10, 18, 16, 0, 0).unwrap());
+
+        let (db, _db_dir) = setup_test_db().await;
+        let now = Utc::now();
+        for (id, is_active) in [("active", true), ("inactive", false)] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, is_active, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(format!("{} feed", id))
+            .bind(format!("https://{}.example.com/rss", id))
+            .bind(is_active)
+            .bind(now.to_rfc3339())
+            .bind(now.to_rfc3339())
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        let articles = [
+            ("today", "active", Some(now)),
+            ("undated", "active", None),
+            ("two-days-ago", "active", Some(now - Duration::days(2))),
+            ("inactive-today", "inactive", Some(now)),
+        ];
+        for (id, feed_id, published_at) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, guid, published_at, created_at) VALUES (?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(feed_id)
+            .bind(id)
+            .bind(id)
+            .bind(published_at.map(|dt| dt.to_rfc3339()))
+            .bind(now.to_rfc3339())
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let today = RssService::get_todays_articles(&db, Some("UTC".to_string())).await.unwrap();
+        let mut ids: Vec<&str> = today.iter().map(|a| a.article.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["today", "undated"]);
+        assert!(today.iter().all(|a| a.feed_title == "active feed"));
+
+        assert!(RssService::get_todays_articles(&db, Some("Mars/Olympus".to_string())).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_recompute_reading_metrics() {
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        for id in ["a", "b"] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(format!("https://{}.example.com/rss", id))
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let long = format!("<p>{}</p>", "word ".repeat(450));
+        let compressed = crate::compression::compress(&"字".repeat(250)).unwrap();
+        let articles = [
+            ("long", "a", Some(long.as_str()), None),
+            ("compressed", "a", None, None),
+            ("summary-only", "a", None, Some("just a teaser")),
+            ("empty", "a", None, None),
+            ("other-feed", "b", Some("one two three"), None),
+        ];
+        for (id, feed_id, content, description) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, guid, content, description, read_time, word_count, created_at) VALUES (?, ?, ?, ?, ?, ?, 'stale', 999, ?)"
+            )
+            .bind(id)
+            .bind(feed_id)
+            .bind(id)
+            .bind(id)
+            .bind(content)
+            .bind(description)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        sqlx::query("UPDATE rss_articles SET content_compressed = ?, content_compression = ? WHERE id = 'compressed'")
+            .bind(&compressed)
+            .bind(crate::compression::GZIP)
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let events = std::sync::Mutex::new(Vec::new());
+        let progress = RssService::recompute_reading_metrics(&db, Some("a".to_string()), |p| {
+            events.lock().unwrap().push(p.processed)
+        })
+        .await
+        .unwrap();
+        assert_eq!(progress.processed, 4);
+        assert_eq!(progress.total, 4);
+        assert_eq!(events.into_inner().unwrap(), vec![0, 4]);
+
+        let metrics = |id: &'static str| {
+            let db = db.clone();
+            async move {
+                sqlx::query_as::<_, (Option<i64>, Option<String>)>(
+                    "SELECT word_count, read_time FROM rss_articles WHERE id = ?",
+                )
+                .bind(id)
+                .fetch_one(&db)
+                .await
+                .unwrap()
+            }
+        };
+        assert_eq!(metrics("long").await, (Some(450), Some("3 min read".to_string())));
+        assert_eq!(metrics("compressed").await, (Some(250), Some("2 min read".to_string())));
+        assert_eq!(metrics("summary-only").await, (Some(3), Some("1 min read".to_string())));
+        assert_eq!(metrics("empty").await, (None, None));
+        // 未指定的RSS源不受影响
+        assert_eq!(metrics("other-feed").await, (Some(999), Some("stale".to_string())));
+    }
+
+    #[test]
+    fn test_estimate_read_time() {
+        use crate::utils::estimate_read_time;
+
+        assert_eq!(estimate_read_time(""), "");
+        assert_eq!(estimate_read_time("<p>  </p>"), "");
+        // 不足一分钟按一分钟计
+        assert_eq!(estimate_read_time("<p>A <b>short</b> note</p>"), "1 min read");
+        // 标签不计入字数：1000词正好5分钟，多一个词进位
+        let long = format!("<div>{}</div>", "<span>word</span> ".repeat(1000));
+        assert_eq!(estimate_read_time(&long), "5 min read");
+        assert_eq!(estimate_read_time(&format!("{} extra", long)), "6 min read");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_with_retry() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // 本地HTTP服务器：/flaky 前两次返回503，/missing 总是返回404
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let flaky_hits = Arc::new(AtomicUsize::new(0));
+        let missing_hits = Arc::new(AtomicUsize::new(0));
+        let (flaky, missing) = (flaky_hits.clone(), missing_hits.clone());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let status = if request.contains(" /missing ") {
+                    missing.fetch_add(1, Ordering::SeqCst);
+                    "404 Not Found"
+                } else if flaky.fetch_add(1, Ordering::SeqCst) < 2 {
+                    "503 Service Unavailable"
+                } else {
+                    "200 OK"
+                };
+                let response = format!("HTTP/1.1 {}\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok", status);
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let client = reqwest::Client::new();
+        let response = RssService::fetch_with_retry(client.get(format!("http://{}/flaky", addr)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(flaky_hits.load(Ordering::SeqCst), 3);
+
+        // 4xx不重试
+        let response = RssService::fetch_with_retry(client.get(format!("http://{}/missing", addr)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404);
+        assert_eq!(missing_hits.load(Ordering::SeqCst), 1);
+
+        // 无效URL直接失败
+        assert!(RssService::fetch_with_retry(client.get("not a url")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_feed_unread_count_cache() {
+        use crate::models::UpdateArticleRequest;
+
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        for id in ["a", "b"] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(format!("https://{}.example.com/rss", id))
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        for (id, is_read) in [("a1", false), ("a2", false), ("a3", true)] {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, guid, is_read, created_at) VALUES (?, 'a', ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(id)
+            .bind(is_read)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        let unread = |feed_id: &'static str| {
+            let db = db.clone();
+            async move { RssService::get_feed(&db, feed_id).await.unwrap().unread_count }
+        };
+        assert_eq!(unread("a").await, 2);
+
+        RssService::update_article(
+            &db,
+            UpdateArticleRequest { id: "a1".to_string(), is_read: Some(true), is_starred: None },
+        )
+        .await
+        .unwrap();
+        assert_eq!(unread("a").await, 1);
+
+        RssService::move_article(&db, "a2".to_string(), "b".to_string()).await.unwrap();
+        assert_eq!((unread("a").await, unread("b").await), (0, 1));
+
+        RssService::mark_all_read(&db, None).await.unwrap();
+        assert_eq!(unread("b").await, 0);
+
+        // 缓存出现偏差时可以重新统计
+        sqlx::query("UPDATE rss_feeds SET unread_count = 42 WHERE id = 'a'")
+            .execute(&db)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE rss_articles SET is_read = 0 WHERE id = 'a3'")
+            .execute(&db)
+            .await
+            .unwrap();
+        assert_eq!(RssService::recompute_unread_counts(&db).await.unwrap(), 1);
+        assert_eq!(unread("a").await, 1);
+        assert_eq!(RssService::recompute_unread_counts(&db).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_save_articles_dedup_by_guid_and_link() {
+        use crate::parser::ParsedEntry;
+        use crate::utils::normalize_article_link;
+
+        assert_eq!(
+            normalize_article_link("https://www.example.com/post/?utm_source=rss&id=7&fbclid=x#top"),
+            normalize_article_link("http://example.com/post?id=7")
+        );
+        assert_ne!(
+            normalize_article_link("https://example.com/post?id=7"),
+            normalize_article_link("https://example.com/post?id=8")
+        );
+
+        let (db, _db_dir) = setup_test_db().await;
+        // 只在正文为空时提取全文，避免测试访问网络
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+
+        let entry = |id: &str, link: &str, content: &str| ParsedEntry {
+            id: id.to_string(),
+            title: Some("Story".to_string()),
+            link: Some(link.to_string()),
+            content: Some(content.to_string()),
+            ..Default::default()
+        };
+        let added = RssService::save_articles(
+            &db,
+            "f",
+            &[entry("g1", "https://example.com/story?utm_source=rss", "<p>first</p>")],
+            &chrono::Utc::now(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(added, 1);
+
+        // guid变化但链接相同（只差跟踪参数），更新已有文章的内容
+        let added = RssService::save_articles(
+            &db,
+            "f",
+            &[entry("g2", "https://example.com/story", "<p>first, revised</p>")],
+            &chrono::Utc::now(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(added, 0);
+
+        // guid相同，同样更新内容
+        let added = RssService::save_articles(
+            &db,
+            "f",
+            &[entry("g1", "https://example.com/moved", "<p>final text here</p>")],
+            &chrono::Utc::now(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(added, 0);
+
+        let articles = RssService::get_articles(&db, Some("f".to_string()), None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(articles.len(), 1);
+        assert_eq!(articles[0].guid.as_deref(), Some("g1"));
+        assert_eq!(articles[0].content.as_deref(), Some("<p>final text here</p>"));
+        assert_eq!(articles[0].word_count, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_interval_setting() {
+        use crate::scheduler::{self, DEFAULT_REFRESH_INTERVAL_MINUTES};
+
+        let (db, _db_dir) = setup_test_db().await;
+        assert_eq!(scheduler::get_refresh_interval(&db).await.unwrap(), DEFAULT_REFRESH_INTERVAL_MINUTES);
+
+        scheduler::set_refresh_interval(&db, 15).await.unwrap();
+        assert_eq!(scheduler::get_refresh_interval(&db).await.unwrap(), 15);
+
+        // 低于最小间隔时拒绝并保留原设置
+        assert!(scheduler::set_refresh_interval(&db, 1).await.is_err());
+        assert_eq!(scheduler::get_refresh_interval(&db).await.unwrap(), 15);
+    }
+
+    #[tokio::test]
+    async fn test_feed_refresh_interval_override() {
+        use crate::scheduler;
+        use chrono::{Duration, Utc};
+
+        let (db, _db_dir) = setup_test_db().await;
+        let now = Utc::now();
+        let last_updated = (now - Duration::minutes(30)).to_rfc3339();
+        for id in ["hourly", "frequent"] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, last_updated, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(format!("https://{}.example.com/rss", id))
+            .bind(&last_updated)
+            .bind(now.to_rfc3339())
+            .bind(now.to_rfc3339())
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        // 30分钟前刷新过，全局默认60分钟，都未到期
+        assert!(scheduler::get_due_feed_ids(&db, now).await.unwrap().is_empty());
+
+        RssService::set_feed_refresh_interval(&db, "frequent".to_string(), Some(15))
+            .await
+            .unwrap();
+        assert_eq!(
+            RssService::get_feed(&db, "frequent").await.unwrap().refresh_interval_minutes,
+            Some(15)
+        );
+        assert_eq!(scheduler::get_due_feed_ids(&db, now).await.unwrap(), vec!["frequent".to_string()]);
+
+        // 清除后恢复使用全局间隔
+        RssService::set_feed_refresh_interval(&db, "frequent".to_string(), None)
+            .await
+            .unwrap();
+        assert!(scheduler::get_due_feed_ids(&db, now).await.unwrap().is_empty());
+
+        assert!(RssService::set_feed_refresh_interval(&db, "frequent".to_string(), Some(1)).await.is_err());
+        assert!(RssService::set_feed_refresh_interval(&db, "missing".to_string(), Some(30)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_saved_and_returned_content_is_sanitized() {
+        use crate::parser::ParsedEntry;
+
+        let (db, _db_dir) = setup_test_db().await;
+        // 只在正文为空时提取全文，避免测试访问网络
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+        crate::settings::set_bool(&db, crate::settings::KEEP_RAW_CONTENT, true).await.unwrap();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+
+        let malicious = "<p onclick=\"alert(1)\">Hi <a href=\"https://example.com\">link</a></p><script>alert(1)</script><img src=\"https://example.com/a.png\">";
+        RssService::save_articles(
+            &db,
+            "f",
+            &[ParsedEntry {
+                id: "g1".to_string(),
+                title: Some("Story".to_string()),
+                link: Some("https://example.com/story".to_string()),
+                summary: Some("<b>teaser</b><script>x()</script>".to_string()),
+                content: Some(malicious.to_string()),
+                ..Default::default()
+            }],
+            &chrono::Utc::now(),
+        )
+        .await
+        .unwrap();
+
+        let id: String = sqlx::query_scalar("SELECT id FROM rss_articles WHERE guid = 'g1'")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        let article = RssService::get_article_content(&db, id.clone()).await.unwrap();
+        let content = article.content.unwrap();
+        assert!(!content.contains("script"));
+        assert!(!content.contains("onclick"));
+        assert!(content.contains("<p>"));
+        assert!(content.contains("href=\"https://example.com\""));
+        assert!(content.contains("<img src=\"https://example.com/a.png\">"));
+        assert_eq!(article.description.as_deref(), Some("<b>teaser</b>"));
+
+        // 原始内容保留未清理的版本
+        let raw = RssService::get_article_raw_content(&db, id.clone()).await.unwrap();
+        assert_eq!(raw.as_deref(), Some(malicious));
+
+        // 早期未经清理保存的内容在返回时清理
+        sqlx::query("UPDATE rss_articles SET content = '<p>old</p><script>alert(1)</script>' WHERE id = ?")
+            .bind(&id)
+            .execute(&db)
+            .await
+            .unwrap();
+        let article = RssService::get_article_content(&db, id).await.unwrap();
+        assert_eq!(article.content.as_deref(), Some("<p>old</p>"));
+    }
+
+    #[test]
+    fn test_resolve_relative_urls() {
+        use crate::utils::resolve_relative_urls;
+        use url::Url;
+
+        let base = Url::parse("https://example.com/post/").unwrap();
+        assert_eq!(
+            resolve_relative_urls("<p><img src=\"/images/foo.png\"></p>", &base),
+            "<p><img src=\"https://example.com/images/foo.png\"></p>"
+        );
+        assert_eq!(
+            resolve_relative_urls("<a href=\"next.html\">next</a>", &base),
+            "<a href=\"https://example.com/post/next.html\">next</a>"
+        );
+        assert_eq!(
+            resolve_relative_urls("<img srcset=\"a.png 1x, /b.png 2x, https://cdn.example.com/c.png 3x\">", &base),
+            "<img srcset=\"https://example.com/post/a.png 1x, https://example.com/b.png 2x, https://cdn.example.com/c.png 3x\">"
+        );
+        // 绝对地址、锚点和其他标签不变，没有需要改写的地址时原样返回
+        let untouched = "<p><a href=\"#top\">top</a> <a href=\"https://other.example/x\">x</a> <script src=\"/a.js\"></script></p>";
+        assert_eq!(resolve_relative_urls(untouched, &base), untouched);
+    }
+
+    #[tokio::test]
+    async fn test_get_articles_paginated() {
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        for id in ["a", "b"] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(format!("https://{}.example.com/rss", id))
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        for i in 0..5 {
+            let feed_id = if i < 3 { "a" } else { "b" };
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, guid, created_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(format!("article-{}", i))
+            .bind(feed_id)
+            .bind(format!("Article {}", i))
+            .bind(format!("guid-{}", i))
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let page = RssService::get_articles_paginated(&db, None, Some(2), Some(0), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(page.total, 5);
+        assert_eq!(page.articles.len(), 2);
+        assert_eq!((page.offset, page.limit), (0, 2));
+
+        // 总数和列表使用相同的筛选条件
+        let page = RssService::get_articles_paginated(&db, Some("a".to_string()), Some(2), Some(2), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(page.articles.len(), 1);
+        assert!(page.articles.iter().all(|a| a.feed_id == "a"));
+    }
+
+    #[tokio::test]
+    async fn test_get_articles_read_starred_filters() {
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        for id in ["a", "b"] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(format!("https://{}.example.com/rss", id))
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        let articles = [
+            ("unread", "a", false, false),
+            ("unread-starred", "a", false, true),
+            ("read-starred", "a", true, true),
+            ("read", "a", true, false),
+            ("other-unread-starred", "b", false, true),
+        ];
+        for (id, feed_id, is_read, is_starred) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, guid, is_read, is_starred, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(feed_id)
+            .bind(id)
+            .bind(id)
+            .bind(is_read)
+            .bind(is_starred)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let ids = |feed_id: Option<&str>, is_read: Option<bool>, is_starred: Option<bool>| {
+            let db = db.clone();
+            let feed_id = feed_id.map(str::to_string);
+            async move {
+                let mut ids: Vec<String> =
+                    RssService::get_articles(&db, feed_id, None, None, None, is_read, is_starred)
+                        .await
+                        .unwrap()
+                        .into_iter()
+                        .map(|a| a.id)
+                        .collect();
+                ids.sort();
+                ids
+            }
+        };
+
+        assert_eq!(ids(Some("a"), Some(false), None).await, vec!["unread", "unread-starred"]);
+        assert_eq!(ids(Some("a"), None, Some(true)).await, vec!["read-starred", "unread-starred"]);
+        assert_eq!(
+            ids(None, Some(false), Some(true)).await,
+            vec!["other-unread-starred", "unread-starred"]
+        );
+        assert_eq!(ids(Some("a"), Some(true), Some(false)).await, vec!["read"]);
+        assert_eq!(ids(None, None, None).await.len(), 5);
+
+        let page = RssService::get_articles_paginated(&db, None, Some(1), None, None, Some(false), Some(true))
+            .await
+            .unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(page.articles.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_article_tags() {
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        for id in ["a1", "a2", "a3"] {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, guid, created_at) VALUES (?, 'f', ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(id)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        RssService::add_article_tag(&db, "a1".to_string(), "Work".to_string()).await.unwrap();
+        // 名称不区分大小写，重复添加不报错
+        RssService::add_article_tag(&db, "a1".to_string(), " work ".to_string()).await.unwrap();
+        RssService::add_article_tag(&db, "a2".to_string(), "work".to_string()).await.unwrap();
+        RssService::add_article_tag(&db, "a2".to_string(), "read-later".to_string()).await.unwrap();
+        assert!(RssService::add_article_tag(&db, "missing".to_string(), "work".to_string()).await.is_err());
+        assert!(RssService::add_article_tag(&db, "a1".to_string(), "  ".to_string()).await.is_err());
+
+        let tags = RssService::list_tags(&db).await.unwrap();
+        let summary: Vec<(&str, u32)> = tags.iter().map(|t| (t.name.as_str(), t.article_count)).collect();
+        assert_eq!(summary, vec![("read-later", 1), ("Work", 2)]);
+
+        let mut tagged: Vec<String> = RssService::get_articles_by_tag(&db, "WORK".to_string(), None, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|a| a.id)
+            .collect();
+        tagged.sort();
+        assert_eq!(tagged, vec!["a1", "a2"]);
+
+        // 合并文章状态时标签一并复制
+        RssService::transfer_article_state(&db, "a2".to_string(), "a3".to_string()).await.unwrap();
+        assert_eq!(
+            RssService::get_articles_by_tag(&db, "read-later".to_string(), None, None).await.unwrap().len(),
+            2
+        );
+
+        // 删除文章时关联一并删除；标签不再使用时删除
+        sqlx::query("DELETE FROM rss_articles WHERE id = 'a3'")
+            .execute(&db)
+            .await
+            .unwrap();
+        let counts: Vec<u32> = RssService::list_tags(&db).await.unwrap().iter().map(|t| t.article_count).collect();
+        assert_eq!(counts, vec![1, 2]);
+        RssService::remove_article_tag(&db, "a1".to_string(), "work".to_string()).await.unwrap();
+        RssService::remove_article_tag(&db, "a2".to_string(), "work".to_string()).await.unwrap();
+        let names: Vec<String> = RssService::list_tags(&db).await.unwrap().into_iter().map(|t| t.name).collect();
+        assert_eq!(names, vec!["read-later"]);
+        assert!(RssService::remove_article_tag(&db, "a1".to_string(), "work".to_string()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_article_image_url_from_media_thumbnail() {
+        let (db, _db_dir) = setup_test_db().await;
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+
+        let xml = r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:media="http://search.yahoo.com/mrss/" xmlns:content="http://purl.org/rss/1.0/modules/content/">
+  <channel>
+    <title>Feed</title>
+    <link>https://example.com</link>
+    <item>
+      <title>With thumbnail</title>
+      <link>https://example.com/a</link>
+      <guid>a</guid>
+      <content:encoded><![CDATA[<p>Body</p><img src="https://example.com/inline-a.png">]]></content:encoded>
+      <media:thumbnail url="https://example.com/thumb.jpg" />
+    </item>
+    <item>
+      <title>Inline image</title>
+      <link>https://example.com/b</link>
+      <guid>b</guid>
+      <content:encoded><![CDATA[<p>Text</p><img src="/images/inline.png">]]></content:encoded>
+    </item>
+  </channel>
+</rss>"#;
+        let feed = crate::parser::parse_feed(None, xml.as_bytes()).unwrap();
+        assert_eq!(feed.entries[0].image_url.as_deref(), Some("https://example.com/thumb.jpg"));
+        assert_eq!(feed.entries[1].image_url, None);
+
+        RssService::save_articles(&db, "f", &feed.entries, &chrono::Utc::now()).await.unwrap();
+        let articles = RssService::get_articles(&db, Some("f".to_string()), None, None, None, None, None)
+            .await
+            .unwrap();
+        let image = |guid: &str| {
+            articles
+                .iter()
+                .find(|a| a.guid.as_deref() == Some(guid))
+                .and_then(|a| a.image_url.clone())
+        };
+        assert_eq!(image("a").as_deref(), Some("https://example.com/thumb.jpg"));
+        // 没有媒体信息时取正文中的第一张图片
+        assert_eq!(image("b").as_deref(), Some("https://example.com/images/inline.png"));
+    }
+
+    #[tokio::test]
+    async fn test_reading_statistics() {
+        use chrono::{Duration, Utc};
+
+        let (db, _db_dir) = setup_test_db().await;
+        let now = Utc::now();
+        for id in ["quiet", "busy"] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(format!("{} feed", id))
+            .bind(format!("https://{}.example.com/rss", id))
+            .bind(now.to_rfc3339())
+            .bind(now.to_rfc3339())
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        // (id, feed, 字数, 读完时间, 创建时间)
+        let recent = Some(now - Duration::days(1));
+        let old = now - Duration::days(30);
+        let articles = [
+            ("a", "quiet", 100, recent, old),
+            ("b", "quiet", 250, Some(old), old),
+            ("c", "busy", 40, None, now),
+            ("d", "busy", 60, None, now - Duration::days(2)),
+            ("e", "quiet", 10, None, now),
+        ];
+        for (id, feed_id, words, read_at, created_at) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, guid, word_count, is_read, read_at, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(feed_id)
+            .bind(id)
+            .bind(id)
+            .bind(words)
+            .bind(read_at.is_some())
+            .bind(read_at.map(|t| t.to_rfc3339()))
+            .bind(created_at.to_rfc3339())
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let stats = RssService::get_statistics(&db).await.unwrap();
+        // 原有字段保持不变
+        assert_eq!(stats["total_articles"], 5);
+        assert_eq!(stats["unread_articles"], 3);
+
+        let reading = &stats["reading"];
+        assert_eq!(reading["total_words_read"], 350);
+        assert_eq!(reading["articles_read_last_7_days"], 1);
+        assert_eq!(reading["most_active_feed"]["id"], "busy");
+        assert_eq!(reading["most_active_feed"]["article_count"], 2);
+    }
+
+    #[test]
+    fn test_reject_html_page() {
+        use crate::error::AppError;
+
+        let html = b"<!DOCTYPE html><html><head><title>404</title></head><body>Not Found</body></html>";
+        let err = RssService::reject_html_page("https://example.com/blog", None, html).unwrap_err();
+        assert!(matches!(err, AppError::InvalidRssUrl { hint: Some(_), .. }));
+        assert_eq!(err.code(), "invalid_url");
+        assert!(err.to_string().contains("HTML"));
+
+        // 以text/html返回但内容是XML的源仍按RSS解析
+        let rss = b"<?xml version=\"1.0\"?><rss version=\"2.0\"><channel></channel></rss>";
+        assert!(RssService::reject_html_page("https://example.com/rss", Some("text/html; charset=utf-8"), rss).is_ok());
+        assert!(RssService::reject_html_page("https://example.com/x", Some("text/html"), b"<div>oops</div>").is_err());
+        assert!(RssService::reject_html_page("https://example.com/rss", Some("application/rss+xml"), rss).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_save_articles_rolls_back_on_failure() {
+        use crate::parser::ParsedEntry;
+
+        let (db, _db_dir) = setup_test_db().await;
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        // 第二篇文章插入时失败
+        sqlx::query(
+            "CREATE TRIGGER fail_on_boom BEFORE INSERT ON rss_articles WHEN NEW.guid = 'boom'
+             BEGIN SELECT RAISE(ABORT, 'boom'); END"
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+
+        let entry = |id: &str| ParsedEntry {
+            id: id.to_string(),
+            title: Some(id.to_string()),
+            link: Some(format!("https://example.com/{}", id)),
+            content: Some("<p>body</p>".to_string()),
+            ..Default::default()
+        };
+        let entries = [entry("first"), entry("boom"), entry("third")];
+        assert!(RssService::save_articles(&db, "f", &entries, &chrono::Utc::now()).await.is_err());
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM rss_articles")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+
+        // 同一批中重复的guid只插入一次
+        let entries = [entry("first"), entry("first"), entry("third")];
+        let saved = RssService::save_articles(&db, "f", &entries, &chrono::Utc::now()).await.unwrap();
+        assert_eq!(saved, 2);
+    }
+
+    #[tokio::test]
+    async fn test_disable_enable_and_delete_feed() {
+        async fn article_count(db: &SqlitePool) -> i64 {
+            sqlx::query_scalar("SELECT COUNT(*) FROM rss_articles WHERE feed_id = 'f'")
+                .fetch_one(db)
+                .await
+                .unwrap()
+        }
+
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        sqlx::query("INSERT INTO rss_articles (id, feed_id, title, guid, created_at) VALUES ('a', 'f', 'A', 'a', ?)")
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+
+        RssService::disable_feed(&db, "f".to_string()).await.unwrap();
+        assert!(RssService::get_feeds(&db, false).await.unwrap().is_empty());
+        let all = RssService::get_feeds(&db, true).await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert!(!all[0].is_active);
+        // 停用不影响文章
+        assert_eq!(article_count(&db).await, 1);
+
+        RssService::enable_feed(&db, "f".to_string()).await.unwrap();
+        assert_eq!(RssService::get_feeds(&db, false).await.unwrap().len(), 1);
+        assert!(RssService::disable_feed(&db, "missing".to_string()).await.is_err());
+
+        RssService::delete_feed(&db, "f".to_string()).await.unwrap();
+        assert_eq!(article_count(&db).await, 0);
+        assert!(RssService::delete_feed(&db, "f".to_string()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_full_feed_content_skips_extraction() {
+        use crate::parser::ParsedEntry;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // 本地文章页面，记录被抓取的次数
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+                let body = format!("<html><body><article><p>{}</p></article></body></html>", "page ".repeat(300));
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let (db, _db_dir) = setup_test_db().await;
+        RssService::set_teaser_threshold(&db, 50).await.unwrap();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+
+        let full = format!("<p>{}</p>", "word ".repeat(120));
+        let entry = |id: &str, content: &str| ParsedEntry {
+            id: id.to_string(),
+            title: Some(id.to_string()),
+            link: Some(format!("http://{}/{}", addr, id)),
+            content: Some(content.to_string()),
+            ..Default::default()
+        };
+        RssService::save_articles(&db, "f", &[entry("full", &full)], &chrono::Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
+
+        let id: String = sqlx::query_scalar("SELECT id FROM rss_articles WHERE guid = 'full'")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        let article = RssService::get_article_content(&db, id).await.unwrap();
+        assert_eq!(article.content.as_deref(), Some(full.as_str()));
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
+
+        // 被截断的内容仍会抓取原文
+        let truncated = format!("{}<p><a href=\"/more\">Read more</a></p>", full);
+        RssService::save_articles(&db, "f", &[entry("truncated", &truncated)], &chrono::Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_http_settings() {
+        use crate::network;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let _guard = NETWORK_SETTINGS_LOCK.lock().await;
+        let (db, _db_dir) = setup_test_db().await;
+        assert!(network::set_http_settings(&db, 1, None).await.is_err());
+        assert!(network::set_http_settings(&db, 60, Some("bad\nagent".to_string())).await.is_err());
+
+        network::set_http_settings(&db, 45, Some(" you-know-test/1.0 ".to_string())).await.unwrap();
+        let settings = network::http_settings();
+        assert_eq!(settings.timeout_secs, 45);
+        assert_eq!(settings.user_agent, "you-know-test/1.0");
+
+        // 共用客户端发出的请求带有自定义User-Agent
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let n = socket.read(&mut buf).await.unwrap();
+            let _ = socket
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                .await;
+            String::from_utf8_lossy(&buf[..n]).to_lowercase()
+        });
+        RssService::build_http_client()
+            .unwrap()
+            .get(format!("http://{}/", addr))
+            .send()
+            .await
+            .unwrap();
+        assert!(server.await.unwrap().contains("user-agent: you-know-test/1.0"));
+
+        // 重新加载设置后保持一致，空User-Agent恢复默认值
+        network::load_settings(&db).await.unwrap();
+        assert_eq!(network::http_settings().user_agent, "you-know-test/1.0");
+        network::set_http_settings(&db, network::DEFAULT_HTTP_TIMEOUT_SECS, Some(String::new()))
+            .await
+            .unwrap();
+        network::load_settings(&db).await.unwrap();
+        assert_eq!(network::http_settings().user_agent, network::DEFAULT_USER_AGENT);
+    }
+
+    #[tokio::test]
+    async fn test_proxy_settings() {
+        use crate::models::ProxySettings;
+        use crate::network;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let _guard = NETWORK_SETTINGS_LOCK.lock().await;
+        // 本地代理：记录收到的请求并返回200
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let n = socket.read(&mut buf).await.unwrap();
+            let _ = socket
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                .await;
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let proxy = ProxySettings {
+            http_proxy: Some(format!("http://{}", addr)),
+            username: Some("user".to_string()),
+            password: Some("secret".to_string()),
+            ..Default::default()
+        };
+        let result = network::test_proxy(proxy, Some("http://feed.invalid/rss")).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.status, Some(200));
+        let request = server.await.unwrap();
+        assert!(request.starts_with("GET http://feed.invalid/rss "));
+        assert!(request.to_lowercase().contains("proxy-authorization: basic dxnlcjpzzwnyzxq="));
+
+        let (db, _db_dir) = setup_test_db().await;
+        let invalid = ProxySettings {
+            http_proxy: Some("not a proxy url".to_string()),
+            ..Default::default()
+        };
+        assert!(network::set_proxy(&db, invalid).await.is_err());
+
+        // 只设置HTTPS代理，避免影响其他测试中的本地HTTP请求
+        let proxy = ProxySettings {
+            https_proxy: Some(" http://127.0.0.1:9 ".to_string()),
+            username: Some("  ".to_string()),
+            ..Default::default()
+        };
+        network::set_proxy(&db, proxy).await.unwrap();
+        let stored = crate::settings::get_setting(&db, crate::settings::HTTPS_PROXY).await.unwrap();
+        network::set_proxy(&db, ProxySettings::default()).await.unwrap();
+        assert_eq!(stored.as_deref(), Some("http://127.0.0.1:9"));
+        let stored = crate::settings::get_setting(&db, crate::settings::HTTPS_PROXY).await.unwrap();
+        assert_eq!(stored.as_deref(), Some(""));
+    }
+
+    #[tokio::test]
+    async fn test_add_feed_rejects_duplicate_url() {
+        use crate::error::AppError;
+        use crate::models::AddFeedRequest;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let body = r#"<?xml version="1.0"?><rss version="2.0"><channel><title>Local</title></channel></rss>"#;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let (db, _db_dir) = setup_test_db().await;
+        let url = format!("http://{}/feed", addr);
+        let feed = RssService::add_feed_sync(&db, AddFeedRequest { url: url.clone(), ..Default::default() }).await.unwrap();
+        assert_eq!(feed.title, "Local");
+
+        for duplicate in [url.clone(), format!("{}/", url)] {
+            let result = RssService::add_feed_sync(&db, AddFeedRequest { url: duplicate, ..Default::default() }).await;
+            assert!(matches!(result, Err(AppError::FeedAlreadyExists { .. })));
+        }
+        assert_eq!(RssService::get_feeds(&db, true).await.unwrap().len(), 1);
+
+        // 主机名大小写不同也视为已订阅，不会发出请求
+        insert_test_feed(&db, "f", "https://Example.com/rss/").await;
+        let result = RssService::add_feed_sync(&db, AddFeedRequest { url: "https://example.com/rss".to_string(), ..Default::default() }).await;
+        assert!(matches!(result, Err(AppError::FeedAlreadyExists { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_export_feed_markdown() {
+        use crate::export::export_feed_markdown;
+
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query(
+            "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES ('f', 'Archive Feed', 'https://example.com/rss', ?, ?)"
+        )
+        .bind(&now)
+        .bind(&now)
+        .execute(&db)
+        .await
+        .unwrap();
+        let articles = [
+            ("later", "Second post", Some("<p>Hello <b>world</b></p>"), None, "2024-02-01T00:00:00+00:00"),
+            ("earlier", "First post", None, Some("<p>Only a summary</p>"), "2024-01-01T00:00:00+00:00"),
+        ];
+        for (id, title, content, description, published_at) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, link, content, description, author, guid, published_at, created_at)
+                 VALUES (?, 'f', ?, ?, ?, ?, 'Ann', ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(title)
+            .bind(format!("https://example.com/{}", id))
+            .bind(content)
+            .bind(description)
+            .bind(id)
+            .bind(published_at)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let markdown = export_feed_markdown(&db, "f").await.unwrap();
+        assert!(markdown.starts_with("# Archive Feed\n"));
+        assert!(markdown.contains("导出时间: "));
+        // 按发布时间从早到晚排列
+        let first = markdown.find("# First post").unwrap();
+        let second = markdown.find("# Second post").unwrap();
+        assert!(first < second);
+        assert!(markdown.contains("*2024-01-01 00:00 · Ann · [原文](https://example.com/earlier)*"));
+        assert!(markdown.contains("Only a summary"));
+        assert!(markdown.contains("Hello **world**"));
+        assert!(!markdown.contains("<p>"));
+
+        assert!(export_feed_markdown(&db, "missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_json_feed_stores_articles() {
+        use crate::models::AddFeedRequest;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let body = r#"{
+                    "version": "https://jsonfeed.org/version/1.1",
+                    "title": "JSON Only",
+                    "home_page_url": "https://example.org",
+                    "items": [
+                        {
+                            "id": "1",
+                            "url": "https://example.org/1",
+                            "title": "HTML item",
+                            "content_html": "<p>Rich body</p>",
+                            "date_published": "2024-01-02T03:04:05Z",
+                            "authors": [{ "name": "Ann" }]
+                        },
+                        {
+                            "id": "2",
+                            "url": "https://example.org/2",
+                            "title": "Text item",
+                            "content_text": "Line one & more\n\nSecond paragraph",
+                            "author": { "name": "Bob" }
+                        }
+                    ]
+                }"#;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/feed+json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let (db, _db_dir) = setup_test_db().await;
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+        let feed = RssService::add_feed_sync(&db, AddFeedRequest { url: format!("http://{}/feed.json", addr), ..Default::default() })
+            .await
+            .unwrap();
+        assert_eq!(feed.title, "JSON Only");
+        assert_eq!(feed.website_url.as_deref(), Some("https://example.org"));
+        // 刚添加的源受刷新频率限制，强制刷新
+        RssService::force_refresh_feed(&db, feed.id.clone()).await.unwrap();
+
+        let articles = RssService::get_articles(&db, Some(feed.id), None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(articles.len(), 2);
+        let article = |title: &str| articles.iter().find(|a| a.title == title).unwrap();
+        assert_eq!(article("HTML item").link.as_deref(), Some("https://example.org/1"));
+        assert_eq!(article("HTML item").author.as_deref(), Some("Ann"));
+        assert!(article("HTML item").published_at.is_some());
+        assert_eq!(article("Text item").author.as_deref(), Some("Bob"));
+
+        let text_item = RssService::get_article_content(&db, article("Text item").id.clone()).await.unwrap();
+        assert_eq!(
+            text_item.content.as_deref(),
+            Some("<p>Line one &amp; more</p><p>Second paragraph</p>")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_old_articles() {
+        use chrono::{Duration, Utc};
+
+        let (db, _db_dir) = setup_test_db().await;
+        let now = Utc::now();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+
+        let old = Some(now - Duration::days(40));
+        let recent = Some(now - Duration::days(3));
+        // (id, 发布时间, 保存时间, 已读, 收藏)
+        let articles = [
+            ("old-read", old, now, true, false),
+            ("old-unread", old, now, false, false),
+            ("old-starred", old, now, true, true),
+            ("recent-read", recent, now, true, false),
+            ("undated-old", None, now - Duration::days(60), true, false),
+            // 发布时间较新但保存得早，按发布时间判断
+            ("recent-saved-long-ago", recent, now - Duration::days(60), true, false),
+        ];
+        for (id, published_at, created_at, is_read, is_starred) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, guid, published_at, created_at, is_read, is_starred) VALUES (?, 'f', ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(id)
+            .bind(published_at.map(|t| t.to_rfc3339()))
+            .bind(created_at.to_rfc3339())
+            .bind(is_read)
+            .bind(is_starred)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        let remaining = |db: SqlitePool| async move {
+            let mut ids: Vec<String> = sqlx::query_scalar("SELECT id FROM rss_articles")
+                .fetch_all(&db)
+                .await
+                .unwrap();
+            ids.sort();
+            ids
+        };
+
+        assert!(RssService::cleanup_old_articles(&db, 0, true, false).await.is_err());
+        let preview = RssService::cleanup_old_articles(&db, 30, true, true).await.unwrap();
+        assert_eq!(preview.count, 2);
+        assert_eq!(remaining(db.clone()).await.len(), 6);
+
+        let report = RssService::cleanup_old_articles(&db, 30, true, false).await.unwrap();
+        assert_eq!(report.count, 2);
+        assert_eq!(
+            remaining(db.clone()).await,
+            vec!["old-starred", "old-unread", "recent-read", "recent-saved-long-ago"]
+        );
+
+        // 不限已读时也删除未读文章，但仍保留收藏
+        let report = RssService::cleanup_old_articles(&db, 30, false, false).await.unwrap();
+        assert_eq!(report.count, 1);
+        assert_eq!(
+            remaining(db.clone()).await,
+            vec!["old-starred", "recent-read", "recent-saved-long-ago"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_vacuum_database() {
+        let (db, db_dir) = setup_test_db().await;
+        let path = db_dir.path().join("test.db");
+
+        let now = chrono::Utc::now().to_rfc3339();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        let content = "x".repeat(100_000);
+        for i in 0..20 {
+            sqlx::query("INSERT INTO rss_articles (id, feed_id, title, guid, content, created_at) VALUES (?, 'f', 't', ?, ?, ?)")
+                .bind(i.to_string())
+                .bind(i.to_string())
+                .bind(&content)
+                .bind(&now)
+                .execute(&db)
+                .await
+                .unwrap();
+        }
+        sqlx::query("DELETE FROM rss_articles").execute(&db).await.unwrap();
+
+        let size = crate::database::database_size(&path).unwrap();
+        assert!(size > 1_000_000);
+        let report = crate::database::vacuum(&db, &path).await.unwrap();
+        assert_eq!(report.size_before, size);
+        assert!(report.reclaimed_bytes > 1_000_000);
+        assert_eq!(report.size_after, crate::database::database_size(&path).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_malformed_timestamps_do_not_break_queries() {
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query(
+            "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES ('f', 'Feed', 'https://example.com/rss', 'garbage', '2024-01-01 08:00:00')"
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        for (id, created_at) in [("good", now.as_str()), ("bad", "not a date"), ("sqlite", "2024-01-01 08:00:00")] {
+            sqlx::query("INSERT INTO rss_articles (id, feed_id, title, guid, created_at) VALUES (?, 'f', ?, ?, ?)")
+                .bind(id)
+                .bind(id)
+                .bind(id)
+                .bind(created_at)
+                .execute(&db)
+                .await
+                .unwrap();
+        }
+
+        let articles = RssService::get_articles(&db, None, None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(articles.len(), 3);
+        let sqlite = articles.iter().find(|a| a.id == "sqlite").unwrap();
+        assert_eq!(sqlite.created_at.to_rfc3339(), "2024-01-01T08:00:00+00:00");
+
+        let feeds = RssService::get_feeds(&db, true).await.unwrap();
+        assert_eq!(feeds.len(), 1);
+        assert_eq!(feeds[0].updated_at.to_rfc3339(), "2024-01-01T08:00:00+00:00");
+        assert!(RssService::get_article_content(&db, "bad".to_string()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_failed_extraction_cooldown() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // 原文链接已失效，记录被请求的次数
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+                let body = "<html><body><p>Not Found</p></body></html>";
+                let response = format!(
+                    "HTTP/1.1 404 Not Found\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        sqlx::query("INSERT INTO rss_articles (id, feed_id, title, link, guid, created_at) VALUES ('a', 'f', 'A', ?, 'a', ?)")
+            .bind(format!("http://{}/gone", addr))
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let article = RssService::get_article_content(&db, "a".to_string()).await.unwrap();
+        assert!(article.content.is_none());
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // 冷却期内再次打开不会重新请求
+        RssService::get_article_content(&db, "a".to_string()).await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // 手动提取忽略冷却时间
+        RssService::force_extract_content(&db, "a".to_string()).await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        // 冷却期过后恢复自动提取
+        let expired = (chrono::Utc::now() - chrono::Duration::hours(25)).to_rfc3339();
+        sqlx::query("UPDATE rss_articles SET extraction_failed_at = ? WHERE id = 'a'")
+            .bind(&expired)
+            .execute(&db)
+            .await
+            .unwrap();
+        RssService::get_article_content(&db, "a".to_string()).await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_favicon() {
+        use base64::Engine;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // 没有favicon.ico，首页通过<link rel="icon">声明图标
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                let (status, content_type, body) = if request.starts_with("GET /static/icon.png ") {
+                    ("200 OK", "image/png", "PNGDATA")
+                } else if request.starts_with("GET / ") {
+                    (
+                        "200 OK",
+                        "text/html",
+                        r#"<html><head><link rel="shortcut icon" href="/static/icon.png"></head></html>"#,
+                    )
+                } else {
+                    ("404 Not Found", "text/plain", "")
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    content_type,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        // 没有任何图标的网站，记录被请求的次数
+        let empty_listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let empty_addr = empty_listener.local_addr().unwrap();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = empty_listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+                let response = "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        for (id, website_url) in [("f", format!("http://{}/", addr)), ("empty", format!("http://{}/", empty_addr))] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, website_url, created_at, updated_at) VALUES (?, 'Feed', ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(format!("https://example.com/{}/rss", id))
+            .bind(&website_url)
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let feed = RssService::refresh_favicon(&db, "f").await.unwrap();
+        assert_eq!(feed.favicon_url, Some(format!("http://{}/static/icon.png", addr)));
+        assert_eq!(
+            RssService::get_feed_favicon(&db, "f").await.unwrap(),
+            Some(base64::engine::general_purpose::STANDARD.encode("PNGDATA"))
+        );
+
+        let feed = RssService::refresh_favicon(&db, "empty").await.unwrap();
+        assert!(feed.favicon_url.is_none());
+        assert!(RssService::get_feed_favicon(&db, "empty").await.unwrap().is_none());
+        assert!(RssService::get_feed_favicon(&db, "missing").await.is_err());
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        // 一天内不再重试
+        RssService::refresh_favicon(&db, "empty").await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        let expired = (chrono::Utc::now() - chrono::Duration::hours(25)).to_rfc3339();
+        sqlx::query("UPDATE rss_feeds SET favicon_checked_at = ? WHERE id = 'empty'")
+            .bind(&expired)
+            .execute(&db)
+            .await
+            .unwrap();
+        RssService::refresh_favicon(&db, "empty").await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_get_articles_after_cursor() {
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+
+        let newer = "2024-01-02T00:00:00+00:00";
+        let older = "2024-01-01T00:00:00+00:00";
+        for (id, published_at) in [
+            ("a", Some(newer)),
+            ("b", Some(newer)),
+            ("c", Some(older)),
+            ("d", None),
+            ("e", None),
+        ] {
+            sqlx::query("INSERT INTO rss_articles (id, feed_id, title, guid, published_at, created_at) VALUES (?, 'f', ?, ?, ?, ?)")
+                .bind(id)
+                .bind(id)
+                .bind(id)
+                .bind(published_at)
+                .bind(&now)
+                .execute(&db)
+                .await
+                .unwrap();
+        }
+
+        let mut seen = Vec::new();
+        let mut cursor: Option<(Option<chrono::DateTime<chrono::Utc>>, String)> = None;
+        loop {
+            let page = RssService::get_articles_after(
+                &db,
+                cursor.as_ref().and_then(|(published_at, _)| *published_at),
+                cursor.as_ref().map(|(_, id)| id.clone()),
+                Some(2),
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+            if page.is_empty() {
+                break;
+            }
+            let last = page.last().unwrap();
+            cursor = Some((last.published_at, last.id.clone()));
+            seen.extend(page.into_iter().map(|article| article.id));
+
+            // 翻页期间新增的文章不影响后续页
+            if seen.len() == 2 {
+                sqlx::query("INSERT INTO rss_articles (id, feed_id, title, guid, published_at, created_at) VALUES ('z', 'f', 'z', 'z', '2024-02-01T00:00:00+00:00', ?)")
+                    .bind(&now)
+                    .execute(&db)
+                    .await
+                    .unwrap();
+            }
+        }
+        assert_eq!(seen, vec!["b", "a", "c", "e", "d"]);
+    }
+
+    #[tokio::test]
+    async fn test_update_articles_bulk() {
+        use sqlx::Row;
+
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        // 超过单条语句的分块大小
+        let ids: Vec<String> = (0..1200).map(|i| format!("a{}", i)).collect();
+        for id in &ids {
+            sqlx::query("INSERT INTO rss_articles (id, feed_id, title, guid, created_at) VALUES (?, 'f', ?, ?, ?)")
+                .bind(id)
+                .bind(id)
+                .bind(id)
+                .bind(&now)
+                .execute(&db)
+                .await
+                .unwrap();
+        }
+
+        let mut targets = ids[..1100].to_vec();
+        targets.push("missing".to_string());
+        let updated = RssService::update_articles_bulk(&db, targets, Some(true), None).await.unwrap();
+        assert_eq!(updated, 1100);
+        let read: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM rss_articles WHERE is_read = 1 AND read_at IS NOT NULL")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(read, 1100);
+        let unread_count = RssService::get_feed(&db, "f").await.unwrap().unread_count;
+        assert_eq!(unread_count, 100);
+
+        // 只修改收藏状态时已读状态不变
+        let updated = RssService::update_articles_bulk(&db, vec!["a0".to_string(), "a1199".to_string()], None, Some(true))
+            .await
+            .unwrap();
+        assert_eq!(updated, 2);
+        let row = sqlx::query("SELECT is_read, is_starred FROM rss_articles WHERE id = 'a0'")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert!(row.get::<bool, _>("is_read"));
+        assert!(row.get::<bool, _>("is_starred"));
+
+        let updated = RssService::update_articles_bulk(&db, vec!["a0".to_string()], Some(false), None).await.unwrap();
+        assert_eq!(updated, 1);
+        let read_at: Option<String> = sqlx::query_scalar("SELECT read_at FROM rss_articles WHERE id = 'a0'")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert!(read_at.is_none());
+        assert_eq!(RssService::update_articles_bulk(&db, ids, None, None).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_preview_feed() {
+        use crate::error::AppError;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let items: String = (1..=12)
+                    .map(|i| format!("<item><title>Item {}</title><guid>{}</guid></item>", i, i))
+                    .collect();
+                let body = format!(
+                    r#"<?xml version="1.0"?><rss version="2.0"><channel><title>Preview</title><description>About</description><link>https://example.com</link>{}</channel></rss>"#,
+                    items
+                );
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let preview = RssService::preview_feed(&format!("http://{}/feed", addr), None, None).await.unwrap();
+        assert_eq!(preview.title.as_deref(), Some("Preview"));
+        assert_eq!(preview.description.as_deref(), Some("About"));
+        assert_eq!(preview.article_titles.len(), 10);
+        assert_eq!(preview.article_titles[0], "Item 1");
+
+        let result = RssService::preview_feed("ftp://example.com/feed", None, None).await;
+        assert!(matches!(result, Err(AppError::InvalidRssUrl { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_follows_permanent_redirect() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                let response = if request.starts_with("GET /old ") {
+                    "HTTP/1.1 301 Moved Permanently\r\nLocation: /new\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+                } else if request.starts_with("GET /temp ") {
+                    "HTTP/1.1 302 Found\r\nLocation: /new\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+                } else {
+                    let body = r#"<?xml version="1.0"?><rss version="2.0"><channel><title>Moved</title><item><title>A</title><guid>a</guid><description>Body</description></item></channel></rss>"#;
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                };
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let (db, _db_dir) = setup_test_db().await;
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+        for (id, path) in [("moved", "old"), ("temporary", "temp")] {
+            insert_test_feed(&db, id, &format!("http://{}/{}", addr, path)).await;
+        }
+
+        RssService::force_refresh_feed(&db, "moved".to_string()).await.unwrap();
+        let feed = RssService::get_feed(&db, "moved").await.unwrap();
+        assert_eq!(feed.url, format!("http://{}/new", addr));
+        assert_eq!(feed.last_fetched_url, Some(format!("http://{}/new", addr)));
+
+        // 临时重定向不修改地址
+        RssService::force_refresh_feed(&db, "temporary".to_string()).await.unwrap();
+        let feed = RssService::get_feed(&db, "temporary").await.unwrap();
+        assert_eq!(feed.url, format!("http://{}/temp", addr));
+
+        // 新地址已被其他源订阅时保留原地址
+        insert_test_feed(&db, "other", &format!("http://{}/old", addr)).await;
+        RssService::force_refresh_feed(&db, "other".to_string()).await.unwrap();
+        let feed = RssService::get_feed(&db, "other").await.unwrap();
+        assert_eq!(feed.url, format!("http://{}/old", addr));
+    }
+
+    #[tokio::test]
+    async fn test_unread_counts() {
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        for feed_id in ["f1", "f2"] {
+            insert_test_feed(&db, feed_id, &format!("https://example.com/{}", feed_id)).await;
+        }
+        for (id, feed_id, is_read) in [("a", "f1", false), ("b", "f1", true), ("c", "f1", false), ("d", "f2", false)] {
+            sqlx::query("INSERT INTO rss_articles (id, feed_id, title, guid, is_read, created_at) VALUES (?, ?, ?, ?, ?, ?)")
+                .bind(id)
+                .bind(feed_id)
+                .bind(id)
+                .bind(id)
+                .bind(is_read)
+                .bind(&now)
+                .execute(&db)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(RssService::get_feed_unread_count(&db, "f1").await.unwrap(), 2);
+        assert_eq!(RssService::get_feed_unread_count(&db, "f2").await.unwrap(), 1);
+        assert_eq!(RssService::get_total_unread_count(&db).await.unwrap(), 3);
+        assert!(RssService::get_feed_unread_count(&db, "missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_render_service_url() {
+        use crate::render;
+
+        assert_eq!(
+            render::service_request_url("http://localhost:3000/", "https://example.com/a?b=1"),
+            "http://localhost:3000/https://example.com/a?b=1"
+        );
+        assert_eq!(
+            render::service_request_url("https://render.local/render?url={url}", "https://example.com/a?b=1"),
+            "https://render.local/render?url=https%3A%2F%2Fexample.com%2Fa%3Fb%3D1"
+        );
+
+        // 无效地址不会被保存
+        let (db, _db_dir) = setup_test_db().await;
+        assert!(render::set_service_url(&db, Some("not a url".to_string())).await.is_err());
+        assert!(render::set_service_url(&db, Some("ftp://render.local/".to_string())).await.is_err());
+        assert!(render::service_url().is_none());
+        assert_eq!(crate::utils::text_char_count("<p> ab </p><p>中文</p>"), 4);
+    }
+
+    #[tokio::test]
+    async fn test_dedup_across_feeds() {
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now();
+        for feed_id in ["site", "aggregator"] {
+            insert_test_feed(&db, feed_id, &format!("https://example.com/{}", feed_id)).await;
+        }
+        // 聚合源转载的链接带有跟踪参数，规范化后与原站相同
+        for (id, feed_id, link, age_minutes) in [
+            ("original", "site", "https://example.com/post", 10),
+            ("copy", "aggregator", "https://www.Example.com/post/?utm_source=agg", 5),
+            ("other", "aggregator", "https://example.com/other", 1),
+        ] {
+            sqlx::query("INSERT INTO rss_articles (id, feed_id, title, link, guid, created_at) VALUES (?, ?, ?, ?, ?, ?)")
+                .bind(id)
+                .bind(feed_id)
+                .bind(id)
+                .bind(link)
+                .bind(id)
+                .bind((now - chrono::Duration::minutes(age_minutes)).to_rfc3339())
+                .execute(&db)
+                .await
+                .unwrap();
+        }
+
+        let ids = |articles: Vec<crate::models::RssArticle>| {
+            let mut ids: Vec<String> = articles.into_iter().map(|a| a.id).collect();
+            ids.sort();
+            ids
+        };
+        let all = RssService::get_articles(&db, None, None, None, None, None, None).await.unwrap();
+        assert_eq!(ids(all), vec!["copy", "original", "other"]);
+
+        RssService::set_dedup_across_feeds(&db, true).await.unwrap();
+        let merged = RssService::get_articles(&db, None, None, None, None, None, None).await.unwrap();
+        assert_eq!(ids(merged), vec!["original", "other"]);
+        let page = RssService::get_articles_paginated(&db, None, None, None, None, None, None).await.unwrap();
+        assert_eq!(page.total, 2);
+
+        // 按RSS源筛选时显示该源的全部文章
+        let aggregator = RssService::get_articles(&db, Some("aggregator".to_string()), None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(ids(aggregator), vec!["copy", "other"]);
+    }
+
+    #[tokio::test]
+    async fn test_request_errors_distinguish_timeout_and_status() {
+        use crate::error::AppError;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // 接受连接但不返回响应
+        let silent = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let silent_addr = silent.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((socket, _)) = silent.accept().await {
+                sockets.push(socket);
+            }
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket
+                    .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                    .await;
+            }
+        });
+
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_millis(200))
+            .build()
+            .unwrap();
+        let url = format!("http://{}/feed", silent_addr);
+        let e = client.get(&url).send().await.unwrap_err();
+        let error = AppError::request_failed(&url, &e);
+        assert_eq!(error.code(), "timeout");
+        let value = serde_json::to_value(&error).unwrap();
+        assert_eq!(value["url"], url.as_str());
+        assert_eq!(AppError::from(e).code(), "timeout");
+        assert_eq!(AppError::timeout("u").code(), "timeout");
+
+        let url = format!("http://{}/feed", addr);
+        let e = client.get(&url).send().await.unwrap().error_for_status().unwrap_err();
+        let value = serde_json::to_value(AppError::from(e)).unwrap();
+        assert_eq!(value["code"], "http_status");
+        assert_eq!(value["status"], 404);
+    }
+
+    #[tokio::test]
+    async fn test_mark_read_on_open() {
+        use crate::settings;
+
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        for id in ["a", "b"] {
+            sqlx::query("INSERT INTO rss_articles (id, feed_id, title, content, guid, created_at) VALUES (?, 'f', ?, '<p>Body</p>', ?, ?)")
+                .bind(id)
+                .bind(id)
+                .bind(id)
+                .bind(&now)
+                .execute(&db)
+                .await
+                .unwrap();
+        }
+
+        // 默认关闭，打开文章不改变阅读状态
+        let article = RssService::get_article_content(&db, "a".to_string()).await.unwrap();
+        assert!(!article.is_read);
+        assert!(!RssService::get_article_content(&db, "a".to_string()).await.unwrap().is_read);
+
+        settings::set_bool(&db, settings::MARK_READ_ON_OPEN, true).await.unwrap();
+        let article = RssService::get_article_content(&db, "b".to_string()).await.unwrap();
+        assert!(article.is_read);
+        assert!(article.read_at.is_some());
+        let stored: bool = sqlx::query_scalar("SELECT is_read FROM rss_articles WHERE id = 'b'")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert!(stored);
+        assert_eq!(RssService::get_feed(&db, "f").await.unwrap().unread_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_feed_health_tracking() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // 先返回404，恢复后返回正常的RSS
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let healthy = Arc::new(AtomicBool::new(false));
+        let flag = healthy.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let response = if flag.load(Ordering::SeqCst) {
+                    let body = r#"<?xml version="1.0"?><rss version="2.0"><channel><title>Back</title></channel></rss>"#;
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                } else {
+                    "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+                };
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let (db, _db_dir) = setup_test_db().await;
+        insert_test_feed(&db, "f", &format!("http://{}/feed", addr)).await;
+
+        for _ in 0..3 {
+            assert!(RssService::force_refresh_feed(&db, "f".to_string()).await.is_err());
+        }
+        let feed = RssService::get_feed(&db, "f").await.unwrap();
+        assert_eq!(feed.consecutive_failures, 3);
+        assert!(feed.last_error.unwrap().contains("404"));
+        assert!(feed.last_error_at.is_some());
+        assert_eq!(RssService::get_unhealthy_feeds(&db, None).await.unwrap().len(), 1);
+        assert!(RssService::get_unhealthy_feeds(&db, Some(4)).await.unwrap().is_empty());
+
+        healthy.store(true, Ordering::SeqCst);
+        RssService::force_refresh_feed(&db, "f".to_string()).await.unwrap();
+        let feed = RssService::get_feed(&db, "f").await.unwrap();
+        assert_eq!(feed.consecutive_failures, 0);
+        assert!(feed.last_error.is_none());
+        assert!(feed.last_error_at.is_none());
+        assert!(RssService::get_unhealthy_feeds(&db, Some(1)).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_feed_auto_disabled_after_failures() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket
+                    .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                    .await;
+            }
+        });
+
+        let (db, _db_dir) = setup_test_db().await;
+        assert_eq!(RssService::get_auto_disable_failures(&db).await.unwrap(), 10);
+        RssService::set_auto_disable_failures(&db, 3).await.unwrap();
+        insert_test_feed(&db, "f", &format!("http://{}/feed", addr)).await;
+
+        for _ in 0..2 {
+            assert!(RssService::force_refresh_feed(&db, "f".to_string()).await.is_err());
+        }
+        assert!(RssService::get_feed(&db, "f").await.unwrap().is_active);
+
+        assert!(RssService::force_refresh_feed(&db, "f".to_string()).await.is_err());
+        let feed = RssService::get_feed(&db, "f").await.unwrap();
+        assert!(!feed.is_active);
+        assert_eq!(feed.consecutive_failures, 3);
+        assert!(RssService::get_feeds(&db, false).await.unwrap().is_empty());
+
+        let feed = RssService::reactivate_feed(&db, "f".to_string()).await.unwrap();
+        assert!(feed.is_active);
+        assert_eq!(feed.consecutive_failures, 0);
+        assert!(feed.last_error.is_none());
+        assert!(RssService::reactivate_feed(&db, "missing".to_string()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_add_feed_uses_declared_updated_date() {
+        use crate::models::AddFeedRequest;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let body = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Dated Feed</title>
+  <id>urn:example:dated</id>
+  <updated>2024-01-02T03:04:05Z</updated>
+  <entry>
+    <title>Entry</title>
+    <id>urn:example:dated:1</id>
+    <link href="https://example.org/1"/>
+    <updated>2024-01-02T03:04:05Z</updated>
+  </entry>
+</feed>"#;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/atom+xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let (db, _db_dir) = setup_test_db().await;
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+        let feed = RssService::add_feed_sync(&db, AddFeedRequest { url: format!("http://{}/atom.xml", addr), ..Default::default() })
+            .await
+            .unwrap();
+        assert_eq!(feed.title, "Dated Feed");
+        assert_eq!(
+            feed.last_updated.map(|u| u.to_rfc3339()).as_deref(),
+            Some("2024-01-02T03:04:05+00:00")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_site_rule_overrides_extraction() {
+        use crate::models::{ExtractionStrategy, SiteRuleRequest};
+        use crate::site_rules;
+
+        let (db, _db_dir) = setup_test_db().await;
+        let rule = |domain: &str, strategy, selector: Option<&str>| SiteRuleRequest {
+            domain: domain.to_string(),
+            strategy,
+            selector: selector.map(str::to_string),
+        };
+
+        // 选择器方式必须提供有效的选择器
+        assert!(site_rules::set_rule(&db, rule("rules-test.example", ExtractionStrategy::Selectors, None)).await.is_err());
+        assert!(site_rules::set_rule(&db, rule("rules-test.example", ExtractionStrategy::Selectors, Some("div[")))
+            .await
+            .is_err());
+
+        let saved = site_rules::set_rule(
+            &db,
+            rule("https://Rules-Test.example/path", ExtractionStrategy::Selectors, Some(".story")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(saved.domain, "rules-test.example");
+        assert_eq!(site_rules::get_rules(&db).await.unwrap().len(), 1);
+
+        let html = format!(
+            r#"<html><body><article>{}</article><div class="story"><p>Real body <img src="/a.png"></p></div></body></html>"#,
+            "Navigation and sidebar text. ".repeat(20)
+        );
+        // 子域名同样使用该规则，相对地址被转换为绝对地址
+        let content = RssService::extract_from_html(&html, "https://news.rules-test.example/post").unwrap();
+        assert!(content.contains("Real body"));
+        assert!(content.contains("https://news.rules-test.example/a.png"));
+        assert!(!content.contains("Navigation"));
+
+        // 修改为段落方式时不保留选择器
+        let updated = site_rules::set_rule(&db, rule("rules-test.example", ExtractionStrategy::Paragraphs, Some(".story")))
+            .await
+            .unwrap();
+        assert_eq!(updated.strategy, ExtractionStrategy::Paragraphs);
+        assert!(updated.selector.is_none());
+        assert_eq!(site_rules::get_rules(&db).await.unwrap().len(), 1);
+
+        assert!(site_rules::delete_rule(&db, "rules-test.example").await.unwrap());
+        assert!(!site_rules::delete_rule(&db, "rules-test.example").await.unwrap());
+        assert!(site_rules::rule_for_url("https://news.rules-test.example/post").is_none());
+    }
+
+    #[test]
+    fn test_clean_element_text_skips_boilerplate() {
+        let html = r#"<article>
+            <header>Site header</header>
+            <p>First paragraph of the story.</p>
+            <nav><a href="/">Home</a> <a href="/about">About</a></nav>
+            <div class="ad">Buy now</div>
+            <div role="navigation">Next article</div>
+            <p>Second <em>paragraph</em>.</p>
+            <script>var tracking = 1;</script>
+            <aside>Related links</aside>
+            <footer>Copyright</footer>
+        </article>"#;
+        let document = scraper::Html::parse_document(html);
+        let selector = scraper::Selector::parse("article").unwrap();
+        let text = crate::utils::clean_element_text(document.select(&selector).next().unwrap());
+
+        assert!(text.contains("First paragraph of the story."));
+        assert!(text.contains("Second"));
+        for junk in ["Site header", "Home", "About", "Buy now", "Next article", "tracking", "Related links", "Copyright"] {
+            assert!(!text.contains(junk), "{} 应被去除", junk);
+        }
+
+        // 按段落提取时同样跳过位于非正文区域中的段落
+        let page = scraper::Html::parse_document(
+            r#"<body>
+                <p>The story begins with a long enough paragraph.</p>
+                <footer><p>Copyright notice that is long enough to keep.</p></footer>
+                <aside><div><p>Related links paragraph that is long enough.</p></div></aside>
+            </body>"#,
+        );
+        let paragraphs = RssService::extract_paragraphs(&page).unwrap();
+        assert_eq!(paragraphs, "The story begins with a long enough paragraph.");
+    }
+
+    #[tokio::test]
+    async fn test_feed_with_basic_auth() {
+        use crate::models::AddFeedRequest;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 2048];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                // "reader:secret"的base64编码
+                let response = if request.contains("Basic cmVhZGVyOnNlY3JldA==") {
+                    let body = r#"<?xml version="1.0"?><rss version="2.0"><channel><title>Private</title>
+                        <item><title>Paid post</title><link>https://example.org/paid</link><guid>paid-1</guid><description>Members only</description></item>
+                        </channel></rss>"#;
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                } else {
+                    "HTTP/1.1 401 Unauthorized\r\nWWW-Authenticate: Basic realm=\"feed\"\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+                };
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let (db, _db_dir) = setup_test_db().await;
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+        let url = format!("http://{}/private.xml", addr);
+
+        // 没有认证信息时服务器拒绝请求
+        let result = RssService::add_feed_sync(&db, AddFeedRequest { url: url.clone(), ..Default::default() }).await;
+        assert!(result.is_err());
+        assert!(RssService::preview_feed(&url, None, None).await.is_err());
+
+        // 预览同样使用认证信息
+        let preview = RssService::preview_feed(&url, Some("reader".to_string()), Some("secret".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(preview.article_titles, vec!["Paid post".to_string()]);
+
+        let feed = RssService::add_feed_sync(
+            &db,
+            AddFeedRequest {
+                url: url.clone(),
+                username: Some("reader".to_string()),
+                password: Some("secret".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(feed.title, "Private");
+        assert_eq!(feed.username.as_deref(), Some("reader"));
+
+        // 刷新时使用保存的认证信息
+        RssService::force_refresh_feed(&db, feed.id.clone()).await.unwrap();
+        let articles = RssService::get_articles(&db, Some(feed.id.clone()), None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(articles.len(), 1);
+
+        // 清除认证信息后刷新失败
+        let cleared = RssService::set_feed_credentials(&db, &feed.id, None, Some("secret".to_string()))
+            .await
+            .unwrap();
+        assert!(cleared.username.is_none());
+        assert!(RssService::force_refresh_feed(&db, feed.id.clone()).await.is_err());
+        assert!(RssService::set_feed_credentials(&db, "missing", None, None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_global_max_content_bytes_truncates_on_tag_boundary() {
+        use crate::parser::ParsedEntry;
+        use crate::rss::CONTENT_TRUNCATED_MARKER;
+        use crate::utils::truncate_html;
+
+        // 截断位置落在标签内部时退回到标签之前
+        assert_eq!(truncate_html("<p>abc</p>", 20, "~"), "<p>abc</p>");
+        assert_eq!(truncate_html("<p>abcdef</p><p>gh</p>", 17, "~"), "<p>abcdef</p>~");
+
+        let (db, _db_dir) = setup_test_db().await;
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+        assert_eq!(
+            RssService::get_max_content_bytes(&db).await.unwrap(),
+            crate::rss::DEFAULT_MAX_CONTENT_BYTES
+        );
+        assert!(RssService::set_max_content_bytes(&db, 100).await.is_err());
+        RssService::set_max_content_bytes(&db, 4096).await.unwrap();
+
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+
+        let body: String = (0..200)
+            .map(|i| format!("<p>Paragraph number {} of a very long article.</p>", i))
+            .collect();
+        let entries = vec![ParsedEntry {
+            id: "huge".to_string(),
+            title: Some("Huge".to_string()),
+            content: Some(body),
+            ..Default::default()
+        }];
+        RssService::save_articles(&db, "f", &entries, &chrono::Utc::now())
+            .await
+            .unwrap();
+
+        let articles = RssService::get_articles(&db, Some("f".to_string()), None, None, None, None, None)
+            .await
+            .unwrap();
+        let content = articles[0].content.clone().unwrap();
+        assert!(content.len() <= 4096);
+        let kept = content.strip_suffix(CONTENT_TRUNCATED_MARKER).unwrap();
+        assert!(kept.ends_with("</p>"));
+        assert!(kept.starts_with("<p>Paragraph number 0 "));
+    }
+
+    #[tokio::test]
+    async fn test_backup_database_to_path() {
+        use crate::backup;
+        use crate::error::AppError;
+
+        let (db, _db_dir) = setup_test_db().await;
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("before-upgrade.db");
+        let bytes = backup::backup_database(&db, &dest).await.unwrap();
+        assert!(bytes > 0);
+        assert_eq!(std::fs::metadata(&dest).unwrap().len(), bytes);
+
+        // 备份可以作为独立的数据库打开
+        let copy = SqlitePool::connect(&format!("sqlite:{}", dest.display())).await.unwrap();
+        let title: String = sqlx::query_scalar("SELECT title FROM rss_feeds WHERE id = 'f'")
+            .fetch_one(&copy)
+            .await
+            .unwrap();
+        assert_eq!(title, "Feed");
+
+        // 目标目录不存在
+        let missing = dir.path().join("missing").join("backup.db");
+        assert!(matches!(backup::backup_database(&db, &missing).await, Err(AppError::Io(_))));
+        // 已存在的文件不会被覆盖
+        assert!(backup::backup_database(&db, &dest).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_restore_database_from_backup() {
+        use crate::backup;
+        use crate::database;
+        use crate::models::AppState;
+
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("app.db");
+        let state = AppState::new(database::open_database(&db_path).await.unwrap());
+
+        let insert_feed = |db: SqlitePool, id: &'static str| async move {
+            let now = chrono::Utc::now().to_rfc3339();
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(format!("https://example.com/{}", id))
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        };
+        let feed_ids = |db: SqlitePool| async move {
+            sqlx::query_scalar::<_, String>("SELECT id FROM rss_feeds ORDER BY id")
+                .fetch_all(&db)
+                .await
+                .unwrap()
+        };
+
+        insert_feed(state.db(), "kept").await;
+        let backup_path = dir.path().join("backup.db");
+        backup::backup_database(&state.db(), &backup_path).await.unwrap();
+        insert_feed(state.db(), "after-backup").await;
+
+        // 不是数据库的文件和缺少数据表的数据库都被拒绝
+        let not_db = dir.path().join("notes.txt");
+        std::fs::write(&not_db, "not a database").unwrap();
+        assert!(backup::restore_database(&state, &not_db, &db_path).await.is_err());
+        let empty = dir.path().join("empty.db");
+        let empty_pool = database::open_database(&empty).await.unwrap();
+        sqlx::query("DROP TABLE rss_articles").execute(&empty_pool).await.unwrap();
+        empty_pool.close().await;
+        assert!(backup::restore_database(&state, &empty, &db_path).await.is_err());
+
+        // 迁移版本不一致的备份被拒绝
+        let newer = dir.path().join("newer.db");
+        std::fs::copy(&backup_path, &newer).unwrap();
+        let newer_pool = SqlitePool::connect(&format!("sqlite:{}", newer.display())).await.unwrap();
+        sqlx::query(
+            "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time) VALUES (99990101000000, 'future', 1, x'00', 0)",
+        )
+        .execute(&newer_pool)
+        .await
+        .unwrap();
+        newer_pool.close().await;
+        let err = backup::restore_database(&state, &newer, &db_path).await.unwrap_err();
+        assert!(err.to_string().contains("版本"));
+
+        // 校验失败时当前数据库不受影响
+        assert_eq!(feed_ids(state.db()).await, vec!["after-backup", "kept"]);
+
+        let old_pool = state.db();
+        backup::restore_database(&state, &backup_path, &db_path).await.unwrap();
+        assert!(old_pool.is_closed());
+        assert_eq!(feed_ids(state.db()).await, vec!["kept"]);
+        assert!(!db_path.with_extension("pre-restore").exists());
+        assert!(!db_path.with_extension("restore").exists());
+    }
+
+    #[tokio::test]
+    async fn test_dedupe_articles_by_link_merges_user_state() {
+        use sqlx::Row;
+
+        let (db, _db_dir) = setup_test_db().await;
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        let articles = [
+            ("keep", "https://example.com/post?id=7&utm_source=rss", false, None, "2024-01-01T00:00:00+00:00"),
+            ("dup", "https://www.example.com/post?utm_medium=x&id=7", true, Some("2024-02-01T00:00:00+00:00"), "2024-01-02T00:00:00+00:00"),
+            // utm参数之后的参数不同，是不同的文章
+            ("other", "https://example.com/post?utm_source=rss&id=8", false, None, "2024-01-03T00:00:00+00:00"),
+        ];
+        for (id, link, is_read, read_at, created_at) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, link, guid, is_read, read_at, reader_prefs, created_at) VALUES (?, 'f', ?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(link)
+            .bind(id)
+            .bind(is_read)
+            .bind(read_at)
+            .bind((id == "dup").then_some(r#"{"font_size":18}"#))
+            .bind(created_at)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        RssService::backfill_link_keys(&db).await.unwrap();
+
+        assert_eq!(RssService::dedupe_articles_by_link(&db).await.unwrap(), 1);
+        assert_eq!(RssService::dedupe_articles_by_link(&db).await.unwrap(), 0);
+
+        let ids: Vec<String> = sqlx::query_scalar("SELECT id FROM rss_articles ORDER BY id")
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        assert_eq!(ids, vec!["keep", "other"]);
+        let kept = sqlx::query("SELECT is_read, read_at, reader_prefs FROM rss_articles WHERE id = 'keep'")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert!(kept.get::<bool, _>("is_read"));
+        assert_eq!(kept.get::<Option<String>, _>("read_at").as_deref(), Some("2024-02-01T00:00:00+00:00"));
+        assert!(kept.get::<Option<String>, _>("reader_prefs").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_export_tag() {
+        use crate::export::export_tag;
+        use crate::models::ExportFormat;
+
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        for (id, title) in [("a", "Alpha Feed"), ("b", "Beta Feed")] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(title)
+            .bind(format!("https://{}.example.com/rss", id))
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        let articles = [
+            ("later", "a", "Later post", "2024-02-01T00:00:00+00:00"),
+            ("earlier", "b", "Earlier post", "2024-01-01T00:00:00+00:00"),
+            ("untagged", "a", "Untagged post", "2024-03-01T00:00:00+00:00"),
+        ];
+        for (id, feed_id, title, published_at) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, link, content, guid, published_at, created_at)
+                 VALUES (?, ?, ?, ?, '<p>Body</p>', ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(feed_id)
+            .bind(title)
+            .bind(format!("https://example.com/{}", id))
+            .bind(id)
+            .bind(published_at)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        RssService::add_article_tag(&db, "later".to_string(), "rust-async".to_string()).await.unwrap();
+        RssService::add_article_tag(&db, "earlier".to_string(), "Rust-Async".to_string()).await.unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let md_path = dir.path().join("tag.md");
+        assert_eq!(export_tag(&db, "RUST-ASYNC", ExportFormat::Markdown, &md_path).await.unwrap(), 2);
+        let markdown = std::fs::read_to_string(&md_path).unwrap();
+        let earlier = markdown.find("# Earlier post").unwrap();
+        let later = markdown.find("# Later post").unwrap();
+        assert!(earlier < later);
+        assert!(markdown.contains("*Beta Feed · 2024-01-01 00:00 · [原文](https://example.com/earlier)*"));
+        assert!(!markdown.contains("Untagged post"));
+
+        let html_path = dir.path().join("tag.html");
+        export_tag(&db, "rust-async", ExportFormat::Html, &html_path).await.unwrap();
+        let html = std::fs::read_to_string(&html_path).unwrap();
+        assert!(html.contains("<h2>Earlier post</h2>"));
+        assert!(html.contains("Alpha Feed"));
+        assert!(html.contains("<p>Body</p>"));
+
+        let json_path = dir.path().join("tag.json");
+        export_tag(&db, "rust-async", ExportFormat::Json, &json_path).await.unwrap();
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+        let items = json.as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0]["article"]["id"], "earlier");
+        assert_eq!(items[0]["feed_title"], "Beta Feed");
+    }
+
+    #[tokio::test]
+    async fn test_prune_articles_keeps_latest_per_feed() {
+        let (db, _db_dir) = setup_test_db().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        for id in ["a", "b"] {
+            sqlx::query(
+                "INSERT INTO rss_feeds (id, title, url, created_at, updated_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(format!("https://{}.example.com/rss", id))
+            .bind(&now)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        // (id, 所属源, 发布时间, 收藏)
+        let articles = [
+            ("a-old", "a", "2024-01-01T00:00:00+00:00", false),
+            ("a-starred", "a", "2024-01-02T00:00:00+00:00", true),
+            ("a-mid", "a", "2024-01-03T00:00:00+00:00", false),
+            ("a-new", "a", "2024-01-04T00:00:00+00:00", false),
+            ("b-only", "b", "2024-01-01T00:00:00+00:00", false),
+        ];
+        for (id, feed_id, published_at, is_starred) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, guid, published_at, is_starred, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(feed_id)
+            .bind(id)
+            .bind(id)
+            .bind(published_at)
+            .bind(is_starred)
+            .bind(&now)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let preview = RssService::prune_articles(&db, 1, true).await.unwrap();
+        assert!(preview.dry_run);
+        let preview_ids: Vec<&str> = preview.sample.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(preview_ids, vec!["a-old", "a-mid"]);
+        assert_eq!(RssService::get_articles(&db, None, None, None, None, None, None).await.unwrap().len(), 5);
+
+        let report = RssService::prune_articles(&db, 1, false).await.unwrap();
+        assert_eq!(report.count, 2);
+        let ids: Vec<String> = sqlx::query_scalar("SELECT id FROM rss_articles ORDER BY id")
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        assert_eq!(ids, vec!["a-new", "a-starred", "b-only"]);
+    }
+
+    #[tokio::test]
+    async fn test_deduplicate_all_articles_dry_run() {
+        let (db, _db_dir) = setup_test_db().await;
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        let articles = [
+            ("keep", "https://example.com/post?utm_source=rss", "2024-01-01T00:00:00+00:00"),
+            ("dup", "https://www.example.com/post", "2024-01-02T00:00:00+00:00"),
+            ("other", "https://example.com/other", "2024-01-03T00:00:00+00:00"),
+        ];
+        for (id, link, created_at) in articles {
+            sqlx::query(
+                "INSERT INTO rss_articles (id, feed_id, title, link, guid, created_at) VALUES (?, 'f', ?, ?, ?, ?)"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(link)
+            .bind(id)
+            .bind(created_at)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        RssService::backfill_link_keys(&db).await.unwrap();
+
+        let preview = RssService::deduplicate_all_articles(&db, true).await.unwrap();
+        assert!(preview.dry_run);
+        assert_eq!(preview.count, 1);
+        assert_eq!(preview.sample[0].id, "dup");
+        assert_eq!(RssService::get_articles(&db, None, None, None, None, None, None).await.unwrap().len(), 3);
+
+        let report = RssService::deduplicate_all_articles(&db, false).await.unwrap();
+        assert!(!report.dry_run);
+        assert_eq!(report.count, 1);
+        let ids: Vec<String> = sqlx::query_scalar("SELECT id FROM rss_articles ORDER BY id")
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        assert_eq!(ids, vec!["keep", "other"]);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_added_feed_takes_metadata_from_first_fetch() {
+        use crate::models::BulkFeedEntry;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let (db, _db_dir) = setup_test_db().await;
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let body = r#"<?xml version="1.0"?><rss version="2.0"><channel><title>Real Title</title><link>https://real.example.com/</link><description>About things</description><item><title>Post</title><guid>1</guid><description>Hello</description></item></channel></rss>"#;
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let url = format!("http://{}/rss", addr);
+        let results = RssService::add_feeds_bulk(&db, vec![BulkFeedEntry { url: url.clone(), category: None }])
+            .await
+            .unwrap();
+        let feed_id = results[0].feed.as_ref().unwrap().id.clone();
+        assert_eq!(RssService::get_feed(&db, &feed_id).await.unwrap().title, url);
+
+        RssService::refresh_feed_diff(&db, feed_id.clone()).await.unwrap();
+        let feed = RssService::get_feed(&db, &feed_id).await.unwrap();
+        assert_eq!(feed.title, "Real Title");
+        assert_eq!(feed.description.as_deref(), Some("About things"));
+        assert_eq!(feed.website_url.as_deref(), Some("https://real.example.com/"));
+
+        // 之后的刷新不覆盖用户修改的标题（提前上次刷新时间以跳过刷新间隔限制）
+        sqlx::query("UPDATE rss_feeds SET title = ?, last_updated = '2000-01-01T00:00:00+00:00' WHERE id = ?")
+            .bind(&url)
+            .bind(&feed_id)
+            .execute(&db)
+            .await
+            .unwrap();
+        RssService::refresh_feed_diff(&db, feed_id.clone()).await.unwrap();
+        assert_eq!(RssService::get_feed(&db, &feed_id).await.unwrap().title, url);
+    }
+
+    #[tokio::test]
+    async fn test_read_time_uses_saved_content() {
+        use crate::parser::ParsedEntry;
+        use crate::utils;
+
+        let (db, _db_dir) = setup_test_db().await;
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+        RssService::set_feed_max_content(&db, "f".to_string(), Some(1024)).await.unwrap();
+
+        // 源中的正文约10分钟，截断后只保存了一小部分
+        let entries = vec![ParsedEntry {
+            id: "1".to_string(),
+            title: Some("Long".to_string()),
+            content: Some(format!("<p>{}</p>", "word ".repeat(2000))),
+            ..Default::default()
+        }];
+        RssService::save_articles(&db, "f", &entries, &chrono::Utc::now()).await.unwrap();
+
+        let (content, read_time): (String, Option<String>) =
+            sqlx::query_as("SELECT content, read_time FROM rss_articles WHERE guid = '1'")
+                .fetch_one(&db)
+                .await
+                .unwrap();
+        assert_eq!(read_time.as_deref(), Some(utils::estimate_read_time(&content).as_str()));
+        assert_ne!(read_time.as_deref(), Some("10 min read"));
+    }
+
+
+    #[tokio::test]
+    async fn test_sqlite_storage() {
+        use crate::parser::ParsedEntry;
+        use crate::storage::Storage;
+
+        // 只依赖Storage的代码
+        async fn count_articles<S: Storage>(storage: &S, feed_id: &str) -> usize {
+            storage
+                .get_articles(Some(feed_id.to_string()), None, None, None, None, None)
+                .await
+                .unwrap()
+                .len()
+        }
+
+        let (db, _db_dir) = setup_test_db().await;
+        RssService::set_teaser_threshold(&db, 0).await.unwrap();
+        insert_test_feed(&db, "f", "https://example.com/rss").await;
+
+        assert_eq!(Storage::get_feeds(&db, true).await.unwrap().len(), 1);
+        assert_eq!(Storage::get_feed(&db, "f").await.unwrap().title, "Feed");
+
+        let entries = vec![ParsedEntry {
+            id: "1".to_string(),
+            title: Some("Hello".to_string()),
+            content: Some("<p>body</p>".to_string()),
+            ..Default::default()
+        }];
+        let saved = Storage::save_articles(&db, "f", &entries, &chrono::Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(saved, 1);
+        assert_eq!(count_articles(&db, "f").await, 1);
+
+        Storage::delete_feed(&db, "f".to_string()).await.unwrap();
+        assert!(Storage::get_feeds(&db, true).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rss_service_with_memory_storage() {
+        use crate::error::{AppError, AppResult};
+        use crate::models::{ArticleSort, RssArticle, RssFeed};
+        use crate::parser::ParsedEntry;
+        use crate::storage::Storage;
+        use chrono::{DateTime, Utc};
+        use std::sync::Mutex;
+
+        // 不使用数据库的存储，只保存在内存中
+        #[derive(Default)]
+        struct MemoryStorage {
+            feeds: Mutex<Vec<RssFeed>>,
+            articles: Mutex<Vec<RssArticle>>,
+        }
+
+        impl Storage for MemoryStorage {
+            async fn get_feeds(&self, include_inactive: bool) -> AppResult<Vec<RssFeed>> {
+                let feeds = self.feeds.lock().unwrap();
+                Ok(feeds.iter().filter(|f| include_inactive || f.is_active).cloned().collect())
+            }
+
+            async fn get_feed(&self, feed_id: &str) -> AppResult<RssFeed> {
+                let feeds = self.feeds.lock().unwrap();
+                feeds
+                    .iter()
+                    .find(|f| f.id == feed_id)
+                    .cloned()
+                    .ok_or_else(|| AppError::feed_not_found(feed_id))
+            }
+
+            async fn get_articles(
+                &self,
+                feed_id: Option<String>,
+                limit: Option<i32>,
+                offset: Option<i32>,
+                _sort: Option<ArticleSort>,
+                is_read: Option<bool>,
+                is_starred: Option<bool>,
+            ) -> AppResult<Vec<RssArticle>> {
+                let articles = self.articles.lock().unwrap();
+                Ok(articles
+                    .iter()
+                    .filter(|a| feed_id.as_ref().is_none_or(|id| &a.feed_id == id))
+                    .filter(|a| is_read.is_none_or(|r| a.is_read == r))
+                    .filter(|a| is_starred.is_none_or(|s| a.is_starred == s))
+                    .skip(offset.unwrap_or(0) as usize)
+                    .take(limit.unwrap_or(50) as usize)
+                    .cloned()
+                    .collect())
+            }
+
+            async fn save_articles(
+                &self,
+                feed_id: &str,
+                entries: &[ParsedEntry],
+                now: &DateTime<Utc>,
+            ) -> AppResult<i32> {
+                let mut articles = self.articles.lock().unwrap();
+                let mut saved = 0;
+                for entry in entries {
+                    if articles.iter().any(|a| a.feed_id == feed_id && a.guid.as_deref() == Some(entry.id.as_str())) {
+                        continue;
+                    }
+                    articles.push(RssArticle {
+                        id: format!("{}-{}", feed_id, entry.id),
+                        feed_id: feed_id.to_string(),
+                        title: entry.title.clone().unwrap_or_default(),
+                        link: entry.link.clone(),
+                        description: entry.summary.clone(),
+                        content: entry.content.clone(),
+                        author: entry.author.clone(),
+                        published_at: entry.published,
+                        updated_at: entry.updated,
+                        guid: Some(entry.id.clone()),
+                        is_read: false,
+                        is_starred: false,
+                        read_time: None,
+                        reader_prefs: None,
+                        read_at: None,
+                        word_count: None,
+                        duplicate_of: None,
+                        image_url: entry.image_url.clone(),
+                        created_at: *now,
+                    });
+                    saved += 1;
+                }
+                Ok(saved)
+            }
+
+            async fn delete_feed(&self, feed_id: String) -> AppResult<String> {
+                let mut feeds = self.feeds.lock().unwrap();
+                let before = feeds.len();
+                feeds.retain(|f| f.id != feed_id);
+                if feeds.len() == before {
+                    return Err(AppError::feed_not_found(&feed_id));
+                }
+                self.articles.lock().unwrap().retain(|a| a.feed_id != feed_id);
+                Ok("RSS feed deleted successfully".to_string())
+            }
+        }
+
+        let feed = |id: &str, is_active: bool| -> RssFeed {
+            serde_json::from_value(serde_json::json!({
+                "id": id,
+                "title": id,
+                "url": format!("https://example.com/{}", id),
+                "is_active": is_active,
+                "alternate_urls": [],
+                "title_strip_is_regex": false,
+                "requires_render": false,
+                "unread_count": 0,
+                "consecutive_failures": 0,
+                "created_at": "2024-01-01T00:00:00Z",
+                "updated_at": "2024-01-01T00:00:00Z"
+            }))
+            .unwrap()
+        };
+        let storage = MemoryStorage::default();
+        storage.feeds.lock().unwrap().extend([feed("a", true), feed("b", false)]);
+
+        assert_eq!(RssService::get_feeds(&storage, false).await.unwrap().len(), 1);
+        assert_eq!(RssService::get_feeds(&storage, true).await.unwrap().len(), 2);
+        assert!(RssService::get_feed(&storage, "missing").await.is_err());
+
+        let entries: Vec<ParsedEntry> = ["1", "2"]
+            .iter()
+            .map(|id| ParsedEntry {
+                id: id.to_string(),
+                title: Some(format!("Post {}", id)),
+                ..Default::default()
+            })
+            .collect();
+        let now = chrono::Utc::now();
+        assert_eq!(RssService::save_articles(&storage, "a", &entries, &now).await.unwrap(), 2);
+        assert_eq!(RssService::save_articles(&storage, "a", &entries, &now).await.unwrap(), 0);
+        let articles = RssService::get_articles(&storage, Some("a".to_string()), Some(1), None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(articles.len(), 1);
+
+        RssService::delete_feed(&storage, "a".to_string()).await.unwrap();
+        assert!(RssService::get_articles(&storage, None, None, None, None, None, None)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(RssService::get_feed(&storage, "b").await.unwrap().title, "b");
+    }
 }
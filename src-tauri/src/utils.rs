@@ -1,5 +1,10 @@
 use crate::error::{AppError, AppResult};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use regex::Regex;
+use scraper::{ElementRef, Selector};
 use std::path::PathBuf;
+use url::Url;
 
 /// 获取应用数据目录路径
 pub fn get_app_data_dir() -> AppResult<PathBuf> {
@@ -20,6 +25,12 @@ pub fn get_database_url() -> AppResult<String> {
     Ok(format!("sqlite:{}", db_path.display()))
 }
 
+/// 获取自动备份目录
+pub fn get_backup_dir() -> AppResult<PathBuf> {
+    let app_data_dir = get_app_data_dir()?;
+    Ok(app_data_dir.join("backups"))
+}
+
 /// 获取日志路径
 pub fn get_log_path() -> AppResult<PathBuf> {
     let app_data_dir = get_app_data_dir()?;
@@ -32,3 +43,347 @@ pub fn ensure_app_data_dir() -> AppResult<PathBuf> {
     std::fs::create_dir_all(&app_data_dir)?;
     Ok(app_data_dir)
 }
+
+/// 规范化RSS源URL，用于判断是否重复订阅
+/// 忽略协议、www前缀、片段和末尾斜杠，主机名不区分大小写
+pub fn normalize_feed_url(url: &str) -> String {
+    let url = url.trim();
+    let mut parsed = match Url::parse(url) {
+        Ok(parsed) => parsed,
+        Err(_) => return url.to_lowercase(),
+    };
+    parsed.set_fragment(None);
+
+    let host = parsed.host_str().unwrap_or_default();
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    let port = parsed.port().map(|p| format!(":{}", p)).unwrap_or_default();
+    let path = parsed.path().trim_end_matches('/');
+    let query = parsed.query().map(|q| format!("?{}", q)).unwrap_or_default();
+
+    format!("{}{}{}{}", host, port, path, query)
+}
+
+/// 规范化文章链接，用于判断同一RSS源中以及跨RSS源的重复文章
+/// 在RSS源URL规范化的基础上（主机名小写、去掉协议、www前缀和末尾斜杠）
+/// 再去除utm_*、fbclid等跟踪参数，其余查询参数按原顺序保留，片段（#...）不参与比较
+pub fn normalize_article_link(link: &str) -> String {
+    let mut parsed = match Url::parse(link.trim()) {
+        Ok(parsed) => parsed,
+        Err(_) => return normalize_feed_url(link),
+    };
+
+    let params: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(key, _)| !is_tracking_param(key))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    if params.is_empty() {
+        parsed.set_query(None);
+    } else {
+        parsed.query_pairs_mut().clear().extend_pairs(params);
+    }
+
+    normalize_feed_url(parsed.as_str())
+}
+
+/// 是否为广告和邮件营销使用的跟踪参数
+fn is_tracking_param(key: &str) -> bool {
+    key.starts_with("utm_")
+        || matches!(key, "fbclid" | "gclid" | "mc_cid" | "mc_eid" | "igshid" | "_hsenc" | "_hsmi")
+}
+
+/// 解析IANA时区名称，如 Asia/Shanghai
+pub fn parse_timezone(tz: &str) -> AppResult<Tz> {
+    tz.trim()
+        .parse::<Tz>()
+        .map_err(|_| AppError::validation(format!("无效的时区: {}", tz)))
+}
+
+/// 解析不带时区信息的时间并按UTC返回，带有时区或偏移的时间返回None
+/// 支持ISO 8601形式（日期和时间之间可用空格）、只有日期的形式，以及省略时区的RFC 2822形式
+pub fn parse_naive_datetime(s: &str) -> Option<DateTime<Utc>> {
+    let s = s.trim();
+    // RFC 2822中的星期可能与日期不符，直接忽略
+    let without_weekday = s.split_once(", ").map_or(s, |(_, rest)| rest);
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+        .or_else(|| NaiveDateTime::parse_from_str(without_weekday, "%d %b %Y %H:%M:%S").ok())
+        .or_else(|| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok().map(|date| date.and_time(NaiveTime::MIN)))
+        .map(|dt| dt.and_utc())
+}
+
+/// 将按UTC解析的不带时区的时间重新解释为指定时区的本地时间
+/// 夏令时切换导致本地时间不存在时保持原值
+pub fn localize_naive(dt: DateTime<Utc>, tz: Tz) -> DateTime<Utc> {
+    tz.from_local_datetime(&dt.naive_utc())
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+        .unwrap_or(dt)
+}
+
+/// 计算指定时区中“今天”的起止时间（UTC表示），结束时间为次日零点
+/// 零点因夏令时切换不存在时，取当天第一个存在的整点
+pub fn local_day_bounds<T: TimeZone>(now: DateTime<Utc>, tz: &T) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start_of = |date: chrono::NaiveDate| {
+        (0..24)
+            .filter_map(|hour| NaiveTime::from_hms_opt(hour, 0, 0))
+            .find_map(|time| tz.from_local_datetime(&date.and_time(time)).earliest())
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|| Utc.from_utc_datetime(&date.and_time(NaiveTime::MIN)))
+    };
+
+    let today = now.with_timezone(tz).date_naive();
+    let tomorrow = today.succ_opt().unwrap_or(today);
+    (start_of(today), start_of(tomorrow))
+}
+
+/// 按每分钟200词估算阅读时间，不足一分钟按一分钟计，没有内容时返回None
+pub fn format_read_time(words: u32) -> Option<String> {
+    if words == 0 {
+        return None;
+    }
+    Some(format!("{} min read", words.div_ceil(200)))
+}
+
+/// 去除HTML标签后统计字数并估算阅读时间，如 "5 min read"，没有内容时返回空字符串
+pub fn estimate_read_time(text: &str) -> String {
+    format_read_time(count_words(text)).unwrap_or_default()
+}
+
+/// 将HTML中img[src]、img[srcset]和a[href]的相对地址按base解析为绝对地址
+/// 没有需要改写的地址时原样返回
+pub fn resolve_relative_urls(html: &str, base: &Url) -> String {
+    let mut fragment = scraper::Html::parse_fragment(html);
+    let selector = match scraper::Selector::parse("img[src], img[srcset], a[href]") {
+        Ok(selector) => selector,
+        Err(_) => return html.to_string(),
+    };
+    let node_ids: Vec<_> = fragment.select(&selector).map(|element| element.id()).collect();
+
+    let mut changed = false;
+    for node_id in node_ids {
+        let Some(mut node) = fragment.tree.get_mut(node_id) else {
+            continue;
+        };
+        let scraper::Node::Element(element) = node.value() else {
+            continue;
+        };
+        for (name, value) in element.attrs.iter_mut() {
+            let resolved = match &*name.local {
+                "src" | "href" => resolve_url(value, base),
+                "srcset" => resolve_srcset(value, base),
+                _ => None,
+            };
+            if let Some(resolved) = resolved {
+                *value = resolved.into();
+                changed = true;
+            }
+        }
+    }
+
+    if changed {
+        fragment.root_element().inner_html()
+    } else {
+        html.to_string()
+    }
+}
+
+/// 解析单个地址，已是绝对地址、锚点或无法解析时返回None
+fn resolve_url(value: &str, base: &Url) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() || value.starts_with('#') || Url::parse(value).is_ok() {
+        return None;
+    }
+    base.join(value).ok().map(String::from)
+}
+
+/// 解析srcset中每个候选图片的地址，保留宽度或像素密度描述
+fn resolve_srcset(value: &str, base: &Url) -> Option<String> {
+    let mut changed = false;
+    let candidates: Vec<String> = value
+        .split(',')
+        .map(|candidate| {
+            let candidate = candidate.trim();
+            let (url, descriptor) = candidate.split_once(char::is_whitespace).unwrap_or((candidate, ""));
+            match resolve_url(url, base) {
+                Some(resolved) => {
+                    changed = true;
+                    format!("{} {}", resolved, descriptor.trim()).trim_end().to_string()
+                }
+                None => candidate.to_string(),
+            }
+        })
+        .collect();
+
+    changed.then(|| candidates.join(", "))
+}
+
+/// 返回HTML中第一张使用http(s)绝对地址的图片
+pub fn first_image_url(html: &str) -> Option<String> {
+    let fragment = scraper::Html::parse_fragment(html);
+    let selector = scraper::Selector::parse("img[src]").ok()?;
+    fragment
+        .select(&selector)
+        .filter_map(|element| element.value().attr("src"))
+        .filter_map(|src| Url::parse(src.trim()).ok())
+        .find(|url| url.scheme() == "http" || url.scheme() == "https")
+        .map(String::from)
+}
+
+/// 判断内容是否明显被截断：去除标签后以省略号或“阅读全文”之类的链接文字结尾
+pub fn looks_truncated(html: &str) -> bool {
+    let fragment = scraper::Html::parse_fragment(html);
+    let text: String = fragment.root_element().text().collect();
+    let text = text
+        .trim_end()
+        .trim_end_matches(['»', '›', '→', '>', ')', ']', '）', '】'])
+        .trim_end()
+        .to_lowercase();
+
+    const ENDINGS: [&str; 10] = [
+        "…",
+        "...",
+        "read more",
+        "continue reading",
+        "read the full article",
+        "read full article",
+        "阅读全文",
+        "查看全文",
+        "阅读更多",
+        "继续阅读",
+    ];
+    ENDINGS.iter().any(|ending| text.ends_with(ending))
+}
+
+/// 统计HTML或纯文本的字数：去除标签后按空白分词，中日韩文字每个字计为一个词
+pub fn count_words(html: &str) -> u32 {
+    let fragment = scraper::Html::parse_fragment(html);
+    let mut count = 0;
+    let mut in_word = false;
+
+    for text in fragment.root_element().text() {
+        for c in text.chars() {
+            let is_cjk = matches!(c,
+                '\u{3040}'..='\u{30ff}'
+                | '\u{3400}'..='\u{4dbf}'
+                | '\u{4e00}'..='\u{9fff}'
+                | '\u{ac00}'..='\u{d7af}');
+            if is_cjk {
+                count += 1;
+                in_word = false;
+            } else if c.is_alphanumeric() {
+                if !in_word {
+                    count += 1;
+                    in_word = true;
+                }
+            } else if c.is_whitespace() {
+                in_word = false;
+            }
+        }
+        // 相邻文本节点之间视为分隔
+        in_word = false;
+    }
+
+    count
+}
+
+/// 统计HTML或纯文本去除标签和首尾空白后的字符数
+pub fn text_char_count(html: &str) -> usize {
+    scraper::Html::parse_fragment(html)
+        .root_element()
+        .text()
+        .map(|text| text.trim().chars().count())
+        .sum()
+}
+
+/// 提取正文文本时跳过的导航、广告、页眉页脚和脚本等元素
+const BOILERPLATE_SELECTOR: &str =
+    "nav, aside, footer, header, .ad, .advertisement, [role='navigation'], script, style, noscript";
+
+/// 提取元素的文本，跳过其中的导航、广告等非正文子树
+pub fn clean_element_text(element: ElementRef) -> String {
+    let mut parts = Vec::new();
+    match Selector::parse(BOILERPLATE_SELECTOR) {
+        Ok(boilerplate) => collect_clean_text(element, &boilerplate, &mut parts),
+        Err(_) => parts.extend(element.text()),
+    }
+    parts.join(" ").trim().to_string()
+}
+
+/// 判断元素本身或其祖先是否属于导航、广告等非正文子树
+pub fn in_boilerplate(element: ElementRef) -> bool {
+    let Ok(boilerplate) = Selector::parse(BOILERPLATE_SELECTOR) else {
+        return false;
+    };
+    std::iter::once(element)
+        .chain(element.ancestors().filter_map(ElementRef::wrap))
+        .any(|node| boilerplate.matches(&node))
+}
+
+fn collect_clean_text<'a>(element: ElementRef<'a>, boilerplate: &Selector, parts: &mut Vec<&'a str>) {
+    for child in element.children() {
+        if let Some(text) = child.value().as_text() {
+            parts.push(text);
+        } else if let Some(child_element) = ElementRef::wrap(child) {
+            if !boilerplate.matches(&child_element) {
+                collect_clean_text(child_element, boilerplate, parts);
+            }
+        }
+    }
+}
+
+/// 编译标题前缀规则：从标题开头匹配，连同其后的空白一起去除
+/// 规则默认按普通文本匹配，is_regex为true时按正则表达式匹配
+pub fn build_title_strip_regex(pattern: &str, is_regex: bool) -> AppResult<Regex> {
+    let pattern_regex = if is_regex {
+        pattern.to_string()
+    } else {
+        regex::escape(pattern)
+    };
+    Regex::new(&format!("^(?:{})\\s*", pattern_regex))
+        .map_err(|e| AppError::validation(format!("无效的标题前缀规则 '{}': {}", pattern, e)))
+}
+
+/// 将文本截断到不超过max_bytes字节，截断位置落在字符边界上
+pub fn truncate_to_bytes(text: &str, max_bytes: usize) -> &str {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// 将HTML连同截断标记截断到不超过max_bytes字节
+/// 截断位置不会落在标签内部，离上限不远处有结束标签时在结束标签之后截断
+pub fn truncate_html(html: &str, max_bytes: usize, marker: &str) -> String {
+    if html.len() <= max_bytes {
+        return html.to_string();
+    }
+    let limit = max_bytes.saturating_sub(marker.len());
+    let mut truncated = truncate_to_bytes(html, limit);
+    if let Some(open) = truncated.rfind('<').filter(|&open| !truncated[open..].contains('>')) {
+        truncated = &truncated[..open];
+    }
+    // 在结束标签之后截断可以保持最后一个元素完整，但不为此丢弃超过一半的内容
+    let last_close_tag = truncated
+        .rfind("</")
+        .and_then(|start| truncated[start..].find('>').map(|end| start + end + 1));
+    if let Some(end) = last_close_tag.filter(|&end| end >= limit / 2) {
+        truncated = &truncated[..end];
+    }
+    format!("{}{}", truncated, marker)
+}
+
+/// 去除标题前缀，去除后为空时保留原标题
+pub fn strip_title_prefix(title: &str, regex: &Regex) -> Option<String> {
+    let stripped = regex.replace(title, "");
+    if stripped.len() == title.len() || stripped.trim().is_empty() {
+        None
+    } else {
+        Some(stripped.into_owned())
+    }
+}